@@ -8,16 +8,27 @@ fn main() {
                 name: "Action0".to_string(),
                 hotkey: Some('a'),
                 exp: Some("Action0 Explanation. This Has Been Assigned To A Hotkey.".to_string()),
+                badges: Vec::new(),
+                confirm_phrase: None,
+                global_hotkey: None,
+                url: None,
+                checkable: false,
             },
             Item::Action {
                 name: "Action1".to_string(),
                 hotkey: None,
                 exp: Some("Action1 Explanation. This Has No Hotkey.".to_string()),
+                badges: Vec::new(),
+                confirm_phrase: None,
+                global_hotkey: None,
+                url: None,
+                checkable: false,
             },
             Item::SubMenu {
                 name: "Submenu0".to_string(),
                 hotkey: Some('s'),
                 exp: Some("Submenu0 explanation.".to_string()),
+                badges: Vec::new(),
                 items: vec![
                     Item::Action {
                         name: "Sub Action0".to_string(),
@@ -26,6 +37,11 @@ fn main() {
                             "Sub Action0 Explanation. This Has Been Assigned To A Hotkey."
                                 .to_string(),
                         ),
+                        badges: Vec::new(),
+                        confirm_phrase: None,
+                        global_hotkey: None,
+                        url: None,
+                        checkable: false,
                     },
                     Item::Action {
                         name: "Sub Action1".to_string(),
@@ -34,21 +50,37 @@ fn main() {
                             "Sub Action1 Explanation. This Has Been Assigned To A Hotkey."
                                 .to_string(),
                         ),
+                        badges: Vec::new(),
+                        confirm_phrase: None,
+                        global_hotkey: None,
+                        url: None,
+                        checkable: false,
                     },
                     Item::SubMenu {
                         name: "Deepermenu0".to_string(),
                         hotkey: Some('d'),
                         exp: Some("Deepermenu0 Explanation.".to_string()),
+                        badges: Vec::new(),
                         items: vec![
                             Item::Action {
                                 name: "Deeper Action0".to_string(),
                                 hotkey: Some('f'),
                                 exp: None,
+                                badges: Vec::new(),
+                                confirm_phrase: None,
+                                global_hotkey: None,
+                                url: None,
+                                checkable: false,
                             },
                             Item::Action {
                                 name: "Deeper Action1".to_string(),
                                 hotkey: Some('g'),
                                 exp: Some("Deeper Action1 Explanation.".to_string()),
+                                badges: Vec::new(),
+                                confirm_phrase: None,
+                                global_hotkey: None,
+                                url: None,
+                                checkable: false,
                             },
                         ],
                     },
@@ -58,31 +90,58 @@ fn main() {
                 name: "Bool0".to_string(),
                 hotkey: Some('b'),
                 exp: Some("Bool0 Explanation.".to_string()),
+                badges: Vec::new(),
+                current: None,
             },
             Item::Char {
                 name: "Char0".to_string(),
                 hotkey: Some('c'),
                 exp: Some("Char0 Explanation.".to_string()),
+                badges: Vec::new(),
+                skippable: false,
+                current: None,
+                allowed: None,
+                ignore_case: false,
             },
             Item::String {
                 name: "String0".to_string(),
                 hotkey: Some('t'),
                 exp: Some("String0 Explanation.".to_string()),
+                badges: Vec::new(),
+                skippable: false,
+                required: false,
+                history: vec![],
+                current: None,
             },
             Item::F32 {
                 name: "F32".to_string(),
                 hotkey: Some('f'),
                 exp: Some("F32 Explanation.".to_string()),
+                badges: Vec::new(),
+                skippable: false,
+                current: None,
+                confirm: false,
+                step: None,
             },
             Item::I32 {
                 name: "I32".to_string(),
                 hotkey: Some('i'),
                 exp: Some("I32 Explanation.".to_string()),
+                badges: Vec::new(),
+                skippable: false,
+                current: None,
+                confirm: false,
+                step: None,
             },
             Item::U32 {
                 name: "U32".to_string(),
                 hotkey: Some('u'),
                 exp: Some("U32 Explanation.".to_string()),
+                badges: Vec::new(),
+                skippable: false,
+                current: None,
+                confirm: false,
+                step: None,
             },
         ],
         exp: Some("My Main Menu Explanation.".to_string()),
@@ -0,0 +1,76 @@
+//! Menus of `clap` subcommands. Enabled by the `clap` feature.
+//!
+//! A program invoked without arguments can offer its subcommands in a `Menu` instead of printing usage. Subcommands with their own subcommands become `SubMenu`s, and the about text of each becomes its explanation.
+//! ```
+//! use clap::Command;
+//! use rushterm::{Item, Menu, RunOptions, Script};
+//!
+//! let command = Command::new("git")
+//!     .about("The stupid content tracker.")
+//!     .subcommand(Command::new("status").about("Shows the working tree status."))
+//!     .subcommand(
+//!         Command::new("remote")
+//!             .subcommand(Command::new("add"))
+//!             .subcommand(Command::new("remove")),
+//!     );
+//! let menu = Menu::from_clap(&command);
+//! assert_eq!(menu.items.len(), 2);
+//! assert_eq!(menu.items[0].exp(), Some("Shows the working tree status."));
+//! assert!(matches!(&menu.items[1], Item::SubMenu { items, .. } if items.len() == 2));
+//!
+//! let opts = RunOptions {
+//!     script: Some(Script::new(["Down", "Enter", "Down", "Enter"])),
+//!     ..RunOptions::default()
+//! };
+//! assert_eq!(Menu::run_clap(&command, &opts).unwrap(), ["remote", "remove"]);
+//! ```
+
+use crate::{Item, Menu, MenuError, RunOptions};
+use ::clap::Command;
+
+impl Menu {
+    /// Builds a `Menu` of the subcommands of `command`, named after it. Hidden subcommands are left out.
+    pub fn from_clap(command: &Command) -> Menu {
+        Menu {
+            name: command.get_name().to_string(),
+            exp: command.get_about().map(|f| f.to_string()),
+            items: items(command),
+            esc: true,
+        }
+    }
+    /// Runs the `Menu` of the subcommands of `command`, returning the names of the chosen subcommand and its parents, like `["remote", "add"]`. Passing them after the program name to `Command::get_matches_from()` parses them as if they were typed. Errors are returned in `Err()` as by `Menu::run_with()`.
+    pub fn run_clap(command: &Command, opts: &RunOptions) -> Result<Vec<String>, MenuError> {
+        let selection = Menu::from_clap(command).run_with(opts)?;
+        Ok(selection.path.into_iter().skip(1).collect())
+    }
+}
+fn items(command: &Command) -> Vec<Item> {
+    command
+        .get_subcommands()
+        .filter(|f| !f.is_hide_set())
+        .map(|sub| {
+            let name = sub.get_name().to_string();
+            let exp = sub.get_about().map(|f| f.to_string());
+            if sub.has_subcommands() {
+                Item::SubMenu {
+                    name,
+                    hotkey: None,
+                    exp,
+                    badges: Vec::new(),
+                    items: items(sub),
+                }
+            } else {
+                Item::Action {
+                    name,
+                    hotkey: None,
+                    exp,
+                    badges: Vec::new(),
+                    confirm_phrase: None,
+                    global_hotkey: None,
+                    url: None,
+                    checkable: false,
+                }
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,1047 @@
+//! A terse text format to define a `Menu`. Enabled by the `dsl` feature.
+//!
+//! Each line declares one `Item` with its kind, name, optional hotkey in parentheses and optional explanation in quotes. The first line declares the `Menu` itself, with the `no_esc` attribute if `Esc` shouldn't exit it. Names containing spaces should be quoted. Quoted text escapes `"` and `\` by a backslash, and line breaks as `\n`. `SubMenu` items are opened by `{` at the end of the line and closed by a `}` line. Lines starting with `#` are comments.
+//! ```text
+//! menu "My Main Menu" "My Main Menu Explanation."
+//! action Action0 (a) "Action0 Explanation."
+//! submenu Submenu0 (s) "Submenu0 explanation." {
+//!     action "Sub Action0" (a)
+//!     i32 Count (c)
+//! }
+//! bool Bool0 (b)
+//! ```
+//! Item kinds are `action`, `submenu`, `bool`, `char`, `string`, `secret`, `f32`, `f64`, `i32`, `u32`, `bytesize`, `color`, `counter`, `group`, `label`, the number lists `list<f32>`, `list<f64>`, `list<i32>` and `list<u32>`, and `json`.
+//!
+//! Attributes follow the explanation. Every kind but `label` takes `badge="NEW"`, repeated for each badge, shown in the color of the theme, or in the color after a dot, like `badge.dark_red="OLD"`, `badge.#ff8800="BETA"` or `badge.ansi_208="BETA"`. `current="dark"` sets the current value of the input kinds that have one. The other attributes by kind:
+//! - `action`: `checkable`, `confirm="deploy"`, the phrase to type before it runs, `global_hotkey=q`, selecting it from any level, and `url="https://example.com"`, the link of its name.
+//! - `submenu`: only `badge`.
+//! - `bool`: `current`.
+//! - `char`: `skippable`, `current`, `allowed=NSEW` or `allowed="N S"`, and `ignore_case`.
+//! - `string`: `skippable`, `current`, `required`, and `history="localhost"`, repeated for each entry from the oldest.
+//! - `secret`: `skippable` and `required`.
+//! - `json`: `skippable`.
+//! - `f32`, `i32` and `u32`: `skippable`, `current`, `confirm`, and `step=0.5`, the step of `Up` and `Down` at the prompt.
+//! - `f64`: the same, and `decimals=2` and `round`.
+//! - `bytesize`, `color` and the number lists: `skippable` and `current`.
+//! - `counter`: `min=0`, `max=100`, `step=1` and `initial=0`, which default to these values. `initial` defaults to `min` if only `min` is set.
+//! - `group`: `collapsed`.
+//! ```text
+//! f64 Price (p) "Price in dollars." decimals=2 round skippable
+//! ```
+//! `Menu::to_dsl()` writes a `Menu` back in this format, and `Menu::to_json()` in JSON with the `serde` feature.
+
+use crate::{Color, Item, Menu, NumberKind, Rgb};
+use std::{convert::TryFrom, error::Error, fmt, str::FromStr};
+
+/// Error while parsing the menu text. Points out the line of the problem.
+#[derive(Debug, PartialEq)]
+pub struct DslError {
+    /// Line number, starting from 1.
+    pub line: usize,
+    /// Description of the problem.
+    pub message: String,
+}
+impl fmt::Display for DslError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+impl Error for DslError {}
+
+/// Declaration of a single line.
+struct Line {
+    kind: String,
+    name: String,
+    hotkey: Option<char>,
+    exp: Option<String>,
+    attrs: Vec<String>,
+    opens: bool,
+}
+/// A `SubMenu` waiting for its closing brace.
+struct Open {
+    line: usize,
+    name: String,
+    hotkey: Option<char>,
+    exp: Option<String>,
+    badges: Vec<(String, Color)>,
+    items: Vec<Item>,
+}
+
+impl Menu {
+    /// Builds a `Menu` from the text format described in the `dsl` module.
+    /// ```
+    /// use rushterm::{dsl::DslError, Item, Menu, RunOptions, Value};
+    ///
+    /// let menu = Menu::from_dsl(
+    ///     r#"
+    /// menu Main
+    /// action Save (s)
+    /// submenu More (m) {
+    ///     submenu Network (n) {
+    ///         u32 Port (p) "Listening port."
+    ///     }
+    ///     action About
+    /// }
+    /// "#,
+    /// )
+    /// .unwrap();
+    /// let mut names = vec![];
+    /// menu.walk(|path, item| names.push((path.to_vec(), item.name().to_string())));
+    /// assert_eq!(names[3], (vec![1, 0, 0], "Port".to_string()));
+    /// assert_eq!(names[4], (vec![1, 1], "About".to_string()));
+    ///
+    /// let selection = menu
+    ///     .run_with_stdin_values(&RunOptions::default(), &["m", "n", "p", "8080"])
+    ///     .unwrap();
+    /// assert_eq!(selection.path, ["Main", "More", "Network", "Port"]);
+    /// assert_eq!(selection.value, Some(Value::U32(8080)));
+    ///
+    /// let unclosed = Menu::from_dsl("menu Main\nsubmenu More {\naction About\n");
+    /// assert!(matches!(unclosed, Err(DslError { line: 2, .. })));
+    /// ```
+    pub fn from_dsl(text: &str) -> Result<Menu, DslError> {
+        let mut menu: Option<Menu> = None;
+        let mut stack: Vec<Open> = vec![];
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let trimmed = raw.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let error = |message: &str| DslError {
+                line,
+                message: message.to_string(),
+            };
+            if trimmed == "}" {
+                let open = stack.pop().ok_or_else(|| error("unexpected `}`"))?;
+                let item = Item::SubMenu {
+                    name: open.name,
+                    hotkey: open.hotkey,
+                    exp: open.exp,
+                    badges: open.badges,
+                    items: open.items,
+                };
+                push_item(&mut menu, &mut stack, item, line)?;
+                continue;
+            }
+            let mut decl = parse_line(trimmed).map_err(|message| error(&message))?;
+            if decl.kind == "menu" {
+                if menu.is_some() {
+                    return Err(error("menu is already declared"));
+                }
+                if decl.hotkey.is_some() || decl.opens {
+                    return Err(error("menu takes only a name and an explanation"));
+                }
+                if let Some(attr) = decl.attrs.iter().find(|f| *f != "no_esc") {
+                    return Err(error(&format!("unknown attribute `{}`", attr)));
+                }
+                menu = Some(Menu {
+                    name: decl.name,
+                    exp: decl.exp,
+                    items: vec![],
+                    esc: decl.attrs.is_empty(),
+                });
+                continue;
+            }
+            let badges = take_badges(&mut decl.attrs).map_err(|message| error(&message))?;
+            if !decl.attrs.is_empty() && decl.opens {
+                return Err(error(&format!(
+                    "{} takes no attributes but `badge`",
+                    decl.kind
+                )));
+            }
+            if decl.opens {
+                if decl.kind != "submenu" {
+                    return Err(error("only submenu can be opened by `{`"));
+                }
+                if menu.is_none() {
+                    return Err(error("menu should be declared first"));
+                }
+                stack.push(Open {
+                    line,
+                    name: decl.name,
+                    hotkey: decl.hotkey,
+                    exp: decl.exp,
+                    badges,
+                    items: vec![],
+                });
+                continue;
+            }
+            let mut skippable = false;
+            let mut decimals = None;
+            let mut round = false;
+            let mut required = false;
+            let mut collapsed = false;
+            let mut confirm = false;
+            let mut allowed = None;
+            let mut ignore_case = false;
+            let mut checkable = false;
+            let mut current = None;
+            let mut history = vec![];
+            let mut confirm_phrase = None;
+            let mut global_hotkey = None;
+            let mut url = None;
+            let mut step = None;
+            let mut bounds = [None; 4];
+            for attr in &decl.attrs {
+                match attr.as_str() {
+                    "skippable" => skippable = true,
+                    "round" => round = true,
+                    "required" => required = true,
+                    "collapsed" => collapsed = true,
+                    "confirm" => confirm = true,
+                    "ignore_case" => ignore_case = true,
+                    "checkable" => checkable = true,
+                    attr if attr.starts_with("current=") => {
+                        current = Some(attr["current=".len()..].to_string())
+                    }
+                    attr if attr.starts_with("confirm=") => {
+                        confirm_phrase = Some(attr["confirm=".len()..].to_string())
+                    }
+                    attr if attr.starts_with("global_hotkey=") => {
+                        let mut chars = attr["global_hotkey=".len()..].chars();
+                        global_hotkey = match (chars.next(), chars.next()) {
+                            (Some(chr), None) => Some(chr),
+                            _ => return Err(error(
+                                "global_hotkey should be a single character like `global_hotkey=q`",
+                            )),
+                        }
+                    }
+                    attr if attr.starts_with("url=") => {
+                        url = Some(attr["url=".len()..].to_string())
+                    }
+                    attr if attr.starts_with("history=") => {
+                        history.push(attr["history=".len()..].to_string())
+                    }
+                    attr if attr.starts_with("allowed=") => {
+                        allowed = Some(attr["allowed=".len()..].chars().collect::<Vec<_>>())
+                    }
+                    attr if attr.starts_with("step=")
+                        && STEP_KINDS.contains(&decl.kind.as_str()) =>
+                    {
+                        step = Some(attr["step=".len()..].to_string())
+                    }
+                    attr if attr
+                        .split_once('=')
+                        .is_some_and(|(key, _)| COUNTER_ATTRS.contains(&key)) =>
+                    {
+                        let (key, value) = attr.split_once('=').expect("counter attribute");
+                        let slot = COUNTER_ATTRS.iter().position(|f| *f == key);
+                        bounds[slot.expect("counter attribute slot")] =
+                            Some(value.parse::<i64>().map_err(|_| {
+                                error(&format!("{} should be a number like `{}=1`", key, key))
+                            })?)
+                    }
+                    attr if attr.starts_with("decimals=") => {
+                        decimals =
+                            Some(attr["decimals=".len()..].parse().map_err(|_| {
+                                error("decimals should be a number like `decimals=2`")
+                            })?)
+                    }
+                    other => return Err(error(&format!("unknown attribute `{}`", other))),
+                }
+            }
+            if (decimals.is_some() || round) && decl.kind != "f64" {
+                return Err(error("`decimals` and `round` are only for f64"));
+            }
+            if required && decl.kind != "string" && decl.kind != "secret" {
+                return Err(error("`required` is only for string and secret"));
+            }
+            if collapsed && decl.kind != "group" {
+                return Err(error("`collapsed` is only for group"));
+            }
+            if (allowed.is_some() || ignore_case) && decl.kind != "char" {
+                return Err(error("`allowed` and `ignore_case` are only for char"));
+            }
+            if confirm && !["f32", "f64", "i32", "u32"].contains(&decl.kind.as_str()) {
+                return Err(error("`confirm` is only for f32, f64, i32 and u32"));
+            }
+            if current.is_some()
+                && !CURRENT_KINDS.contains(&decl.kind.as_str())
+                && list_kind(&decl.kind).is_none()
+            {
+                return Err(error("`current` is only for input items"));
+            }
+            if !history.is_empty() && decl.kind != "string" {
+                return Err(error("`history` is only for string"));
+            }
+            if (checkable || confirm_phrase.is_some() || global_hotkey.is_some() || url.is_some())
+                && decl.kind != "action"
+            {
+                return Err(error(
+                    "`checkable`, `confirm=`, `global_hotkey` and `url` are only for action",
+                ));
+            }
+            if bounds.iter().any(Option::is_some) && decl.kind != "counter" {
+                return Err(error(
+                    "`min`, `max` and `initial` are only for counter, and `step` for counter, f32, f64, i32 and u32",
+                ));
+            }
+            let (name, hotkey, exp) = (decl.name, decl.hotkey, decl.exp);
+            let item = match decl.kind.as_str() {
+                "action" => Item::Action {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    confirm_phrase,
+                    global_hotkey,
+                    url,
+                    checkable,
+                },
+                "submenu" => return Err(error("submenu should be opened by `{`")),
+                "bool" => Item::Bool {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    current,
+                },
+                "char" => Item::Char {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    skippable,
+                    current,
+                    allowed,
+                    ignore_case,
+                },
+                "string" => Item::String {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    skippable,
+                    required,
+                    history,
+                    current,
+                },
+                "secret" => Item::Secret {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    skippable,
+                    required,
+                },
+                "json" => Item::Json {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    skippable,
+                },
+                "f32" => Item::F32 {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    skippable,
+                    current,
+                    confirm,
+                    step: parse_step(step.as_deref()).map_err(|message| error(&message))?,
+                },
+                "f64" => Item::F64 {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    skippable,
+                    decimals,
+                    round,
+                    current,
+                    confirm,
+                    step: parse_step(step.as_deref()).map_err(|message| error(&message))?,
+                },
+                "i32" => Item::I32 {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    skippable,
+                    current,
+                    confirm,
+                    step: parse_step(step.as_deref()).map_err(|message| error(&message))?,
+                },
+                "u32" => Item::U32 {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    skippable,
+                    current,
+                    confirm,
+                    step: parse_step(step.as_deref()).map_err(|message| error(&message))?,
+                },
+                "bytesize" => Item::ByteSize {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    skippable,
+                    current,
+                },
+                "counter" => Item::Counter {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    min: bounds[0].unwrap_or(0),
+                    max: bounds[1].unwrap_or(100),
+                    step: bounds[2].unwrap_or(1),
+                    initial: bounds[3].or(bounds[0]).unwrap_or(0),
+                },
+                "group" => Item::Group {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    collapsed,
+                },
+                "label" if hotkey.is_some() || exp.is_some() || !badges.is_empty() => {
+                    return Err(error("label takes only a text"))
+                }
+                "label" => Item::Label { text: name },
+                "color" => Item::Color {
+                    name,
+                    hotkey,
+                    exp,
+                    badges,
+                    skippable,
+                    current,
+                },
+                other => match list_kind(other) {
+                    Some(kind) => Item::NumberList {
+                        name,
+                        hotkey,
+                        exp,
+                        badges,
+                        skippable,
+                        kind,
+                        current,
+                    },
+                    None => return Err(error(&format!("unknown item kind `{}`", other))),
+                },
+            };
+            if skippable
+                && matches!(
+                    item,
+                    Item::Action { .. }
+                        | Item::Bool { .. }
+                        | Item::Group { .. }
+                        | Item::Label { .. }
+                )
+            {
+                return Err(error("`skippable` is only for input items"));
+            }
+            push_item(&mut menu, &mut stack, item, line)?;
+        }
+        if let Some(open) = stack.pop() {
+            return Err(DslError {
+                line: open.line,
+                message: format!("submenu `{}` is not closed", open.name),
+            });
+        }
+        menu.ok_or(DslError {
+            line: text.lines().count().max(1),
+            message: "menu is not declared".to_string(),
+        })
+    }
+}
+impl Menu {
+    /// Writes the `Menu` in the text format described in the `dsl` module, which `from_dsl()` parses back to an equal `Menu`. `LazySubMenu`s are written as `submenu`s with the `Item`s their provider builds now. Returns a `DslError` at the line of the first `Item` with a field the format can't express, like a control character as a hotkey, rather than dropping it.
+    /// ```
+    /// use rushterm::{Color, Item, Menu, NumberKind};
+    ///
+    /// let mut menu = Menu::from_dsl(
+    ///     r#"
+    /// menu Main "Main menu." no_esc
+    /// action Deploy (d) "Ships it." checkable confirm="deploy now" global_hotkey=D badge="NEW"
+    /// action Docs url="https://example.com/docs?a=1&b=\"2\""
+    /// submenu Settings (s) badge.dark_red="OLD" badge.#ff8800="BETA" {
+    ///     submenu "Network \"lan\"" {
+    ///         u32 Port (p) confirm
+    ///     }
+    ///     bool Verbose (v) current=true
+    /// }
+    /// "#,
+    /// )
+    /// .unwrap();
+    /// // Every kind of `Item`, with its attributes set.
+    /// let input = |name: &str| (name.to_string(), None, Some(format!("{} input.", name)), Vec::new());
+    /// let (name, hotkey, exp, badges) = input("Initial");
+    /// menu.items.push(Item::Char {
+    ///     name,
+    ///     hotkey: Some('i'),
+    ///     exp,
+    ///     badges,
+    ///     skippable: true,
+    ///     current: None,
+    ///     allowed: Some(vec!['A', ' ', '"']),
+    ///     ignore_case: true,
+    /// });
+    /// let (name, hotkey, exp, badges) = input("Host");
+    /// let history = vec!["localhost".to_string(), "example.com".to_string()];
+    /// let current = Some("my \"host\"".to_string());
+    /// menu.items.push(Item::String { name, hotkey, exp, badges, skippable: true, required: true, history, current });
+    /// let (name, hotkey, exp, badges) = input("Token");
+    /// menu.items.push(Item::Secret { name, hotkey, exp, badges, skippable: false, required: true });
+    /// let (name, hotkey, exp, badges) = input("Ratio");
+    /// menu.items.push(Item::F32 { name, hotkey, exp, badges, skippable: true, current: None, confirm: true, step: Some(0.25) });
+    /// let (name, hotkey, exp, badges) = input("Price");
+    /// let (decimals, round) = (Some(2), true);
+    /// menu.items.push(Item::F64 { name, hotkey, exp, badges, skippable: false, decimals, round, current: None, confirm: false, step: None });
+    /// let (name, hotkey, exp, badges) = input("Offset");
+    /// menu.items.push(Item::I32 { name, hotkey, exp, badges, skippable: false, current: None, confirm: true, step: Some(-5) });
+    /// let (name, hotkey, exp, badges) = input("Ports");
+    /// let kind = NumberKind::U32;
+    /// let current = Some("80, 443".to_string());
+    /// menu.items.push(Item::NumberList { name, hotkey, exp, badges, skippable: true, kind, current });
+    /// let (name, hotkey, exp, badges) = input("Cache");
+    /// menu.items.push(Item::ByteSize { name, hotkey, exp, badges, skippable: true, current: None });
+    /// let (name, hotkey, exp, badges) = input("Accent");
+    /// menu.items.push(Item::Color { name, hotkey, exp, badges, skippable: false, current: None });
+    /// let (name, hotkey, exp, badges) = input("Copies");
+    /// menu.items.push(Item::Counter { name, hotkey, exp, badges, min: -5, max: 5, step: 2, initial: 1 });
+    /// let (name, hotkey, exp, badges) = input("Advanced");
+    /// menu.items.push(Item::Group { name, hotkey, exp, badges, collapsed: true });
+    /// menu.items.push(Item::Label { text: "Multi-line\nlabel with \\ and \"quotes\"".to_string() });
+    /// let (name, hotkey, exp, mut badges) = input("Config");
+    /// badges.push(("A=B \"C\"".to_string(), Color::AnsiValue(208)));
+    /// menu.items.push(Item::Json { name, hotkey, exp, badges, skippable: true });
+    ///
+    /// let text = menu.to_dsl().unwrap();
+    /// assert!(text.contains("\n    submenu \"Network \\\"lan\\\"\" {\n        u32 \"Port\" (p) confirm\n"));
+    /// assert!(text.contains("\ni32 \"Offset\" \"Offset input.\" step=-5 confirm\n"));
+    /// assert_eq!(Menu::from_dsl(&text), Ok(menu.clone()));
+    /// let badges = vec![("OLD".to_string(), Color::DarkRed), ("BETA".to_string(), Color::Rgb { r: 255, g: 136, b: 0 })];
+    /// assert_eq!(menu.items[2].badges(), &badges[..]);
+    /// assert!(matches!(&menu.items[1], Item::Action { url: Some(url), .. } if url == "https://example.com/docs?a=1&b=\"2\""));
+    ///
+    /// // A control character as a hotkey isn't part of the format.
+    /// if let Item::Action { hotkey, .. } = &mut menu.items[1] {
+    ///     *hotkey = Some('\t');
+    /// }
+    /// let err = menu.to_dsl().unwrap_err();
+    /// assert_eq!((err.line, err.message.as_str()), (3, "`hotkey` of `Docs` can't be written"));
+    /// ```
+    pub fn to_dsl(&self) -> Result<String, DslError> {
+        if self.name.is_empty() {
+            return Err(DslError {
+                line: 1,
+                message: "`name` of the menu can't be written".to_string(),
+            });
+        }
+        let mut text = format!("menu {}", quote(&self.name));
+        if let Some(exp) = &self.exp {
+            text.push(' ');
+            text.push_str(&quote(exp));
+        }
+        if !self.esc {
+            text.push_str(" no_esc");
+        }
+        text.push('\n');
+        write_items(&mut text, &self.items, 1)?;
+        Ok(text)
+    }
+    /// Same as `to_dsl()`, but writes the `Menu` as pretty-printed JSON for tools and persistence. The `Menu` is an object of its `name`, `exp`, `esc` and `items`, and each `Item` an object of its `kind`, `name`, `hotkey` and `exp` as in the DSL, its attributes by their DSL names, flags as `true`, its badges as `badges`, objects of their `text` and `color` unless it's the theme's, and the `items` of a `submenu`. Keys are sorted, and absent fields are left out. Needs the `serde` feature too.
+    /// ```
+    /// use rushterm::Menu;
+    ///
+    /// let menu = Menu::from_dsl(
+    ///     r#"
+    /// menu Main
+    /// submenu Settings (s) {
+    ///     f64 Price "In dollars." decimals=2 round
+    /// }
+    /// "#,
+    /// )
+    /// .unwrap();
+    /// let json: serde_json::Value = serde_json::from_str(&menu.to_json().unwrap()).unwrap();
+    /// let expected = serde_json::json!({
+    ///     "name": "Main",
+    ///     "esc": true,
+    ///     "items": [{
+    ///         "kind": "submenu",
+    ///         "name": "Settings",
+    ///         "hotkey": "s",
+    ///         "items": [{
+    ///             "kind": "f64",
+    ///             "name": "Price",
+    ///             "exp": "In dollars.",
+    ///             "decimals": 2,
+    ///             "round": true
+    ///         }]
+    ///     }]
+    /// });
+    /// assert_eq!(json, expected);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, DslError> {
+        // the DSL checks the fields can be written, and finds the line of a failure.
+        self.to_dsl()?;
+        let mut menu = serde_json::Map::new();
+        menu.insert("name".to_string(), self.name.clone().into());
+        if let Some(exp) = &self.exp {
+            menu.insert("exp".to_string(), exp.clone().into());
+        }
+        menu.insert("esc".to_string(), self.esc.into());
+        menu.insert("items".to_string(), json_items(&self.items));
+        Ok(serde_json::to_string_pretty(&menu).expect("JSON of a map"))
+    }
+}
+impl TryFrom<&str> for Menu {
+    type Error = DslError;
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        Menu::from_dsl(text)
+    }
+}
+
+/// Attribute written after the explanation of an `Item`.
+enum Attr {
+    /// A bare word like `skippable`.
+    Flag(&'static str),
+    /// A number like `decimals=2`.
+    Number(&'static str, String),
+    /// A quoted text like `allowed="NSEW"`.
+    Text(&'static str, String),
+    /// Quoted texts repeating the key, like `history="a" history="b"`.
+    List(&'static str, Vec<String>),
+    /// Badges with their colors, like `badge="NEW" badge.green="BETA"`.
+    Badges(Vec<(String, Color)>),
+}
+/// Returns the kind the `Item` is declared by.
+fn kind(item: &Item) -> &'static str {
+    match item {
+        Item::Action { .. } => "action",
+        Item::SubMenu { .. } | Item::LazySubMenu { .. } => "submenu",
+        Item::Bool { .. } => "bool",
+        Item::Char { .. } => "char",
+        Item::String { .. } => "string",
+        Item::Secret { .. } => "secret",
+        Item::Json { .. } => "json",
+        Item::F32 { .. } => "f32",
+        Item::F64 { .. } => "f64",
+        Item::I32 { .. } => "i32",
+        Item::U32 { .. } => "u32",
+        Item::ByteSize { .. } => "bytesize",
+        Item::Color { .. } => "color",
+        Item::Counter { .. } => "counter",
+        Item::Group { .. } => "group",
+        Item::Label { .. } => "label",
+        Item::NumberList { kind, .. } => match kind {
+            NumberKind::F32 => "list<f32>",
+            NumberKind::F64 => "list<f64>",
+            NumberKind::I32 => "list<i32>",
+            NumberKind::U32 => "list<u32>",
+        },
+    }
+}
+/// Returns the attributes of the `Item`, leaving out the ones at their default.
+fn attrs(item: &Item) -> Vec<Attr> {
+    let mut attrs = vec![];
+    if !item.badges().is_empty() {
+        attrs.push(Attr::Badges(item.badges().to_vec()));
+    }
+    if let Item::Action {
+        checkable,
+        confirm_phrase,
+        global_hotkey,
+        url,
+        ..
+    } = item
+    {
+        if *checkable {
+            attrs.push(Attr::Flag("checkable"));
+        }
+        if let Some(phrase) = confirm_phrase {
+            attrs.push(Attr::Text("confirm", phrase.to_string()));
+        }
+        if let Some(hotkey) = global_hotkey {
+            attrs.push(Attr::Text("global_hotkey", hotkey.to_string()));
+        }
+        if let Some(url) = url {
+            attrs.push(Attr::Text("url", url.to_string()));
+        }
+    }
+    if let Item::F64 {
+        decimals, round, ..
+    } = item
+    {
+        if let Some(decimals) = decimals {
+            attrs.push(Attr::Number("decimals", decimals.to_string()));
+        }
+        if *round {
+            attrs.push(Attr::Flag("round"));
+        }
+    }
+    if let Item::Counter {
+        min,
+        max,
+        step,
+        initial,
+        ..
+    } = item
+    {
+        for (key, value) in COUNTER_ATTRS.iter().zip([min, max, step, initial]) {
+            attrs.push(Attr::Number(key, value.to_string()));
+        }
+    }
+    let step = match item {
+        Item::F32 { step, .. } => step.map(|f| f.to_string()),
+        Item::F64 { step, .. } => step.map(|f| f.to_string()),
+        Item::I32 { step, .. } => step.map(|f| f.to_string()),
+        Item::U32 { step, .. } => step.map(|f| f.to_string()),
+        _ => None,
+    };
+    if let Some(step) = step {
+        attrs.push(Attr::Number("step", step));
+    }
+    if item.skippable() {
+        attrs.push(Attr::Flag("skippable"));
+    }
+    if let Item::String { required: true, .. } | Item::Secret { required: true, .. } = item {
+        attrs.push(Attr::Flag("required"));
+    }
+    if let Item::Group {
+        collapsed: true, ..
+    } = item
+    {
+        attrs.push(Attr::Flag("collapsed"));
+    }
+    if item.confirms() {
+        attrs.push(Attr::Flag("confirm"));
+    }
+    if let Item::Char {
+        allowed,
+        ignore_case,
+        ..
+    } = item
+    {
+        if let Some(allowed) = allowed {
+            attrs.push(Attr::Text("allowed", allowed.iter().collect()));
+        }
+        if *ignore_case {
+            attrs.push(Attr::Flag("ignore_case"));
+        }
+    }
+    if let Some(current) = item.current() {
+        attrs.push(Attr::Text("current", current.to_string()));
+    }
+    if let Item::String { history, .. } = item {
+        if !history.is_empty() {
+            attrs.push(Attr::List("history", history.to_vec()));
+        }
+    }
+    attrs
+}
+/// Returns the first field of the `Item` the format can't express, unless it's at its default.
+fn unwritable(item: &Item) -> Option<&'static str> {
+    if item.name().is_empty() {
+        return Some("name");
+    }
+    if item.hotkey().is_some_and(char::is_control) {
+        return Some("hotkey");
+    }
+    match item {
+        Item::Action {
+            global_hotkey: Some(hotkey),
+            ..
+        } if hotkey.is_control() => Some("global_hotkey"),
+        _ => None,
+    }
+}
+fn write_items(text: &mut String, items: &[Item], depth: usize) -> Result<(), DslError> {
+    let indent = "    ".repeat(depth - 1);
+    for item in items {
+        if let Some(field) = unwritable(item) {
+            return Err(DslError {
+                line: text.lines().count() + 1,
+                message: format!("`{}` of `{}` can't be written", field, item.name()),
+            });
+        }
+        text.push_str(&format!("{}{} {}", indent, kind(item), quote(item.name())));
+        if let Some(hotkey) = item.hotkey() {
+            text.push_str(&format!(" ({})", hotkey));
+        }
+        if let Some(exp) = item.exp() {
+            text.push_str(&format!(" {}", quote(exp)));
+        }
+        for attr in attrs(item) {
+            match attr {
+                Attr::Flag(key) => text.push_str(&format!(" {}", key)),
+                Attr::Number(key, value) => text.push_str(&format!(" {}={}", key, value)),
+                Attr::Text(key, value) => text.push_str(&format!(" {}={}", key, quote(&value))),
+                Attr::List(key, values) => {
+                    for value in values {
+                        text.push_str(&format!(" {}={}", key, quote(&value)));
+                    }
+                }
+                Attr::Badges(badges) => {
+                    for (badge, color) in badges {
+                        match color_name(color) {
+                            Some(color) => text.push_str(&format!(" badge.{}", color)),
+                            None => text.push_str(" badge"),
+                        }
+                        text.push_str(&format!("={}", quote(&badge)));
+                    }
+                }
+            }
+        }
+        match item.sub_items() {
+            Some(items) => {
+                text.push_str(" {\n");
+                write_items(text, &items, depth + 1)?;
+                text.push_str(&indent);
+                text.push_str("}\n");
+            }
+            None => text.push('\n'),
+        }
+    }
+    Ok(())
+}
+#[cfg(feature = "serde")]
+fn json_items(items: &[Item]) -> serde_json::Value {
+    let items = items.iter().map(|item| {
+        let mut object = serde_json::Map::new();
+        object.insert("kind".to_string(), kind(item).into());
+        object.insert("name".to_string(), item.name().into());
+        if let Some(hotkey) = item.hotkey() {
+            object.insert("hotkey".to_string(), hotkey.to_string().into());
+        }
+        if let Some(exp) = item.exp() {
+            object.insert("exp".to_string(), exp.into());
+        }
+        for attr in attrs(item) {
+            let (key, value) = match attr {
+                Attr::Flag(key) => (key, true.into()),
+                Attr::Number(key, value) => match serde_json::from_str(&value) {
+                    Ok(number) => (key, number),
+                    Err(_) => (key, value.into()),
+                },
+                Attr::Text(key, value) => (key, value.into()),
+                Attr::List(key, values) => (key, values.into()),
+                Attr::Badges(badges) => {
+                    let badges = badges.into_iter().map(|(badge, color)| {
+                        let mut object = serde_json::Map::new();
+                        object.insert("text".to_string(), badge.into());
+                        if let Some(color) = color_name(color) {
+                            object.insert("color".to_string(), color.into());
+                        }
+                        serde_json::Value::Object(object)
+                    });
+                    ("badges", badges.collect())
+                }
+            };
+            object.insert(key.to_string(), value);
+        }
+        if let Some(items) = item.sub_items() {
+            object.insert("items".to_string(), json_items(&items));
+        }
+        serde_json::Value::Object(object)
+    });
+    serde_json::Value::Array(items.collect())
+}
+/// Kinds taking `current`, besides the number lists.
+const CURRENT_KINDS: [&str; 9] = [
+    "bool", "char", "string", "f32", "f64", "i32", "u32", "bytesize", "color",
+];
+/// Attributes of `counter`, in the order of their slots.
+const COUNTER_ATTRS: [&str; 4] = ["min", "max", "step", "initial"];
+/// Kinds taking `step` as the step of `Up` and `Down` at the prompt, besides `counter`.
+const STEP_KINDS: [&str; 4] = ["f32", "f64", "i32", "u32"];
+/// Removes the `badge` attributes from the attributes, giving their badges in order.
+fn take_badges(attrs: &mut Vec<String>) -> Result<Vec<(String, Color)>, String> {
+    let mut badges = vec![];
+    for (key, badge) in attrs.iter().filter_map(|f| f.split_once('=')) {
+        let color = match key.strip_prefix("badge.") {
+            _ if key == "badge" => Color::Reset,
+            Some(color) => {
+                parse_color(color).ok_or_else(|| format!("unknown badge color `{}`", color))?
+            }
+            None => continue,
+        };
+        badges.push((badge.to_string(), color));
+    }
+    attrs.retain(|f| {
+        let key = f.split_once('=').map_or("", |(key, _)| key);
+        key != "badge" && !key.starts_with("badge.")
+    });
+    Ok(badges)
+}
+/// Returns the color of a badge as written after `badge.`, or `None` for `Color::Reset`, which shows it in the color of the theme.
+fn color_name(color: Color) -> Option<String> {
+    let name = match color {
+        Color::Reset => return None,
+        Color::Black => "black",
+        Color::DarkGrey => "dark_grey",
+        Color::Red => "red",
+        Color::DarkRed => "dark_red",
+        Color::Green => "green",
+        Color::DarkGreen => "dark_green",
+        Color::Yellow => "yellow",
+        Color::DarkYellow => "dark_yellow",
+        Color::Blue => "blue",
+        Color::DarkBlue => "dark_blue",
+        Color::Magenta => "magenta",
+        Color::DarkMagenta => "dark_magenta",
+        Color::Cyan => "cyan",
+        Color::DarkCyan => "dark_cyan",
+        Color::White => "white",
+        Color::Grey => "grey",
+        Color::Rgb { r, g, b } => return Some(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+        Color::AnsiValue(value) => return Some(format!("ansi_{}", value)),
+    };
+    Some(name.to_string())
+}
+/// Parses the color of a badge written by `color_name()`.
+fn parse_color(name: &str) -> Option<Color> {
+    if name.starts_with('#') {
+        let Rgb(r, g, b) = name.parse().ok()?;
+        return Some(Color::Rgb { r, g, b });
+    }
+    if let Some(value) = name.strip_prefix("ansi_") {
+        return value.parse().ok().map(Color::AnsiValue);
+    }
+    Color::try_from(name).ok()
+}
+/// Parses the `step` of a number input, if it's set.
+fn parse_step<T: FromStr>(step: Option<&str>) -> Result<Option<T>, String> {
+    step.map(|f| {
+        f.parse()
+            .map_err(|_| "step should be a number like `step=1`".to_string())
+    })
+    .transpose()
+}
+/// Quotes the text, escaping quotes, backslashes and line breaks, which `read_quoted_rest()` reads back.
+fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+fn list_kind(kind: &str) -> Option<NumberKind> {
+    match kind {
+        "list<f32>" => Some(NumberKind::F32),
+        "list<f64>" => Some(NumberKind::F64),
+        "list<i32>" => Some(NumberKind::I32),
+        "list<u32>" => Some(NumberKind::U32),
+        _ => None,
+    }
+}
+fn push_item(
+    menu: &mut Option<Menu>,
+    stack: &mut [Open],
+    item: Item,
+    line: usize,
+) -> Result<(), DslError> {
+    if let Some(open) = stack.last_mut() {
+        open.items.push(item);
+    } else if let Some(menu) = menu {
+        menu.items.push(item);
+    } else {
+        return Err(DslError {
+            line,
+            message: "menu should be declared first".to_string(),
+        });
+    }
+    Ok(())
+}
+fn parse_line(line: &str) -> Result<Line, String> {
+    let mut chars = line.chars().peekable();
+    let kind = read_word(&mut chars);
+    skip_spaces(&mut chars);
+    let name = match chars.peek() {
+        Some('"') => read_quoted(&mut chars)?,
+        Some(_) => read_word(&mut chars),
+        None => String::new(),
+    };
+    if name.is_empty() {
+        return Err(format!("{} needs a name", kind));
+    }
+    let mut decl = Line {
+        kind,
+        name,
+        hotkey: None,
+        exp: None,
+        attrs: vec![],
+        opens: false,
+    };
+    loop {
+        skip_spaces(&mut chars);
+        match chars.next() {
+            None => break,
+            Some('(') if decl.hotkey.is_none() && decl.exp.is_none() => {
+                let hotkey = chars.next().ok_or("hotkey is not closed")?;
+                if chars.next() != Some(')') {
+                    return Err("hotkey should be a single character like `(a)`".to_string());
+                }
+                decl.hotkey = Some(hotkey);
+            }
+            Some('"') if decl.exp.is_none() => {
+                decl.exp = Some(read_quoted_rest(&mut chars)?);
+            }
+            Some('{') => {
+                decl.opens = true;
+                skip_spaces(&mut chars);
+                if chars.peek().is_some() {
+                    return Err("`{` should end the line".to_string());
+                }
+            }
+            Some(chr) if chr.is_alphanumeric() && !decl.opens => {
+                let mut attr = chr.to_string() + &read_word(&mut chars);
+                if attr.ends_with('=') && chars.peek() == Some(&'"') {
+                    attr.push_str(&read_quoted(&mut chars)?);
+                }
+                decl.attrs.push(attr);
+            }
+            Some(other) => return Err(format!("unexpected `{}`", other)),
+        }
+    }
+    Ok(decl)
+}
+fn skip_spaces(chars: &mut std::iter::Peekable<std::str::Chars>) {
+    while chars.peek().is_some_and(|c| c.is_whitespace()) {
+        chars.next();
+    }
+}
+fn read_word(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut word = String::new();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || c == '(' || c == '"' || c == '{' {
+            break;
+        }
+        word.push(c);
+        chars.next();
+    }
+    word
+}
+fn read_quoted(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<String, String> {
+    chars.next();
+    read_quoted_rest(chars)
+}
+fn read_quoted_rest(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<String, String> {
+    let mut text = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(text),
+            Some('\\') => match chars.next() {
+                Some('n') => text.push('\n'),
+                Some('r') => text.push('\r'),
+                Some(c) => text.push(c),
+                None => return Err("quote is not closed".to_string()),
+            },
+            Some(c) => text.push(c),
+            None => return Err("quote is not closed".to_string()),
+        }
+    }
+}
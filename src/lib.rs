@@ -12,16 +12,27 @@
 //!               name: "Action0".to_string(),
 //!               hotkey: Some('a'),
 //!               exp: Some("Action0 Explanation. This Has Been Assigned To A Hotkey.".to_string()),
+//!               badges: Vec::new(),
+//!               confirm_phrase: None,
+//!               global_hotkey: None,
+//!               url: None,
+//!               checkable: false,
 //!           },
 //!           Item::Action {
 //!               name: "Action1".to_string(),
 //!               hotkey: None,
 //!               exp: Some("Action1 Explanation. This Has No Hotkey.".to_string()),
+//!               badges: Vec::new(),
+//!               confirm_phrase: None,
+//!               global_hotkey: None,
+//!               url: None,
+//!               checkable: false,
 //!           },
 //!           Item::SubMenu {
 //!               name: "Submenu0".to_string(),
 //!               hotkey: Some('s'),
 //!               exp: Some("Submenu0 explanation.".to_string()),
+//!               badges: Vec::new(),
 //!               items: vec![
 //!                   Item::Action {
 //!                       name: "Sub Action0".to_string(),
@@ -30,6 +41,11 @@
 //!                           "Sub Action0 Explanation. This Has Been Assigned To A Hotkey."
 //!                               .to_string(),
 //!                       ),
+//!                       badges: Vec::new(),
+//!                       confirm_phrase: None,
+//!                       global_hotkey: None,
+//!                       url: None,
+//!                       checkable: false,
 //!                   },
 //!                   Item::Action {
 //!                       name: "Sub Action1".to_string(),
@@ -38,21 +54,37 @@
 //!                           "Sub Action1 Explanation. This Has Been Assigned To A Hotkey."
 //!                               .to_string(),
 //!                       ),
+//!                       badges: Vec::new(),
+//!                       confirm_phrase: None,
+//!                       global_hotkey: None,
+//!                       url: None,
+//!                       checkable: false,
 //!                   },
 //!                   Item::SubMenu {
 //!                       name: "Deepermenu0".to_string(),
 //!                       hotkey: Some('d'),
 //!                       exp: Some("Deepermenu0 Explanation.".to_string()),
+//!                       badges: Vec::new(),
 //!                       items: vec![
 //!                           Item::Action {
 //!                               name: "Deeper Action0".to_string(),
 //!                               hotkey: Some('f'),
 //!                               exp: None,
+//!                               badges: Vec::new(),
+//!                               confirm_phrase: None,
+//!                               global_hotkey: None,
+//!                               url: None,
+//!                               checkable: false,
 //!                           },
 //!                           Item::Action {
 //!                               name: "Deeper Action1".to_string(),
 //!                               hotkey: Some('g'),
 //!                               exp: Some("Deeper Action1 Explanation.".to_string()),
+//!                               badges: Vec::new(),
+//!                               confirm_phrase: None,
+//!                               global_hotkey: None,
+//!                               url: None,
+//!                               checkable: false,
 //!                           },
 //!                       ],
 //!                   },
@@ -62,31 +94,58 @@
 //!               name: "Bool0".to_string(),
 //!               hotkey: Some('b'),
 //!               exp: Some("Bool0 Explanation.".to_string()),
+//!               badges: Vec::new(),
+//!               current: None,
 //!           },
 //!           Item::Char {
 //!               name: "Char0".to_string(),
 //!               hotkey: Some('c'),
 //!               exp: Some("Char0 Explanation.".to_string()),
+//!               badges: Vec::new(),
+//!               skippable: false,
+//!               current: None,
+//!               allowed: None,
+//!               ignore_case: false,
 //!           },
 //!           Item::String {
 //!               name: "String0".to_string(),
 //!               hotkey: Some('t'),
 //!               exp: Some("String0 Explanation.".to_string()),
+//!               badges: Vec::new(),
+//!               skippable: false,
+//!               required: false,
+//!               history: vec![],
+//!               current: None,
 //!           },
 //!           Item::F32 {
 //!               name: "F32".to_string(),
 //!               hotkey: Some('f'),
 //!               exp: Some("F32 Explanation.".to_string()),
+//!               badges: Vec::new(),
+//!               skippable: false,
+//!               current: None,
+//!               confirm: false,
+//!               step: None,
 //!           },
 //!           Item::I32 {
 //!               name: "I32".to_string(),
 //!               hotkey: Some('i'),
 //!               exp: Some("I32 Explanation.".to_string()),
+//!               badges: Vec::new(),
+//!               skippable: false,
+//!               current: None,
+//!               confirm: false,
+//!               step: None,
 //!           },
 //!           Item::U32 {
 //!               name: "U32".to_string(),
 //!               hotkey: Some('u'),
 //!               exp: Some("U32 Explanation.".to_string()),
+//!               badges: Vec::new(),
+//!               skippable: false,
+//!               current: None,
+//!               confirm: false,
+//!               step: None,
 //!           },
 //!       ],
 //!       exp: Some("My Main Menu Explanation.".to_string()),
@@ -99,22 +158,106 @@
 //! ```
 //! If selection is successful, `run()` method will return us `Selection` type in `Ok()` variant to get information we may need in ongoing execution. If not, exits the execution with an `Err()` variant.
 
+#[cfg(feature = "clap")]
+pub mod clap;
+#[cfg(feature = "dsl")]
+pub mod dsl;
+#[cfg(feature = "macros")]
+mod macros;
+pub mod prelude;
+#[cfg(test)]
+mod tests;
+
 use crossterm::{
     cursor,
-    event::{read, Event, KeyCode, KeyEvent},
+    event::{read, Event},
     style::Stylize,
     terminal::{self, ClearType},
     QueueableCommand,
 };
+pub use crossterm::{
+    event::{KeyCode, KeyEvent, KeyModifiers},
+    style::Color,
+};
 use std::{
-    fmt,
-    io::{stdin, stdout, Stdout, Write},
+    borrow::Cow,
+    cell::{Cell, OnceCell, RefCell},
+    collections::{BTreeMap, HashMap, VecDeque},
+    env, fmt, fs,
+    io::{self, stderr, stdin, stdout, Write},
+    iter, mem,
+    path::{Component, Path, PathBuf},
+    rc::Rc,
     str::FromStr,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
 };
-/// Anything that can be listed in `Menu`.
-#[derive(Clone, Debug)]
+/// Prints to the stream chosen by `RunOptions::output`.
+macro_rules! out {
+    ($opts:expr, $($arg:tt)*) => {
+        $opts.write_out(format_args!($($arg)*))
+    };
+}
+/// Prints a line to the stream chosen by `RunOptions::output`.
+macro_rules! outln {
+    ($opts:expr) => {
+        out!($opts, "\n{}", " ".repeat($opts.margin()))
+    };
+    ($opts:expr, $($arg:tt)*) => {{
+        out!($opts, $($arg)*);
+        outln!($opts);
+    }};
+}
+/// Anything that can be listed in `Menu`. New kinds of `Item`s may be added, so matches on it need a wildcard arm. `name()`, `hotkey()`, `exp()` and `badges()` read the fields common to all `Item`s without matching.
+/// ```
+/// use rushterm::Item;
+///
+/// let items = [
+///     Item::Bool {
+///         name: "Verbose".to_string(),
+///         hotkey: Some('v'),
+///         exp: Some("Logs every step".to_string()),
+///         badges: Vec::new(),
+///         current: None,
+///     },
+///     Item::Counter {
+///         name: "Copies".to_string(),
+///         hotkey: None,
+///         exp: None,
+///         badges: Vec::new(),
+///         min: 1,
+///         max: 9,
+///         step: 1,
+///         initial: 1,
+///     },
+///     Item::Label {
+///         text: "Printing".to_string(),
+///     },
+/// ];
+/// // The wildcard arm keeps the match compiling when `Item`s are added.
+/// let kinds: Vec<&str> = items
+///     .iter()
+///     .map(|f| match f {
+///         Item::Bool { .. } => "switch",
+///         Item::Label { .. } => "text",
+///         _ => "other",
+///     })
+///     .collect();
+/// assert_eq!(kinds, ["switch", "other", "text"]);
+/// let names: Vec<&str> = items.iter().map(Item::name).collect();
+/// assert_eq!(names, ["Verbose", "Copies", "Printing"]);
+/// let hotkeys: Vec<Option<char>> = items.iter().map(Item::hotkey).collect();
+/// assert_eq!(hotkeys, [Some('v'), None, None]);
+/// let exps: Vec<Option<&str>> = items.iter().map(Item::exp).collect();
+/// assert_eq!(exps, [Some("Logs every step"), None, None]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
 pub enum Item {
     /// A menu item to execute an action. Exits `Menu`.
+    ///
+    /// Hotkeys that aren't letters, like `?`, `/` or `+`, are displayed and matched as they are, while letters are displayed in uppercase and matched in either case.
     Action {
         /// Action name.
         name: String,
@@ -122,6 +265,122 @@ pub enum Item {
         hotkey: Option<char>,
         /// Optional explanation in gray color is displayed next to the item.
         exp: Option<String>,
+        /// Optional badges like `NEW` or `beta` are displayed in brackets after the name, each in its color, to point out new or experimental `Item`s. A badge colored `Color::Reset` takes `Theme::badge`. The badges are counted in the width of the `Item`, so hotkeys and borders stay aligned.
+        badges: Vec<(String, Color)>,
+        /// Phrase the user has to type exactly, like the name of the thing to delete, before the action is selected. Guards extremely destructive actions more strongly than a yes or no question. Typing anything else cancels and returns to the `Menu`.
+        /// ```
+        /// use rushterm::{Item, Menu, RunOptions};
+        ///
+        /// let menu = Menu {
+        ///     name: "Main".to_string(),
+        ///     exp: None,
+        ///     items: vec![
+        ///         Item::Action {
+        ///             name: "Delete production".to_string(),
+        ///             hotkey: None,
+        ///             exp: None,
+        ///             badges: Vec::new(),
+        ///             confirm_phrase: Some("production".to_string()),
+        ///             global_hotkey: None,
+        ///             url: None,
+        ///             checkable: false,
+        ///         },
+        ///         Item::Action {
+        ///             name: "Quit".to_string(),
+        ///             hotkey: None,
+        ///             exp: None,
+        ///             badges: Vec::new(),
+        ///             confirm_phrase: None,
+        ///             global_hotkey: None,
+        ///             url: None,
+        ///             checkable: false,
+        ///         },
+        ///     ],
+        ///     esc: true,
+        /// };
+        /// let opts = RunOptions::default();
+        /// // A wrong phrase cancels, back to the `Menu`.
+        /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "Production", "Down", "Enter"]);
+        /// assert_eq!(selection.unwrap().name, "Quit");
+        /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "production"]);
+        /// assert_eq!(selection.unwrap().name, "Delete production");
+        /// ```
+        confirm_phrase: Option<String>,
+        /// Assigning a global hotkey is optional. Unlike `hotkey`, it selects the `Action` from any `SubMenu` depth, as long as no `Item` of the displayed level takes the key. Use distinct global hotkeys, the last one wins.
+        /// ```
+        /// use rushterm::{Item, Menu, RunOptions};
+        ///
+        /// let menu = Menu {
+        ///     name: "Main".to_string(),
+        ///     exp: None,
+        ///     items: vec![
+        ///         Item::SubMenu {
+        ///             name: "Settings".to_string(),
+        ///             hotkey: None,
+        ///             exp: None,
+        ///             badges: Vec::new(),
+        ///             items: vec![Item::Action {
+        ///                 name: "Reset".to_string(),
+        ///                 hotkey: None,
+        ///                 exp: None,
+        ///                 badges: Vec::new(),
+        ///                 confirm_phrase: None,
+        ///                 global_hotkey: None,
+        ///                 url: None,
+        ///                 checkable: false,
+        ///             }],
+        ///         },
+        ///         Item::Action {
+        ///             name: "Quit".to_string(),
+        ///             hotkey: None,
+        ///             exp: None,
+        ///             badges: Vec::new(),
+        ///             confirm_phrase: None,
+        ///             global_hotkey: Some('q'),
+        ///             url: None,
+        ///             checkable: false,
+        ///         },
+        ///     ],
+        ///     esc: true,
+        /// };
+        /// let opts = RunOptions::default();
+        /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "q"]).unwrap();
+        /// assert_eq!(selection.name, "Quit");
+        /// assert_eq!(selection.indexes, vec![1]);
+        /// ```
+        global_hotkey: Option<char>,
+        /// Link the `Action` stands for, like its documentation. The name is rendered as a hyperlink the terminal opens on click, if `Capabilities::hyperlinks`, or else as plain text. The `Action` is selected as usual, the `Menu` doesn't open the link.
+        /// ```
+        /// use rushterm::{Capabilities, Item, Menu, RunOptions};
+        ///
+        /// let menu = Menu {
+        ///     name: "Main".to_string(),
+        ///     exp: None,
+        ///     items: vec![Item::Action {
+        ///         name: "Docs".to_string(),
+        ///         hotkey: None,
+        ///         exp: None,
+        ///         badges: Vec::new(),
+        ///         confirm_phrase: None,
+        ///         global_hotkey: None,
+        ///         url: Some("https://docs.rs/rushterm".to_string()),
+        ///         checkable: false,
+        ///     }],
+        ///     esc: true,
+        /// };
+        /// let render = |hyperlinks: bool| {
+        ///     let opts = RunOptions {
+        ///         capabilities: Capabilities { unicode: true, color: false, hyperlinks },
+        ///         ..RunOptions::default()
+        ///     };
+        ///     menu.render_to_string(&opts, 80)
+        /// };
+        /// assert!(render(true).contains("\x1b]8;;https://docs.rs/rushterm\x1b\\Docs\x1b]8;;\x1b\\"));
+        /// assert!(render(false).contains("0.     Docs\n"));
+        /// ```
+        url: Option<String>,
+        /// Let `Space` check the `Action` in `Menu::run_checklist()`, where selecting a checkable `Action` confirms all checked ones. Other `Action`s are selected alone as usual.
+        checkable: bool,
     },
     /// A menu item to enter branch menus. Eclipses `Menu` or another `SubMenu`.
     SubMenu {
@@ -131,9 +390,65 @@ pub enum Item {
         hotkey: Option<char>,
         /// Optional explanation in gray color is displayed next to the item.
         exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
         /// `SubMenu` items should be vector of `Item`s.
         items: Vec<Item>,
     },
+    /// A `SubMenu` whose `Item`s are built by `provider` each time it's entered, instead of being built upfront. Suits expensive or dynamic contents like a file browser. It can be distinguished by the `+` character before it.
+    /// ```
+    /// use rushterm::{Item, ItemsProvider, Menu, RunOptions};
+    /// use std::sync::{
+    ///     atomic::{AtomicUsize, Ordering},
+    ///     Arc,
+    /// };
+    ///
+    /// let calls = Arc::new(AtomicUsize::new(0));
+    /// let counted = calls.clone();
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::LazySubMenu {
+    ///         name: "Files".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         provider: ItemsProvider::new(move || {
+    ///             let count = counted.fetch_add(1, Ordering::Relaxed) + 1;
+    ///             vec![Item::Action {
+    ///                 name: format!("file{}.txt", count),
+    ///                 hotkey: None,
+    ///                 exp: None,
+    ///                 badges: Vec::new(),
+    ///                 confirm_phrase: None,
+    ///                 global_hotkey: None,
+    ///                 url: None,
+    ///                 checkable: false,
+    ///             }]
+    ///         }),
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// menu.render_to_string(&RunOptions::default(), 80);
+    /// assert_eq!(calls.load(Ordering::Relaxed), 0);
+    /// // built again on each entry.
+    /// let keys = ["Enter", "Backspace", "Enter", "Enter"];
+    /// let selection = menu.run_with_stdin_values(&RunOptions::default(), &keys).unwrap();
+    /// assert_eq!(selection.name, "file2.txt");
+    /// assert_eq!(calls.load(Ordering::Relaxed), 2);
+    /// ```
+    LazySubMenu {
+        /// Sub menu name. It can be distinguished by the `+` character before it.
+        name: String,
+        /// Assigning a hotkey to the item is optional. The hotkey is displayed in yellow.
+        hotkey: Option<char>,
+        /// Optional explanation in gray color is displayed next to the item.
+        exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Builds the `Item`s of the sub menu.
+        provider: ItemsProvider,
+    },
     /// A menu item to input `bool`. It can be distinguished by the `=` character after it.
     Bool {
         /// Value name.
@@ -142,6 +457,10 @@ pub enum Item {
         hotkey: Option<char>,
         /// Optional explanation in gray color is displayed next to the item.
         exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Optional current value is displayed in green after the name, like `Theme=dark`.
+        current: Option<String>,
     },
     /// A menu item to input `String`. It can be distinguished by the `=` character after it.
     Char {
@@ -151,8 +470,69 @@ pub enum Item {
         hotkey: Option<char>,
         /// Optional explanation in gray color is displayed next to the item.
         exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Typing `RunOptions::skip_sentinel` skips the input, giving a `Selection` with `skipped` set and no value.
+        skippable: bool,
+        /// Optional current value is displayed in green after the name, like `Theme=dark`.
+        current: Option<String>,
+        /// Optional set of characters the input is restricted to, like `N`, `S`, `E` and `W` for a direction. They are listed in the prompt, and any other character is rejected and prompted again.
+        /// ```
+        /// use rushterm::{Item, Menu, RunOptions, Value};
+        ///
+        /// let menu = Menu {
+        ///     name: "Main".to_string(),
+        ///     exp: None,
+        ///     items: vec![Item::Char {
+        ///         name: "Direction".to_string(),
+        ///         hotkey: None,
+        ///         exp: None,
+        ///         badges: Vec::new(),
+        ///         skippable: false,
+        ///         current: None,
+        ///         allowed: Some(vec!['N', 'S', 'E', 'W']),
+        ///         ignore_case: true,
+        ///     }],
+        ///     esc: true,
+        /// };
+        /// let opts = RunOptions::default();
+        /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "x", "e"]).unwrap();
+        /// // `e` is taken as the allowed `E`, after `x` is rejected.
+        /// assert_eq!((selection.value, selection.attempt), (Some(Value::Char('E')), Some(2)));
+        /// ```
+        allowed: Option<Vec<char>>,
+        /// Whether the input matches `allowed` characters in either case, giving the allowed one.
+        ignore_case: bool,
     },
     /// A menu item to input `String`. It can be distinguished by the `=` character after it.
+    /// ```
+    /// use rushterm::{Item, Menu, RunOptions, Value};
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::String {
+    ///         name: "Host".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         skippable: false,
+    ///         required: false,
+    ///         history: vec!["alpha".to_string(), "beta".to_string()],
+    ///         current: None,
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions::default();
+    /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "Up", "Up", "Enter"]);
+    /// assert_eq!(selection.unwrap().value, Some(Value::String("alpha".to_string())));
+    /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "Up", "Up", "Down", "Enter"]);
+    /// assert_eq!(selection.unwrap().value, Some(Value::String("beta".to_string())));
+    /// // Typing in the middle of the line and deleting forward.
+    /// let keys = ["Enter", "gmma", "Home", "Right", "a", "End", "Left", "Delete", "Enter"];
+    /// let selection = menu.run_with_stdin_values(&opts, &keys);
+    /// assert_eq!(selection.unwrap().value, Some(Value::String("gamm".to_string())));
+    /// ```
     String {
         /// Value name.
         name: String,
@@ -160,8 +540,46 @@ pub enum Item {
         hotkey: Option<char>,
         /// Optional explanation in gray color is displayed next to the item.
         exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Typing `RunOptions::skip_sentinel` skips the input, giving a `Selection` with `skipped` set and no value.
+        skippable: bool,
+        /// Reject empty input and prompt again. Empty input is accepted if `false`.
+        /// ```
+        /// use rushterm::{Item, Menu, RunOptions, Value};
+        ///
+        /// let menu = |required| Menu {
+        ///     name: "Signup".to_string(),
+        ///     exp: None,
+        ///     items: vec![Item::String {
+        ///         name: "Email".to_string(),
+        ///         hotkey: None,
+        ///         exp: None,
+        ///         badges: Vec::new(),
+        ///         skippable: false,
+        ///         required,
+        ///         history: vec![],
+        ///         current: None,
+        ///     }],
+        ///     esc: true,
+        /// };
+        /// let opts = RunOptions::default();
+        /// let email = || Some(Value::String("a@b.c".to_string()));
+        /// // the empty line is rejected, and the next one accepted.
+        /// let selection = menu(true).run_with_stdin_values(&opts, &["Enter", "", "a@b.c"]).unwrap();
+        /// assert_eq!((selection.value, selection.attempt), (email(), Some(2)));
+        /// let selection = menu(true).run_with_stdin_values(&opts, &["Enter", "a@b.c"]).unwrap();
+        /// assert_eq!((selection.value, selection.attempt), (email(), Some(1)));
+        /// let selection = menu(false).run_with_stdin_values(&opts, &["Enter", ""]).unwrap();
+        /// assert_eq!((selection.value, selection.attempt), (Some(Value::String(String::new())), Some(1)));
+        /// ```
+        required: bool,
+        /// Previously entered values, oldest first. If any, the line is edited in raw mode: `Up` and `Down` recall them into the input like a shell does, and `Left`, `Right`, `Home`, `End` and `Delete` edit it at the cursor.
+        history: Vec<String>,
+        /// Optional current value is displayed in green after the name, like `Theme=dark`.
+        current: Option<String>,
     },
-    /// A menu item to input `f32`. It can be distinguished by the `=` character after it.
+    /// A menu item to input `f32`, with an optional `-` or `+` sign. It can be distinguished by the `=` character after it.
     F32 {
         /// Value name.
         name: String,
@@ -169,8 +587,100 @@ pub enum Item {
         hotkey: Option<char>,
         /// Optional explanation in gray color is displayed next to the item.
         exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Typing `RunOptions::skip_sentinel` skips the input, giving a `Selection` with `skipped` set and no value.
+        skippable: bool,
+        /// Optional current value is displayed in green after the name, like `Theme=dark`.
+        current: Option<String>,
+        /// Show the parsed value and ask to confirm it by `Locale::yes` before accepting, prompting again if declined. Catches typos in critical numbers.
+        confirm: bool,
+        /// Optional step `Up` and `Down` add to and subtract from the typed value at the prompt, starting from `current` or zero if nothing is typed, so the value can be typed or adjusted.
+        step: Option<f32>,
+    },
+    /// A menu item to input `f64`, optionally limited to a number of decimal places. It takes an optional `-` or `+` sign. It can be distinguished by the `=` character after it.
+    F64 {
+        /// Value name.
+        name: String,
+        /// Assigning a hotkey to the item is optional. The hotkey is displayed in yellow.
+        hotkey: Option<char>,
+        /// Optional explanation in gray color is displayed next to the item.
+        exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Typing `RunOptions::skip_sentinel` skips the input, giving a `Selection` with `skipped` set and no value.
+        skippable: bool,
+        /// Maximum number of decimal places, like `Some(2)` for money, at most 15. Any precision is accepted if `None`.
+        /// ```
+        /// use rushterm::{Item, Menu, RunOptions, Value};
+        ///
+        /// let price = |decimals, round| Menu {
+        ///     name: "Main".to_string(),
+        ///     exp: None,
+        ///     items: vec![Item::F64 {
+        ///         name: "Price".to_string(),
+        ///         hotkey: None,
+        ///         exp: None,
+        ///         badges: Vec::new(),
+        ///         skippable: false,
+        ///         decimals,
+        ///         round,
+        ///         current: None,
+        ///         confirm: false,
+        ///         step: None,
+        ///     }],
+        ///     esc: true,
+        /// };
+        /// let run = |menu: Menu, input: &[&str]| {
+        ///     let keys: Vec<&str> = ["Enter"].iter().chain(input).copied().collect();
+        ///     let selection = menu.run_with_stdin_values(&RunOptions::default(), &keys).unwrap();
+        ///     (selection.value, selection.attempt)
+        /// };
+        /// // rejected, then accepted.
+        /// assert_eq!(run(price(Some(2), false), &["3.14159", "3.14"]), (Some(Value::F64(3.14)), Some(2)));
+        /// // rounded half away from zero as typed, though the binary 2.675 is a bit less.
+        /// assert_eq!(run(price(Some(2), true), &["2.675"]), (Some(Value::F64(2.68)), Some(1)));
+        /// assert_eq!(run(price(Some(2), true), &["-9.999"]), (Some(Value::F64(-10.0)), Some(1)));
+        /// assert_eq!(run(price(Some(0), true), &["1e-3"]), (Some(Value::F64(0.0)), Some(1)));
+        /// // more than 15 places are limited to 15.
+        /// let value = run(price(Some(20), true), &["0.12345678901234567"]).0;
+        /// assert_eq!(value, Some(Value::F64(0.123456789012346)));
+        /// ```
+        decimals: Option<u8>,
+        /// Round input having more decimal places than `decimals` instead of rejecting it.
+        round: bool,
+        /// Optional current value is displayed in green after the name, like `Theme=dark`.
+        current: Option<String>,
+        /// Show the parsed value and ask to confirm it by `Locale::yes` before accepting, prompting again if declined. Catches typos in critical numbers.
+        confirm: bool,
+        /// Optional step `Up` and `Down` add to and subtract from the typed value at the prompt, starting from `current` or zero if nothing is typed, so the value can be typed or adjusted.
+        step: Option<f64>,
     },
-    /// A menu item to input `i32`. It can be distinguished by the `=` character after it.
+    /// A menu item to input `i32`, with an optional `-` or `+` sign. A sign alone is invalid and prompts again. It can be distinguished by the `=` character after it.
+    /// ```
+    /// use rushterm::{Item, Menu, RunOptions, Value};
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::I32 {
+    ///         name: "Offset".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         skippable: false,
+    ///         current: None,
+    ///         confirm: false,
+    ///         step: None,
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions::default();
+    /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "-42"]).unwrap();
+    /// assert_eq!(selection.value, Some(Value::I32(-42)));
+    /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "-", "+42"]).unwrap();
+    /// assert_eq!((selection.value, selection.attempt), (Some(Value::I32(42)), Some(2)));
+    /// ```
     I32 {
         /// Value name.
         name: String,
@@ -178,6 +688,40 @@ pub enum Item {
         hotkey: Option<char>,
         /// Optional explanation in gray color is displayed next to the item.
         exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Typing `RunOptions::skip_sentinel` skips the input, giving a `Selection` with `skipped` set and no value.
+        skippable: bool,
+        /// Optional current value is displayed in green after the name, like `Theme=dark`.
+        current: Option<String>,
+        /// Show the parsed value and ask to confirm it by `Locale::yes` before accepting, prompting again if declined. Catches typos in critical numbers.
+        confirm: bool,
+        /// Optional step `Up` and `Down` add to and subtract from the typed value at the prompt, starting from `current` or zero if nothing is typed, so the value can be typed or adjusted.
+        /// ```
+        /// use rushterm::{Item, Menu, RunOptions, Value};
+        ///
+        /// let menu = Menu {
+        ///     name: "Main".to_string(),
+        ///     exp: None,
+        ///     items: vec![Item::I32 {
+        ///         name: "Volume".to_string(),
+        ///         hotkey: None,
+        ///         exp: None,
+        ///         badges: Vec::new(),
+        ///         skippable: false,
+        ///         current: Some("10".to_string()),
+        ///         confirm: false,
+        ///         step: Some(5),
+        ///     }],
+        ///     esc: true,
+        /// };
+        /// let opts = RunOptions::default();
+        /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "42", "Enter"]).unwrap();
+        /// assert_eq!(selection.value, Some(Value::I32(42)));
+        /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "Up", "Up", "Down", "Enter"]);
+        /// assert_eq!(selection.unwrap().value, Some(Value::I32(15)));
+        /// ```
+        step: Option<i32>,
     },
     /// A menu item to input `u32`. It can be distinguished by the `=` character after it.
     U32 {
@@ -187,478 +731,6412 @@ pub enum Item {
         hotkey: Option<char>,
         /// Optional explanation in gray color is displayed next to the item.
         exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Typing `RunOptions::skip_sentinel` skips the input, giving a `Selection` with `skipped` set and no value.
+        skippable: bool,
+        /// Optional current value is displayed in green after the name, like `Theme=dark`.
+        current: Option<String>,
+        /// Show the parsed value and ask to confirm it by `Locale::yes` before accepting, prompting again if declined. Catches typos in critical numbers.
+        /// ```
+        /// use rushterm::{Item, Menu, RunOptions, Value};
+        ///
+        /// let menu = Menu {
+        ///     name: "Main".to_string(),
+        ///     exp: None,
+        ///     items: vec![Item::U32 {
+        ///         name: "Port".to_string(),
+        ///         hotkey: None,
+        ///         exp: None,
+        ///         badges: Vec::new(),
+        ///         skippable: false,
+        ///         current: None,
+        ///         confirm: true,
+        ///         step: None,
+        ///     }],
+        ///     esc: true,
+        /// };
+        /// let opts = RunOptions::default();
+        /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "65535", "y"]).unwrap();
+        /// assert_eq!((selection.value, selection.attempt), (Some(Value::U32(65535)), Some(1)));
+        /// // declining prompts again.
+        /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "65353", "n", "65535", "y"]).unwrap();
+        /// assert_eq!((selection.value, selection.attempt), (Some(Value::U32(65535)), Some(2)));
+        /// ```
+        confirm: bool,
+        /// Optional step `Up` and `Down` add to and subtract from the typed value at the prompt, starting from `current` or zero if nothing is typed, so the value can be typed or adjusted.
+        step: Option<u32>,
+    },
+    /// A menu item to input several numbers on one line, separated by commas or spaces, like `80, 443 8080`. It can be distinguished by the `=` character after it.
+    /// ```
+    /// use rushterm::{Item, Menu, NumberKind, RunOptions, Value};
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::NumberList {
+    ///         name: "Ports".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         skippable: false,
+    ///         kind: NumberKind::U32,
+    ///         current: None,
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions::default();
+    /// let ports = |ports: &[u32]| Some(Value::List(ports.iter().copied().map(Value::U32).collect()));
+    /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "80, 443 8080"]).unwrap();
+    /// assert_eq!((selection.value, selection.attempt), (ports(&[80, 443, 8080]), Some(1)));
+    /// // `-1` is reported as the invalid element and the whole line is asked again.
+    /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "80, -1", "80"]).unwrap();
+    /// assert_eq!((selection.value, selection.attempt), (ports(&[80]), Some(2)));
+    /// // An empty line gives an empty list.
+    /// let selection = menu.run_with_stdin_values(&opts, &["Enter", ""]).unwrap();
+    /// assert_eq!((selection.value, selection.attempt), (ports(&[]), Some(1)));
+    /// ```
+    NumberList {
+        /// Value name.
+        name: String,
+        /// Assigning a hotkey to the item is optional. The hotkey is displayed in yellow.
+        hotkey: Option<char>,
+        /// Optional explanation in gray color is displayed next to the item.
+        exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Typing `RunOptions::skip_sentinel` skips the input, giving a `Selection` with `skipped` set and no value.
+        skippable: bool,
+        /// Type of the numbers.
+        kind: NumberKind,
+        /// Optional current value is displayed in green after the name, like `Theme=dark`.
+        current: Option<String>,
+    },
+    /// A menu item to input a size like `4KB`, `1.5MiB` or `2G`, given as a number of bytes. See `ByteSize` for accepted units. It can be distinguished by the `=` character after it.
+    ByteSize {
+        /// Value name.
+        name: String,
+        /// Assigning a hotkey to the item is optional. The hotkey is displayed in yellow.
+        hotkey: Option<char>,
+        /// Optional explanation in gray color is displayed next to the item.
+        exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Typing `RunOptions::skip_sentinel` skips the input, giving a `Selection` with `skipped` set and no value.
+        skippable: bool,
+        /// Optional current value is displayed in green after the name, like `Theme=dark`.
+        current: Option<String>,
+    },
+    /// A menu item to adjust an `i64` inline by `Left` and `Right`, or `-` and `+`, while hovered, without a prompt. Faster than typing for small ranges like volume. `Left` doesn't go back while it's hovered, but `Backspace` does. Selecting it gives its value. It can be distinguished by the `=` character after it.
+    /// ```
+    /// use rushterm::{Item, Menu, RunOptions, Value};
+    ///
+    /// let menu = Menu {
+    ///     name: "Sound".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::Counter {
+    ///         name: "Volume".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         min: 0,
+    ///         max: 10,
+    ///         step: 4,
+    ///         initial: 5,
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions::default();
+    /// let selection = menu.run_with_stdin_values(&opts, &["Right", "+", "-", "Enter"]);
+    /// assert_eq!(selection.unwrap().value, Some(Value::I64(6)));
+    /// ```
+    Counter {
+        /// Value name.
+        name: String,
+        /// Assigning a hotkey to the item is optional. The hotkey is displayed in yellow.
+        hotkey: Option<char>,
+        /// Optional explanation in gray color is displayed next to the item.
+        exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Lowest value.
+        min: i64,
+        /// Highest value.
+        max: i64,
+        /// Amount added or subtracted by each key press.
+        step: i64,
+        /// Value before any adjustment. It's kept within `min` and `max`.
+        initial: i64,
+    },
+    /// A menu item to input a color as a hex code like `#FF8800`, or a name like `orange`. See `Rgb` for the names. It can be distinguished by the `=` character after it.
+    /// ```
+    /// use rushterm::{Item, Menu, RunOptions, Value};
+    ///
+    /// let menu = Menu {
+    ///     name: "Theme".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::Color {
+    ///         name: "Accent".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         skippable: false,
+    ///         current: None,
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions::default();
+    /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "#ff8800"]).unwrap();
+    /// assert_eq!(selection.value, Some(Value::Color(255, 136, 0)));
+    /// // Malformed hex codes are asked again, and names are case-insensitive.
+    /// let values = ["Enter", "#ff88", "ff88zz", "Orange"];
+    /// let selection = menu.run_with_stdin_values(&opts, &values).unwrap();
+    /// assert_eq!(selection.value, Some(Value::Color(255, 165, 0)));
+    /// assert_eq!(selection.attempt, Some(3));
+    /// ```
+    Color {
+        /// Value name.
+        name: String,
+        /// Assigning a hotkey to the item is optional. The hotkey is displayed in yellow.
+        hotkey: Option<char>,
+        /// Optional explanation in gray color is displayed next to the item.
+        exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Typing `RunOptions::skip_sentinel` skips the input, giving a `Selection` with `skipped` set and no value.
+        skippable: bool,
+        /// Optional current value is displayed in green after the name, like `Theme=dark`.
+        current: Option<String>,
+    },
+    /// A header of the `Item`s after it, up to the next `Group`. `Enter`, `Space` or its hotkey collapses the `Item`s under it, hiding them from navigation and selection, and expands them again. Folds very long menus into sections. It can be distinguished by the `▾` character before it, or `▸` while collapsed. Searching finds `Item`s of collapsed `Group`s too.
+    Group {
+        /// Group name.
+        name: String,
+        /// Assigning a hotkey to the item is optional. The hotkey is displayed in yellow.
+        hotkey: Option<char>,
+        /// Optional explanation in gray color is displayed next to the item.
+        exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Whether the `Item`s under it are collapsed when `Menu` starts. Hotkeys and index numbers of the collapsed `Item`s do nothing, as they aren't displayed.
+        collapsed: bool,
+    },
+    /// A line of informational text among the `Item`s, like a status, an instruction or a computed value. Each line of the text is displayed in gray color on a row of its own. It can't be hovered or selected, navigation skips it and it doesn't take an index number. Set its text on the `Item`s of `Menu::items` between runs to keep it up to date.
+    Label {
+        /// Displayed text, on as many rows as it has lines.
+        text: String,
+    },
+    /// A menu item to input a sensitive `String` like a password. The input is echoed as `*`, and it's given as `Value::Secret`, which `Debug` and serialization redact as `***`, so logging the `Selection` doesn't leak it. It can be distinguished by the `=` character after it.
+    /// ```
+    /// use rushterm::{Item, Menu, RunOptions, Value};
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::Secret {
+    ///         name: "Password".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         skippable: false,
+    ///         required: true,
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions::default();
+    /// let selection = menu.run_with_stdin_values(&opts, &["Enter", "hunter2", "Enter"]).unwrap();
+    /// match &selection.value {
+    ///     Some(Value::Secret(secret)) => assert_eq!(secret.expose(), "hunter2"),
+    ///     other => panic!("unexpected value {:?}", other),
+    /// }
+    /// let debug = format!("{:?}", selection);
+    /// assert!(debug.contains("***"));
+    /// assert!(!debug.contains("hunter2"));
+    /// ```
+    Secret {
+        /// Value name.
+        name: String,
+        /// Assigning a hotkey to the item is optional. The hotkey is displayed in yellow.
+        hotkey: Option<char>,
+        /// Optional explanation in gray color is displayed next to the item.
+        exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Typing `RunOptions::skip_sentinel` skips the input, giving a `Selection` with `skipped` set and no value.
+        skippable: bool,
+        /// Reject empty input and prompt again. Empty input is accepted if `false`.
+        required: bool,
+    },
+    /// A menu item to input JSON, like a structured config pasted at once. Lines are read until a blank one, in raw mode on the terminal, where pasted text keeps its line breaks. With the `serde` feature, the input is prompted again with the parse error until it's valid JSON, and given as `Value::String` of the JSON without whitespace, with the keys of objects sorted. Without it, the lines are given as typed. It can be distinguished by the `=` character after it.
+    /// ```
+    /// # #[cfg(feature = "serde")]
+    /// # {
+    /// use rushterm::{Input, Item, Menu, Output, Reader, RunOptions, Value, Writer};
+    /// use std::io::Cursor;
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::Json {
+    ///         name: "Config".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         skippable: false,
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions::default();
+    /// // the malformed input is prompted again.
+    /// let script = ["Enter", "{\"port\": ", "", "{\"port\": 8080,", "\"tls\": true}", ""];
+    /// let selection = menu.run_with_stdin_values(&opts, &script).unwrap();
+    /// assert_eq!(selection.value, Some(Value::String(r#"{"port":8080,"tls":true}"#.to_string())));
+    /// assert_eq!(selection.attempt, Some(2));
+    ///
+    /// // `Enter`, then a paste keeping its blank line, and two `Enter`s.
+    /// let typed = b"\r\x1b[200~{\"tls\": true,\r\n\r\n\"port\": 8080}\x1b[201~\r\r".to_vec();
+    /// let opts = RunOptions {
+    ///     input: Input::Reader(Reader::new(Cursor::new(typed))),
+    ///     output: Output::Writer(Writer::new(Vec::new())),
+    ///     ..RunOptions::default()
+    /// };
+    /// let selection = menu.run_with(&opts).unwrap();
+    /// assert_eq!(selection.value, Some(Value::String(r#"{"port":8080,"tls":true}"#.to_string())));
+    /// assert_eq!(selection.attempt, Some(1));
+    /// # }
+    /// ```
+    Json {
+        /// Value name.
+        name: String,
+        /// Assigning a hotkey to the item is optional. The hotkey is displayed in yellow.
+        hotkey: Option<char>,
+        /// Optional explanation in gray color is displayed next to the item.
+        exp: Option<String>,
+        /// Optional badges like `NEW` are displayed in brackets after the name, each in its color.
+        badges: Vec<(String, Color)>,
+        /// Typing `RunOptions::skip_sentinel` followed by a blank line skips the input, giving a `Selection` with `skipped` set and no value.
+        skippable: bool,
     },
 }
-impl fmt::Display for Item {
+impl Item {
+    /// Returns the variant name of the `Item`, such as `"Action"` or `"I32"`. Input items display it as the expected type of value.
+    /// ```
+    /// use rushterm::{Item, ItemsProvider, NumberKind};
+    ///
+    /// // each `Item` is named after its variant.
+    /// let items = vec![
+    ///         Item::Action {
+    ///             name: "Action".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             confirm_phrase: None,
+    ///             global_hotkey: None,
+    ///             url: None,
+    ///             checkable: false,
+    ///         },
+    ///         Item::SubMenu {
+    ///             name: "SubMenu".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             items: Vec::new(),
+    ///         },
+    ///         Item::LazySubMenu {
+    ///             name: "LazySubMenu".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             provider: ItemsProvider::new(Vec::new),
+    ///         },
+    ///         Item::Bool {
+    ///             name: "Bool".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             current: None,
+    ///         },
+    ///         Item::Char {
+    ///             name: "Char".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             skippable: false,
+    ///             current: None,
+    ///             allowed: None,
+    ///             ignore_case: false,
+    ///         },
+    ///         Item::String {
+    ///             name: "String".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             skippable: false,
+    ///             required: false,
+    ///             history: Vec::new(),
+    ///             current: None,
+    ///         },
+    ///         Item::Secret {
+    ///             name: "Secret".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             skippable: false,
+    ///             required: false,
+    ///         },
+    ///         Item::F32 {
+    ///             name: "F32".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             skippable: false,
+    ///             current: None,
+    ///             confirm: false,
+    ///             step: None,
+    ///         },
+    ///         Item::F64 {
+    ///             name: "F64".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             skippable: false,
+    ///             decimals: None,
+    ///             round: false,
+    ///             current: None,
+    ///             confirm: false,
+    ///             step: None,
+    ///         },
+    ///         Item::I32 {
+    ///             name: "I32".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             skippable: false,
+    ///             current: None,
+    ///             confirm: false,
+    ///             step: None,
+    ///         },
+    ///         Item::U32 {
+    ///             name: "U32".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             skippable: false,
+    ///             current: None,
+    ///             confirm: false,
+    ///             step: None,
+    ///         },
+    ///         Item::NumberList {
+    ///             name: "NumberList".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             skippable: false,
+    ///             kind: NumberKind::I32,
+    ///             current: None,
+    ///         },
+    ///         Item::ByteSize {
+    ///             name: "ByteSize".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             skippable: false,
+    ///             current: None,
+    ///         },
+    ///         Item::Color {
+    ///             name: "Color".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             skippable: false,
+    ///             current: None,
+    ///         },
+    ///         Item::Counter {
+    ///             name: "Counter".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             min: 0,
+    ///             max: 10,
+    ///             step: 1,
+    ///             initial: 0,
+    ///         },
+    ///         Item::Group {
+    ///             name: "Group".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             collapsed: false,
+    ///         },
+    ///     Item::Label {
+    ///         text: "Label".to_string(),
+    ///     },
+    /// ];
+    /// for item in &items {
+    ///     assert_eq!(item.type_name(), item.name());
+    /// }
+    /// ```
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Item::Action { .. } => "Action",
+            Item::SubMenu { .. } => "SubMenu",
+            Item::LazySubMenu { .. } => "LazySubMenu",
+            Item::Bool { .. } => "Bool",
+            Item::Char { .. } => "Char",
+            Item::String { .. } => "String",
+            Item::Secret { .. } => "Secret",
+            Item::Json { .. } => "Json",
+            Item::F32 { .. } => "F32",
+            Item::F64 { .. } => "F64",
+            Item::NumberList { .. } => "NumberList",
+            Item::I32 { .. } => "I32",
+            Item::U32 { .. } => "U32",
+            Item::ByteSize { .. } => "ByteSize",
+            Item::Color { .. } => "Color",
+            Item::Counter { .. } => "Counter",
+            Item::Group { .. } => "Group",
+            Item::Label { .. } => "Label",
+        }
+    }
+    /// Returns the name of the `Item`, or the text of a `Label`.
+    /// ```
+    /// use rushterm::Item;
+    ///
+    /// let sub_menu = Item::SubMenu {
+    ///     name: "Network".to_string(),
+    ///     hotkey: Some('n'),
+    ///     exp: Some("Proxy and Wi-Fi".to_string()),
+    ///     badges: Vec::new(),
+    ///     items: Vec::new(),
+    /// };
+    /// let string = Item::String {
+    ///     name: "Host".to_string(),
+    ///     hotkey: None,
+    ///     exp: Some("Name or address".to_string()),
+    ///     badges: Vec::new(),
+    ///     skippable: false,
+    ///     required: false,
+    ///     history: Vec::new(),
+    ///     current: None,
+    /// };
+    /// let label = Item::Label {
+    ///     text: "Connection".to_string(),
+    /// };
+    /// assert_eq!(sub_menu.name(), "Network");
+    /// assert_eq!(string.name(), "Host");
+    /// assert_eq!(label.name(), "Connection");
+    /// ```
+    pub fn name(&self) -> &str {
+        match self {
+            Item::Action { name, .. }
+            | Item::SubMenu { name, .. }
+            | Item::LazySubMenu { name, .. }
+            | Item::Bool { name, .. }
+            | Item::Char { name, .. }
+            | Item::String { name, .. }
+            | Item::Secret { name, .. }
+            | Item::Json { name, .. }
+            | Item::F32 { name, .. }
+            | Item::F64 { name, .. }
+            | Item::I32 { name, .. }
+            | Item::U32 { name, .. }
+            | Item::NumberList { name, .. }
+            | Item::ByteSize { name, .. }
+            | Item::Color { name, .. }
+            | Item::Counter { name, .. }
+            | Item::Group { name, .. } => name,
+            Item::Label { text } => text,
+        }
+    }
+    /// Returns the explanation of the `Item`. A `Label` has none.
+    /// ```
+    /// use rushterm::Item;
+    ///
+    /// let sub_menu = Item::SubMenu {
+    ///     name: "Network".to_string(),
+    ///     hotkey: Some('n'),
+    ///     exp: Some("Proxy and Wi-Fi".to_string()),
+    ///     badges: Vec::new(),
+    ///     items: Vec::new(),
+    /// };
+    /// let string = Item::String {
+    ///     name: "Host".to_string(),
+    ///     hotkey: None,
+    ///     exp: Some("Name or address".to_string()),
+    ///     badges: Vec::new(),
+    ///     skippable: false,
+    ///     required: false,
+    ///     history: Vec::new(),
+    ///     current: None,
+    /// };
+    /// let label = Item::Label {
+    ///     text: "Connection".to_string(),
+    /// };
+    /// assert_eq!(sub_menu.exp(), Some("Proxy and Wi-Fi"));
+    /// assert_eq!(string.exp(), Some("Name or address"));
+    /// assert_eq!(label.exp(), None);
+    /// ```
+    pub fn exp(&self) -> Option<&str> {
+        match self {
+            Item::Action { exp, .. }
+            | Item::SubMenu { exp, .. }
+            | Item::LazySubMenu { exp, .. }
+            | Item::Bool { exp, .. }
+            | Item::Char { exp, .. }
+            | Item::String { exp, .. }
+            | Item::Secret { exp, .. }
+            | Item::Json { exp, .. }
+            | Item::F32 { exp, .. }
+            | Item::F64 { exp, .. }
+            | Item::I32 { exp, .. }
+            | Item::U32 { exp, .. }
+            | Item::NumberList { exp, .. }
+            | Item::ByteSize { exp, .. }
+            | Item::Color { exp, .. }
+            | Item::Counter { exp, .. }
+            | Item::Group { exp, .. } => exp.as_deref(),
+            Item::Label { .. } => None,
+        }
+    }
+    /// Returns the badges of the `Item`.
+    pub fn badges(&self) -> &[(String, Color)] {
+        match self {
+            Item::Action { badges, .. }
+            | Item::SubMenu { badges, .. }
+            | Item::LazySubMenu { badges, .. }
+            | Item::Bool { badges, .. }
+            | Item::Char { badges, .. }
+            | Item::String { badges, .. }
+            | Item::Secret { badges, .. }
+            | Item::Json { badges, .. }
+            | Item::F32 { badges, .. }
+            | Item::F64 { badges, .. }
+            | Item::I32 { badges, .. }
+            | Item::U32 { badges, .. }
+            | Item::NumberList { badges, .. }
+            | Item::ByteSize { badges, .. }
+            | Item::Color { badges, .. }
+            | Item::Counter { badges, .. }
+            | Item::Group { badges, .. } => badges,
+            Item::Label { .. } => &[],
+        }
+    }
+    /// Returns whether the input `Item` can be skipped.
+    fn skippable(&self) -> bool {
+        match self {
+            Item::Char { skippable, .. }
+            | Item::String { skippable, .. }
+            | Item::Secret { skippable, .. }
+            | Item::Json { skippable, .. }
+            | Item::F32 { skippable, .. }
+            | Item::F64 { skippable, .. }
+            | Item::I32 { skippable, .. }
+            | Item::U32 { skippable, .. }
+            | Item::NumberList { skippable, .. }
+            | Item::ByteSize { skippable, .. }
+            | Item::Color { skippable, .. } => *skippable,
+            _ => false,
+        }
+    }
+    /// Returns whether the parsed input of the `Item` is confirmed before it's accepted.
+    fn confirms(&self) -> bool {
+        match self {
+            Item::F32 { confirm, .. }
+            | Item::F64 { confirm, .. }
+            | Item::I32 { confirm, .. }
+            | Item::U32 { confirm, .. } => *confirm,
+            _ => false,
+        }
+    }
+    /// Returns the typed text with the `step` of the `Item` added, or subtracted if not `up`, or `None` if it has no step or the text isn't a number. An empty text starts from `current`, or else zero.
+    fn stepped(&self, text: &str, up: bool) -> Option<String> {
+        let text = match text {
+            "" => self.current().unwrap_or("0"),
+            text => text,
+        };
+        match self {
+            Item::F32 {
+                step: Some(step), ..
+            } => step_decimal(text, *step as f64, &step.to_string(), up),
+            Item::F64 {
+                step: Some(step), ..
+            } => step_decimal(text, *step, &step.to_string(), up),
+            Item::I32 {
+                step: Some(step), ..
+            } => {
+                let value: i32 = text.parse().ok()?;
+                let value = match up {
+                    true => value.saturating_add(*step),
+                    false => value.saturating_sub(*step),
+                };
+                Some(value.to_string())
+            }
+            Item::U32 {
+                step: Some(step), ..
+            } => {
+                let value: u32 = text.parse().ok()?;
+                let value = match up {
+                    true => value.saturating_add(*step),
+                    false => value.saturating_sub(*step),
+                };
+                Some(value.to_string())
+            }
+            _ => None,
+        }
+    }
+    /// Returns the current value of the `Item`. Set it on the `Item`s of `Menu::items` after each change to keep a settings `Menu` live.
+    /// ```
+    /// use rushterm::{Item, Menu, RunOptions, Script};
+    ///
+    /// let mut menu = Menu {
+    ///     name: "Settings".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::String {
+    ///         name: "Theme".to_string(),
+    ///         hotkey: Some('t'),
+    ///         exp: Some("Colors.".to_string()),
+    ///         badges: Vec::new(),
+    ///         skippable: false,
+    ///         required: false,
+    ///         history: vec![],
+    ///         current: Some("dark".to_string()),
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions::default();
+    /// assert_eq!(menu.items[0].current(), Some("dark"));
+    /// assert_eq!(menu.render_to_string(&opts, 80).lines().nth(1), Some("0.(T)  Theme=dark Colors."));
+    ///
+    /// // Each entered value becomes the current one.
+    /// let opts = RunOptions {
+    ///     script: Some(Script::new(["t", "light", "Esc"])),
+    ///     ..opts
+    /// };
+    /// menu.run_loop(&opts, |selection, menu| {
+    ///     if let Item::String { current, .. } = &mut menu.items[0] {
+    ///         *current = selection.value.as_ref().map(|f| f.to_plain_string());
+    ///     }
+    /// })
+    /// .unwrap();
+    /// assert_eq!(menu.items[0].current(), Some("light"));
+    /// assert_eq!(menu.render_to_string(&opts, 80).lines().nth(1), Some("0.(T)  Theme=light Colors."));
+    /// ```
+    pub fn current(&self) -> Option<&str> {
+        match self {
+            Item::Bool { current, .. }
+            | Item::Char { current, .. }
+            | Item::String { current, .. }
+            | Item::F32 { current, .. }
+            | Item::F64 { current, .. }
+            | Item::I32 { current, .. }
+            | Item::U32 { current, .. }
+            | Item::NumberList { current, .. }
+            | Item::ByteSize { current, .. }
+            | Item::Color { current, .. } => current.as_deref(),
+            _ => None,
+        }
+    }
+    /// Returns the current value of the `Item` to change it, or `None` if it has none.
+    fn current_mut(&mut self) -> Option<&mut Option<String>> {
+        match self {
+            Item::Bool { current, .. }
+            | Item::Char { current, .. }
+            | Item::String { current, .. }
+            | Item::F32 { current, .. }
+            | Item::F64 { current, .. }
+            | Item::I32 { current, .. }
+            | Item::U32 { current, .. }
+            | Item::NumberList { current, .. }
+            | Item::ByteSize { current, .. }
+            | Item::Color { current, .. } => Some(current),
+            _ => None,
+        }
+    }
+    /// Returns the hotkey of the `Item`. A `Label` has none.
+    /// ```
+    /// use rushterm::Item;
+    ///
+    /// let sub_menu = Item::SubMenu {
+    ///     name: "Network".to_string(),
+    ///     hotkey: Some('n'),
+    ///     exp: Some("Proxy and Wi-Fi".to_string()),
+    ///     badges: Vec::new(),
+    ///     items: Vec::new(),
+    /// };
+    /// let string = Item::String {
+    ///     name: "Host".to_string(),
+    ///     hotkey: None,
+    ///     exp: Some("Name or address".to_string()),
+    ///     badges: Vec::new(),
+    ///     skippable: false,
+    ///     required: false,
+    ///     history: Vec::new(),
+    ///     current: None,
+    /// };
+    /// let label = Item::Label {
+    ///     text: "Connection".to_string(),
+    /// };
+    /// assert_eq!(sub_menu.hotkey(), Some('n'));
+    /// assert_eq!(string.hotkey(), None);
+    /// assert_eq!(label.hotkey(), None);
+    /// ```
+    pub fn hotkey(&self) -> Option<char> {
+        match self {
+            Item::Action { hotkey, .. }
+            | Item::SubMenu { hotkey, .. }
+            | Item::LazySubMenu { hotkey, .. }
+            | Item::Bool { hotkey, .. }
+            | Item::Char { hotkey, .. }
+            | Item::String { hotkey, .. }
+            | Item::Secret { hotkey, .. }
+            | Item::Json { hotkey, .. }
+            | Item::F32 { hotkey, .. }
+            | Item::F64 { hotkey, .. }
+            | Item::I32 { hotkey, .. }
+            | Item::U32 { hotkey, .. }
+            | Item::NumberList { hotkey, .. }
+            | Item::ByteSize { hotkey, .. }
+            | Item::Color { hotkey, .. }
+            | Item::Counter { hotkey, .. }
+            | Item::Group { hotkey, .. } => *hotkey,
+            Item::Label { .. } => None,
+        }
+    }
+    /// Returns `Item`s of a sub menu. `LazySubMenu` builds them by its provider.
+    fn sub_items(&self) -> Option<Vec<Item>> {
+        match self {
+            Item::SubMenu { items, .. } => Some(items.clone()),
+            Item::LazySubMenu { provider, .. } => Some(provider.items()),
+            _ => None,
+        }
+    }
+}
+/// Function building `Item`s of `Item::LazySubMenu`. Cloning the provider doesn't clone the function or call it, so clones of an `Item` share the same provider. The function is `Send` and `Sync`, so `Item`s and `Menu`s can be moved and shared across threads.
+#[derive(Clone)]
+pub struct ItemsProvider(Arc<dyn Fn() -> Vec<Item> + Send + Sync>);
+impl ItemsProvider {
+    /// Wraps the function building `Item`s.
+    pub fn new(provider: impl Fn() -> Vec<Item> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(provider))
+    }
+    /// Calls the function and returns the built `Item`s.
+    pub fn items(&self) -> Vec<Item> {
+        (self.0)()
+    }
+}
+impl fmt::Debug for ItemsProvider {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "{:?}", self)
+        write!(f, "ItemsProvider")
     }
 }
-/// Starting point for creating a menu instance.
-pub struct Menu {
-    /// `Menu` name is displayed at the top.
-    pub name: String,
-    /// Optional explanation in gray color next to the menu name.
-    pub exp: Option<String>,
-    /// `Menu` items should be vector of `Item`s.
-    pub items: Vec<Item>,
-    /// Enable exiting menu by `Esc` hotkey. Usually set it to `true`. But it may be useful to set to `false` when you want to restrict the user from escaping without any selection.
-    pub esc: bool,
+impl PartialEq for ItemsProvider {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
 }
-/// Gives the data of the selection made in the menu.
-#[derive(Debug, PartialEq)]
-pub struct Selection {
-    /// Name of selected `Item`.
-    pub name: String,
-    /// Vector containing direction of the selected item in the menu tree.
-    pub path: Vec<String>,
-    /// Input by user, if it exists.
-    pub value: Option<Value>,
-    /// Length of value.
-    pub len: Option<usize>,
-    /// Number of attempts of input.
-    pub attempt: Option<i32>,
+/// Callback of `RunOptions::on_unhandled_key`. Like `ItemsProvider`, clones share the same function.
+#[derive(Clone)]
+pub struct KeyHandler(Rc<dyn Fn(KeyEvent) -> KeyFlow>);
+impl KeyHandler {
+    /// Wraps the function handling keys.
+    pub fn new(handler: impl Fn(KeyEvent) -> KeyFlow + 'static) -> Self {
+        Self(Rc::new(handler))
+    }
+    /// Calls the function with the key.
+    pub fn handle(&self, event: KeyEvent) -> KeyFlow {
+        (self.0)(event)
+    }
 }
-/// Input by user.
-#[derive(Debug, PartialEq)]
-pub enum Value {
-    Bool(bool),
-    Char(char),
-    String(String),
-    F32(f32),
-    I32(i32),
-    U32(u32),
+impl fmt::Debug for KeyHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "KeyHandler")
+    }
 }
-impl Menu {
-    /// Prints out `Item`s, executes the `Menu` and returns `Result`.
-    pub fn run(&self) -> Result<Selection, String> {
-        let mut stdout_ins = stdout();
-        let mut hover = 0 as usize;
-        self.printer(&mut stdout_ins, &mut hover)
-    }
-    fn rerun(&self, hover: usize) -> Result<Selection, String> {
-        let mut stdout_ins = stdout();
-        let mut hover = hover;
-        self.printer(&mut stdout_ins, &mut hover)
-    }
-    fn printer(&self, stdout_ins: &mut Stdout, hover: &mut usize) -> Result<Selection, String> {
-        self.print_top(&vec![self.name.to_string()]);
-        self.print_items(hover);
-        self.print_bottom(false, hover);
-        self.matcher(stdout_ins, hover)
-    }
-    fn matcher(&self, stdout_ins: &mut Stdout, hover: &mut usize) -> Result<Selection, String> {
-        let keycode = self.poll_read();
-        let key = self.match_keycode(keycode);
-        let res = self.match_selection(
-            &key,
-            false,
-            stdout_ins,
-            &mut vec![self.name.to_string()],
-            hover,
-        );
-        if res == Err("No Selection".to_string()) {
-            self.matcher(stdout_ins, hover)
-        } else {
-            res
-        }
+impl PartialEq for KeyHandler {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
     }
-    fn run_sub(&self, path: &mut Vec<String>) -> Result<Selection, String> {
-        let mut stdout_ins = stdout();
-        let mut hover = 0 as usize;
-        self.printer_sub(path, &mut stdout_ins, &mut hover)
+}
+/// Scripted input replacing the terminal, given by `RunOptions::script`. Like `KeyHandler`, clones share the same actions, so `SubMenu`s carry on with the rest of the script.
+///
+/// Each action is consumed by whatever the `Menu` waits for next. While `Item`s are displayed, an action is a key: `Up`, `Down`, `Left`, `Right`, `Home`, `End`, `Enter`, `Esc`, `Backspace`, `Delete`, `Tab`, `BackTab` for `Shift+Tab`, `Space`, `F1` to `F12`, or a single character like `3` or `s`. While an input `Item` prompts for a value, the action is the typed line as is, like `42`. At the prompt of a `String` with `history`, or a number with a `step`, which are edited in raw mode, an action is a key again and any other text is typed at the cursor, like `["Up", "Home", "new ", "Enter"]` prefixing the last entry with `new `.
+#[derive(Clone)]
+pub struct Script(
+    /// Actions left, each with its line: the line of the script file, or the position among the actions given to `Script::new()`, from 1, and whether it types the text after `>` of a script file.
+    Rc<RefCell<VecDeque<(usize, String, bool)>>>,
+    /// Whether an action was waited for after the last one, which ends the run with `MenuError::ScriptEnded`.
+    Rc<Cell<bool>>,
+    /// Line of an action taken as a key that names none, which ends the run with `MenuError::InvalidScriptLine`.
+    Rc<Cell<Option<usize>>>,
+);
+impl Script {
+    /// Wraps the actions, in the order they are consumed.
+    pub fn new<T: Into<String>>(actions: impl IntoIterator<Item = T>) -> Self {
+        let actions = actions.into_iter().map(Into::into);
+        Self::with_lines(
+            (1..)
+                .zip(actions)
+                .map(|(line, action)| (line, action, false))
+                .collect(),
+        )
     }
-    fn rerun_sub(&self, path: &mut Vec<String>, hover: usize) -> Result<Selection, String> {
-        let mut stdout_ins = stdout();
-        let mut hover = hover;
-        self.printer_sub(path, &mut stdout_ins, &mut hover)
+    /// Wraps the actions with their lines, and whether they type text.
+    fn with_lines(actions: VecDeque<(usize, String, bool)>) -> Self {
+        Self(Rc::new(RefCell::new(actions)), Rc::default(), Rc::default())
     }
-    fn printer_sub(
-        &self,
-        path: &mut Vec<String>,
-        stdout_ins: &mut Stdout,
-        hover: &mut usize,
-    ) -> Result<Selection, String> {
-        self.print_top(path);
-        self.print_items(hover);
-        self.print_bottom(true, hover);
-        self.matcher_sub(stdout_ins, path, hover)
-    }
-    fn matcher_sub(
-        &self,
-        stdout_ins: &mut Stdout,
-        path: &mut Vec<String>,
-        hover: &mut usize,
-    ) -> Result<Selection, String> {
-        let keycode = self.poll_read();
-        let key = self.match_keycode(keycode);
-        let res = self.match_selection(&key, true, stdout_ins, path, hover);
-        if res == Err("No Selection".to_string()) {
-            self.matcher_sub(stdout_ins, path, hover)
-        } else {
-            res
+    /// Parses a script file's text: each line is an action, with trailing whitespace removed. A line names a key, like `Down` or `s`, or types the text after `>` at a prompt, like `> 8080`. Blank lines and lines starting with `#` are skipped. Returns `MenuError::InvalidScriptLine` for a line naming no key, like a misspelled `Dwn`, or containing control characters like a tab.
+    /// ```
+    /// use rushterm::{Item, Menu, MenuError, RunOptions, Script, Value};
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::U32 {
+    ///         name: "Port".to_string(),
+    ///         hotkey: Some('p'),
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         skippable: false,
+    ///         current: None,
+    ///         confirm: false,
+    ///         step: None,
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let script = Script::parse("# set the port\np\n> 8080\n").unwrap();
+    /// let opts = RunOptions {
+    ///     script: Some(script),
+    ///     ..RunOptions::default()
+    /// };
+    /// assert_eq!(menu.run_with(&opts).unwrap().value, Some(Value::U32(8080)));
+    /// assert_eq!(Script::parse("Down\nDwn\nEnter"), Err(MenuError::InvalidScriptLine(2)));
+    /// assert_eq!(Script::parse("> 80\t80"), Err(MenuError::InvalidScriptLine(1)));
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, MenuError> {
+        let mut actions = VecDeque::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (action, typed) = match line.strip_prefix('>') {
+                Some(typed) => (typed.strip_prefix(' ').unwrap_or(typed), true),
+                None if Script::key_code(line).is_some() => (line, false),
+                None => return Err(MenuError::InvalidScriptLine(n + 1)),
+            };
+            if action.chars().any(char::is_control) {
+                return Err(MenuError::InvalidScriptLine(n + 1));
+            }
+            actions.push_back((n + 1, action.to_string(), typed));
         }
+        Ok(Self::with_lines(actions))
     }
-    fn print_top(&self, path: &Vec<String>) {
-        for dir in path {
-            print!("{}/", dir);
-        }
-        if let Some(exp) = &self.exp {
-            print!(" {}", String::from(exp).dark_grey());
+    /// Reads and parses a script file, as `parse()` does. Returns `MenuError::ScriptUnreadable` if the file can't be read.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, MenuError> {
+        let text = fs::read_to_string(path).map_err(|f| MenuError::ScriptUnreadable(f.kind()))?;
+        Self::parse(&text)
+    }
+    /// Takes the next action, or `None` if the script has ended, which marks it `ended()` for the `Menu` to stop.
+    fn next(&self) -> Option<String> {
+        self.next_action().map(|(_, action, _)| action)
+    }
+    /// Same as `next()`, but gives the line of the action too, and whether it types text.
+    fn next_action(&self) -> Option<(usize, String, bool)> {
+        let action = self.0.borrow_mut().pop_front();
+        if action.is_none() {
+            self.1.set(true);
         }
-        println!();
+        action
     }
-    fn print_items(&self, hover: &mut usize) {
-        for (i, item) in self.items.iter().enumerate() {
-            match item {
-                Item::Action { name, hotkey, exp } => {
-                    self.print_hotkey(&i, hotkey);
-                    self.print_name_exp(&i, hover, false, name, exp);
+    /// Whether the `Menu` waited for an action after the last one, or took an action as a key that names none.
+    fn ended(&self) -> bool {
+        self.1.get() || self.2.get().is_some()
+    }
+    /// Returns the line of the action taken as a key that names none.
+    fn invalid_line(&self) -> Option<usize> {
+        self.2.get()
+    }
+    /// Takes the next action as a key, or `Esc` if the script has ended or the action isn't a key, like a text typed after `>`. Either ends the run, the latter with `MenuError::InvalidScriptLine`.
+    fn next_key(&self) -> KeyEvent {
+        let code = match self.next_action() {
+            Some((line, action, typed)) => match Script::key_code(&action).filter(|_| !typed) {
+                Some(code) => code,
+                None => {
+                    self.2.set(Some(line));
+                    KeyCode::Esc
+                }
+            },
+            None => KeyCode::Esc,
+        };
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+    /// Returns the key the action names, or `None` if it's not a key.
+    fn key_code(action: &str) -> Option<KeyCode> {
+        let code = match action {
+            "Up" => KeyCode::Up,
+            "Down" => KeyCode::Down,
+            "Left" => KeyCode::Left,
+            "Right" => KeyCode::Right,
+            "Home" => KeyCode::Home,
+            "End" => KeyCode::End,
+            "Enter" => KeyCode::Enter,
+            "Esc" => KeyCode::Esc,
+            "Backspace" => KeyCode::Backspace,
+            "Delete" => KeyCode::Delete,
+            "Tab" => KeyCode::Tab,
+            "BackTab" => KeyCode::BackTab,
+            "Space" => KeyCode::Char(' '),
+            _ => match (action.strip_prefix('F'), action.chars().count()) {
+                (Some(number), _) if number.parse::<u8>().is_ok() => {
+                    KeyCode::F(number.parse().expect("function key number"))
+                }
+                (_, 1) => KeyCode::Char(action.chars().next().expect("key char")),
+                _ => return None,
+            },
+        };
+        Some(code)
+    }
+}
+impl fmt::Debug for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "Script")
+    }
+}
+impl PartialEq for Script {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+/// Result of a `Menu` run, as `Menu::run_with()` returns it.
+pub type MenuOutcome = Result<Selection, MenuError>;
+/// A `Menu` driven one key at a time by a program owning the event loop, created by `Menu::session()`. The program reads keys from wherever it likes, passes them to `handle_key()` and calls `render()` when it redraws, interleaving any other work between keys.
+///
+/// The session keeps the levels of the `Menu` down to the displayed `SubMenu`, each with its hover and other interactive state, and advances them by each key. `Menu::run_with()` drives the same levels with keys read from the terminal, so callbacks of `RunOptions` run once per key as they do there. At the prompt of an input `Item` or of `Item::Action::confirm_phrase`, keys edit the typed line until `Enter` hands it over.
+pub struct MenuSession<'a> {
+    menu: &'a Menu,
+    /// Options of the `Menu`, writing to the `Writer` of the session.
+    opts: RunOptions,
+    /// The running `Menu`, writing nowhere, or `None` once it has ended.
+    run: Option<MenuRun<'a>>,
+    /// Line typed so far at a prompt.
+    edit: LineEdit,
+    /// Lines entered so far at the prompt of a `Json`, which ends at a blank one.
+    lines: Vec<String>,
+    /// Number of lines `render()` wrote last, to clear them before the next frame.
+    height: usize,
+}
+impl MenuSession<'_> {
+    /// Handles the key as the `Menu` would if it was pressed on the terminal. Returns the outcome once, when the `Menu` ends, and `None` before and after that.
+    pub fn handle_key(&mut self, event: KeyEvent) -> Option<MenuOutcome> {
+        let run = self.run.as_mut()?;
+        let outcome = match run.prompt {
+            Some(Prompt::Phrase(..)) | Some(Prompt::Input(_)) => {
+                let item = run.input_item();
+                let trim = item.map_or(TrimPolicy::Both, |f| input_trim(&self.opts, f));
+                let history = match item {
+                    Some(Item::String { history, .. }) => history.as_slice(),
+                    _ => &[],
+                };
+                match (event.code, item) {
+                    (KeyCode::Enter, Some(Item::Json { .. })) => {
+                        let text = mem::take(&mut self.edit).text(trim);
+                        match text.is_empty() {
+                            true => run.line(mem::take(&mut self.lines).join("\n")),
+                            false => {
+                                self.lines.push(text);
+                                None
+                            }
+                        }
+                    }
+                    (KeyCode::Enter, _) => {
+                        let text = mem::take(&mut self.edit).text(trim);
+                        run.line(text)
+                    }
+                    _ if event.modifiers.contains(KeyModifiers::CONTROL) => None,
+                    (code, Some(item)) => {
+                        self.edit.edit_key(item, code, history);
+                        None
+                    }
+                    (code, None) => {
+                        self.edit.key(code, history);
+                        None
+                    }
                 }
-                Item::SubMenu {
-                    name, hotkey, exp, ..
-                } => {
-                    self.print_hotkey(&i, hotkey);
-                    self.print_name_exp(&i, hover, true, &("+".to_owned() + name), exp);
-                }
-                Item::Bool { name, hotkey, exp } => {
-                    self.print_hotkey(&i, hotkey);
-                    self.print_name_exp(&i, hover, true, &("+".to_owned() + name + "="), exp);
-                }
-                Item::Char { name, hotkey, exp }
-                | Item::String { name, hotkey, exp }
-                | Item::F32 { name, hotkey, exp }
-                | Item::I32 { name, hotkey, exp }
-                | Item::U32 { name, hotkey, exp } => {
-                    self.print_hotkey(&i, hotkey);
-                    self.print_name_exp(&i, hover, false, &(name.to_owned() + "="), exp);
-                }
-            }
-        }
-    }
-    fn print_bottom(&self, is_sub: bool, hover: &mut usize) {
-        print!(
-            "{}{}{}{}{}{}{}",
-            "(".dark_grey(),
-            "Up".yellow(),
-            ")".dark_grey(),
-            "(".dark_grey(),
-            "Down".yellow(),
-            ") ".dark_grey(),
-            "Move",
-        );
-        if let Item::SubMenu { .. } | Item::Bool { .. } = self.items[*hover] {
-            print!(
-                "{}{}{}{}{}{}{}",
-                ", (".dark_grey(),
-                "Enter".yellow(),
-                ")".dark_grey(),
-                "(".dark_grey(),
-                "Right".yellow(),
-                ") ".dark_grey(),
-                "Select",
-            );
-        } else {
-            print!(
-                "{}{}{}{}",
-                ", (".dark_grey(),
-                "Enter".yellow(),
-                ") ".dark_grey(),
-                "Select",
-            );
-        }
-        if is_sub {
-            print!(
-                "{}{}{}{}{}{}{}",
-                ", (".dark_grey(),
-                "Backspace".yellow(),
-                ")".dark_grey(),
-                "(".dark_grey(),
-                "Left".yellow(),
-                ") ".dark_grey(),
-                "Back",
-            );
-            if self.esc {
-                print!(
-                    "{}{}{}{}",
-                    ", (".dark_grey(),
-                    "Esc".yellow(),
-                    ") ".dark_grey(),
-                    "Exit",
-                );
-            }
-        } else {
-            if self.esc {
-                print!(
-                    "{}{}{}{}",
-                    ", (".dark_grey(),
-                    "Esc".yellow(),
-                    ") ".dark_grey(),
-                    "Exit",
-                );
             }
+            _ => run.key(event),
+        }?;
+        self.run = None;
+        Some(outcome.map(|f| self.menu.trace(&self.opts, f)))
+    }
+    /// Writes the frame the `Menu` displays now to the `Writer` of the session, clearing the frame written by the previous call. Writes nothing but the clearing once the `Menu` has ended. Returns `MenuError::Io` if writing fails.
+    pub fn render(&mut self) -> Result<(), MenuError> {
+        let opts = &self.opts.for_run();
+        let mut term = opts.output.term();
+        if self.height > 0 {
+            let lines = self.height as u16;
+            self.menu.clear_lines(&mut term, lines);
+        }
+        self.height = 0;
+        if let Some(run) = &mut self.run {
+            let buffer = Rc::new(RefCell::new(Vec::new()));
+            let frame_opts = RunOptions {
+                output: Output::Writer(Writer(buffer.clone(), Rc::default(), false)),
+                ..opts.clone()
+            };
+            run.render(&frame_opts, &self.edit, &self.lines);
+            let frame = buffer.borrow();
+            self.height = frame.iter().filter(|f| **f == b'\n').count();
+            term.write_all(&frame).expect("write");
+        }
+        term.flush().expect("flush");
+        match opts.error() {
+            Some(err) => Err(err),
+            None => Ok(()),
         }
-        println!();
-        println!(
-            "{}",
-            "Press an index number or a hotkey to select:".dark_grey()
-        );
     }
-    fn poll_read(&self) -> KeyCode {
-        loop {
-            if let Ok(Event::Key(KeyEvent { code, .. })) = read() {
-                break code;
+    /// Returns the level of the `Menu` waiting for a key, or `None` once it has ended.
+    pub fn state(&self) -> Option<SessionState> {
+        self.run.as_ref().map(|f| {
+            let level = f.levels.last().expect("level");
+            SessionState {
+                path: level.path.to_vec(),
+                hover: Some(level.state.hover).filter(|f| *f != UNFOCUSED),
             }
+        })
+    }
+}
+/// Level of a `Menu` waiting for a key in a `MenuSession`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionState {
+    /// Names of the `Menu` and the `SubMenu`s down to the displayed level, like `Selection::path`.
+    pub path: Vec<String>,
+    /// Index of the hovered `Item` of the displayed level, or `None` while nothing is hovered, like at the start with `RunOptions::start_unfocused`.
+    pub hover: Option<usize>,
+}
+/// A running `Menu`, advanced by one key or line at a time: its levels down to the displayed one, and the prompt it waits for instead of a key, if any. `Menu::run_with()` drives it by the keys and lines it reads, a `MenuSession` by the keys its program passes.
+struct MenuRun<'a> {
+    levels: Vec<Level<'a>>,
+    prompt: Option<Prompt>,
+}
+/// Displayed or entered level of a running `Menu`: the `Menu` itself, a `SubMenu` or the choice of a `Bool`.
+struct Level<'a> {
+    menu: Cow<'a, Menu>,
+    /// Options the level runs with, like `sub_opts()` for a `SubMenu`.
+    opts: RunOptions,
+    /// Names of the `Menu` and the `SubMenu`s down to the level.
+    path: Vec<String>,
+    state: State,
+    /// Index of the `Item` of the level above that entered this one, `None` for the `Menu` itself.
+    index: Option<usize>,
+    /// Whether the level is the `true` or `false` choice of a `Bool`, whose selection gives the value of the `Bool`.
+    choice: bool,
+}
+/// What a running `Menu` waits for instead of a key for its `Item`s.
+enum Prompt {
+    /// Any key clearing `RunOptions::splash`, which takes so many lines.
+    Splash(u16),
+    /// The yes key of `Locale` to the question, like `RunOptions::confirm_exit` asks, ending the level with the outcome.
+    Confirm(String, LevelOutcome),
+    /// A line of the phrase of `Item::Action::confirm_phrase`, selecting the `Action` as given.
+    Phrase(String, Selection),
+    /// A line of input for an input `Item`.
+    Input(InputPrompt),
+    /// The yes or no answer confirming the value entered for an input `Item` with `confirm`, and the length of its text.
+    Value(InputPrompt, Value, usize),
+}
+/// Prompt of an input `Item` for its value.
+struct InputPrompt {
+    /// Index of the `Item`.
+    index: usize,
+    /// Number of the attempt, counting rejected and declined values.
+    attempt: i32,
+    /// Lines the last input takes, more than one for `Json`.
+    height: u16,
+}
+/// What a level did with a key.
+enum Step {
+    /// Handled it, waiting for the next key.
+    Wait,
+    /// Ended the level with the outcome.
+    Done(LevelOutcome),
+    /// Entered the level of a `SubMenu` or a `Bool`.
+    Descend(Box<Level<'static>>),
+    /// Waits for the prompt instead of a key.
+    Ask(Prompt),
+}
+/// Outcome a level ends with, handed to the level above it.
+type LevelOutcome = Result<Selection, Unwind>;
+/// Why a level ended without a selection.
+enum Unwind {
+    /// Going back from a `SubMenu` to the level above, which is displayed again.
+    Back,
+    /// The global hotkey of the `Action` at the index path was pressed, which the root `Menu` selects.
+    Global(Vec<usize>),
+    /// The `Menu` ends with the error.
+    Error(MenuError),
+}
+impl From<MenuError> for Unwind {
+    fn from(err: MenuError) -> Self {
+        Unwind::Error(err)
+    }
+}
+/// Value of an input, or what stopped it from being one.
+enum Parsed<T> {
+    Valid(T),
+    /// The input was `RunOptions::skip_sentinel`, or the `script` ended.
+    Skipped,
+    /// The input was rejected, and the prompt is printed again with the reason.
+    Invalid,
+}
+impl<T> Parsed<T> {
+    fn map<U>(self, map: impl FnOnce(T) -> U) -> Parsed<U> {
+        match self {
+            Parsed::Valid(value) => Parsed::Valid(map(value)),
+            Parsed::Skipped => Parsed::Skipped,
+            Parsed::Invalid => Parsed::Invalid,
         }
     }
-    fn match_keycode(&self, keycode: KeyCode) -> Option<String> {
-        match keycode {
-            KeyCode::Up => Some(String::from("Up")),
-            KeyCode::Down => Some(String::from("Down")),
-            KeyCode::Left => Some(String::from("Left")),
-            KeyCode::Right => Some(String::from("Right")),
-            KeyCode::Enter => Some(String::from("Enter")),
-            KeyCode::Esc => Some(String::from("Exit")),
-            KeyCode::Backspace => Some(String::from("Back")),
-            KeyCode::Char(chr) => Some(chr.to_string().to_lowercase()),
+}
+impl Level<'_> {
+    fn is_sub(&self) -> bool {
+        self.path.len() > 1
+    }
+    fn print(&mut self) {
+        self.menu.fix_hover(&self.opts, &mut self.state);
+        self.menu.print_header(&self.opts, &self.path, &self.state);
+        self.menu
+            .print_items(&self.opts, &self.path, &mut self.state);
+        self.menu
+            .print_bottom(&self.opts, self.is_sub(), &mut self.state);
+    }
+}
+impl<'a> MenuRun<'a> {
+    /// Starts the `Menu` at its last level, printing it, or first `RunOptions::splash` if `splash` is set.
+    fn start(levels: Vec<Level<'a>>, splash: bool) -> Self {
+        let mut run = MenuRun {
+            levels,
+            prompt: None,
+        };
+        let level = run.top();
+        let lines = match splash {
+            true => level.menu.splash(&level.opts),
+            false => None,
+        };
+        match lines {
+            Some(lines) => run.prompt = Some(Prompt::Splash(lines)),
+            None => level.print(),
+        }
+        run
+    }
+    fn top(&mut self) -> &mut Level<'a> {
+        self.levels.last_mut().expect("level")
+    }
+    /// Returns the input `Item` whose value the `Menu` waits a line for, if any.
+    fn input_item(&self) -> Option<&Item> {
+        match &self.prompt {
+            Some(Prompt::Input(input)) => self.levels.last().map(|f| &f.menu.items[input.index]),
             _ => None,
         }
     }
-    fn match_selection(
-        &self,
-        key: &Option<String>,
-        is_sub: bool,
-        stdout_ins: &mut Stdout,
-        path: &mut Vec<String>,
-        hover: &mut usize,
-    ) -> Result<Selection, String> {
-        if *key == None {
-            return Err("No Selection".to_string());
-        } else if is_sub && (*key == Some("Back".to_string()) || *key == Some("Left".to_string())) {
-            self.clear_menu(stdout_ins);
-            return Err("Back".to_string());
-        } else if *key == Some("Exit".to_string()) {
-            if self.esc {
-                self.clear_menu(stdout_ins);
-                stdout_ins.flush().unwrap();
-                return Err("Exit".to_string());
+    /// Drives the `Menu` by the keys and lines read as its `RunOptions` set, until it ends.
+    fn drive(&mut self) -> MenuOutcome {
+        loop {
+            let level = self.levels.last_mut().expect("level");
+            let (menu, opts) = (&level.menu, &level.opts);
+            if let Some(err) = opts.error() {
+                return Err(err);
             }
-        } else if *key == Some("Up".to_string()) {
-            if *hover > 0 {
-                *hover -= 1;
-            } else {
-                *hover = self.items.len() - 1;
+            let outcome = match &self.prompt {
+                Some(Prompt::Phrase(..)) => {
+                    let line = menu.read_line_string(opts);
+                    self.line(line)
+                }
+                Some(Prompt::Input(input)) => {
+                    let line = menu.read_input(opts, &menu.items[input.index]);
+                    self.line(line)
+                }
+                _ => {
+                    let event = menu.poll_read(opts, &mut level.state);
+                    self.key(event)
+                }
+            };
+            if let Some(outcome) = outcome {
+                return outcome;
             }
-            self.clear_menu(stdout_ins);
-            self.print_top(path);
-            self.print_items(hover);
-            if path.len() == 1 {
-                self.print_bottom(false, hover);
-            } else {
-                self.print_bottom(true, hover);
+        }
+    }
+    /// Handles the key, returning the outcome if the `Menu` ends. Keys are ignored while it waits for a line.
+    fn key(&mut self, event: KeyEvent) -> Option<MenuOutcome> {
+        let outcome = match self.prompt.take() {
+            None => {
+                let level = self.top();
+                level.state.jumping = false;
+                let key = level.menu.match_keycode(&level.opts, event.code);
+                self.step(event, key)
             }
-            return Err("No Selection".to_string());
-        } else if *key == Some("Down".to_string()) {
-            if (*hover + 1) < self.items.len() {
-                *hover += 1;
-            } else {
-                *hover = 0;
+            Some(Prompt::Splash(lines)) => {
+                let level = self.top();
+                let mut term = level.opts.output.term();
+                level.menu.clear_lines(&mut term, lines);
+                term.flush().expect("flush");
+                level.print();
+                None
             }
-            self.clear_menu(stdout_ins);
-            self.print_top(path);
-            self.print_items(hover);
-            if path.len() == 1 {
-                self.print_bottom(false, hover);
-            } else {
-                self.print_bottom(true, hover);
+            Some(Prompt::Confirm(_, outcome)) => {
+                let level = self.top();
+                let mut term = level.opts.output.term();
+                level.menu.clear_lines(&mut term, 1);
+                term.flush().expect("flush");
+                if !matches!(event.code, KeyCode::Char(chr) if same_letter(chr, level.opts.locale.yes))
+                {
+                    return None;
+                }
+                level
+                    .menu
+                    .clear_menu(&level.opts, &mut term, level.is_sub(), &level.state);
+                term.flush().expect("flush");
+                self.finish(outcome)
             }
-            return Err("No Selection".to_string());
-        }
-        for (i, item) in self.items.iter().enumerate() {
-            match item {
-                Item::Action { name, hotkey, .. } => {
-                    if (*key == hotkey.map(|f| f.to_string()))
-                        || (*key == Some(i.to_string()))
-                        || (*key == Some("Enter".to_string()) && i == *hover)
-                    {
-                        self.clear_menu(stdout_ins);
-                        stdout_ins.flush().unwrap();
-                        path.push(name.to_string());
-                        return Ok(Selection {
-                            name: name.to_string(),
-                            path: path.to_vec(),
-                            value: None,
-                            len: None,
-                            attempt: None,
-                        });
-                    } else {
-                        continue;
+            Some(Prompt::Value(mut input, value, len)) => {
+                let level = self.top();
+                let (menu, opts) = (&level.menu, &level.opts);
+                let confirmed = match answer(event.code, false, opts) {
+                    Some(confirmed) => confirmed,
+                    None => {
+                        self.prompt = Some(Prompt::Value(input, value, len));
+                        return None;
                     }
+                };
+                outln!(opts);
+                let mut term = opts.output.term();
+                if confirmed {
+                    menu.clear_lines(&mut term, 1);
+                    term.flush().expect("flush");
+                    return self.select(input, Some((value, len)));
                 }
-                Item::SubMenu {
-                    name,
-                    hotkey,
-                    exp,
-                    items,
-                } => {
-                    if (*key == hotkey.map(|f| f.to_string()))
-                        || (*key == Some(i.to_string()))
-                        || ((*key == Some("Enter".to_string())
-                            || *key == Some("Right".to_string()))
-                            && i == *hover)
+                // question, input and prompt.
+                menu.clear_lines(&mut term, 3);
+                term.flush().expect("flush");
+                menu.print_input_bottom(opts, &menu.items[input.index]);
+                input.attempt += 1;
+                self.prompt = Some(Prompt::Input(input));
+                None
+            }
+            prompt => {
+                self.prompt = prompt;
+                None
+            }
+        };
+        outcome.or_else(|| self.replay_jump())
+    }
+    /// Handles the line the `Menu` waits for, returning the outcome if it ends.
+    fn line(&mut self, line: String) -> Option<MenuOutcome> {
+        let outcome = match self.prompt.take() {
+            Some(Prompt::Phrase(phrase, selection)) => {
+                let level = self.top();
+                let mut term = level.opts.output.term();
+                // prompt and input.
+                level.menu.clear_lines(&mut term, 2);
+                term.flush().expect("flush");
+                if line == phrase {
+                    self.finish(Ok(selection))
+                } else {
+                    level.print();
+                    None
+                }
+            }
+            Some(Prompt::Input(input)) => self.enter(input, line),
+            prompt => {
+                self.prompt = prompt;
+                None
+            }
+        };
+        outcome.or_else(|| self.replay_jump())
+    }
+    /// Hands the key to the displayed level.
+    fn step(&mut self, event: KeyEvent, key: Option<String>) -> Option<MenuOutcome> {
+        let level = self.top();
+        let (menu, opts) = (&level.menu, &level.opts);
+        let mut term = opts.output.term();
+        let hover = level.state.hover;
+        let step = match key {
+            None => menu.unhandled_key(opts, event, &mut term, &level.path, &mut level.state),
+            _ => menu.match_selection(
+                opts,
+                &key,
+                level.is_sub(),
+                &mut term,
+                &level.path,
+                &mut level.state,
+            ),
+        };
+        menu.record_move(opts, hover, &key, &step, &mut level.state);
+        match step {
+            Step::Wait => None,
+            Step::Done(outcome) => self.finish(outcome),
+            Step::Descend(level) => {
+                self.levels.push(*level);
+                let level = self.top();
+                level.menu.transition(&level.opts);
+                level.print();
+                None
+            }
+            Step::Ask(prompt) => {
+                self.prompt = Some(prompt);
+                None
+            }
+        }
+    }
+    /// Handles the keys left to replay for a `RunOptions::path_jump`, returning the outcome if the `Menu` ends.
+    fn replay_jump(&mut self) -> Option<MenuOutcome> {
+        while self.prompt.is_none() {
+            let level = self.top();
+            let key = level.state.jump.borrow_mut().pop_front()?;
+            level.state.jumping = true;
+            if let Some(outcome) = self.step(KeyEvent::from(KeyCode::Null), Some(key)) {
+                return Some(outcome);
+            }
+        }
+        None
+    }
+    /// Ends the displayed level with the outcome, handing it to the level above as the outcome of its `Item` that entered it. Returns the outcome of the `Menu` once it ends.
+    fn finish(&mut self, mut outcome: LevelOutcome) -> Option<MenuOutcome> {
+        loop {
+            let entered: Vec<usize> = self.levels.iter().filter_map(|f| f.index).collect();
+            let level = self.top();
+            if !level.is_sub() {
+                outcome = level.menu.resolve_global(outcome);
+            }
+            if let Some(err) = level.opts.error() {
+                return Some(Err(err));
+            }
+            let confirmed = (level.state.checked.as_ref()).is_some_and(|f| f.borrow().confirmed);
+            if !confirmed && level.menu.stays(&level.opts, &mut outcome, &entered) {
+                level.state.entered |= outcome.as_ref().is_ok_and(|f| f.value.is_some());
+                level.print();
+                return None;
+            }
+            if self.levels.len() == 1 {
+                return match outcome {
+                    Ok(selection) => Some(Ok(selection)),
+                    Err(Unwind::Error(err)) => Some(Err(err)),
+                    // the root `Menu` has no level above to go back to, so it stays open.
+                    Err(_) => {
+                        self.top().print();
+                        None
+                    }
+                };
+            }
+            let child = self.levels.pop().expect("level");
+            let index = child.index.expect("entered level index");
+            outcome = match outcome {
+                Ok(mut ok) if child.choice => {
+                    let last = ok.path.pop().expect("item bool path pop");
+                    ok.indexes = vec![index];
+                    ok.value = Some(Value::Bool(last.parse().expect("item bool value parse")));
+                    Ok(ok)
+                }
+                Ok(mut ok) => {
+                    ok.indexes.insert(0, index);
+                    Ok(ok)
+                }
+                Err(Unwind::Back) => {
+                    let level = self.top();
+                    level.menu.transition(&level.opts);
+                    level.print();
+                    return None;
+                }
+                Err(Unwind::Error(MenuError::Exited)) => {
+                    Err(MenuError::Escaped(vec![index]).into())
+                }
+                Err(Unwind::Error(MenuError::Escaped(mut path))) => {
+                    path.insert(0, index);
+                    Err(MenuError::Escaped(path).into())
+                }
+                Err(err) => Err(err),
+            };
+        }
+    }
+    /// Takes the input for the prompt of an input `Item`, prompting again if it's invalid or, for `Item::Json`, until a blank line ends it.
+    fn enter(&mut self, mut input: InputPrompt, line: String) -> Option<MenuOutcome> {
+        let level = self.top();
+        let (menu, opts) = (&level.menu, &level.opts);
+        let item = &menu.items[input.index];
+        if let Item::Json { .. } = item {
+            input.height = json_height(opts, &line);
+        }
+        let line = match &opts.normalize {
+            Some(normalize) => normalize.normalize(item, line),
+            None => line,
+        };
+        // a lone sign is an unfinished number for signed `Item`s, prompted again instead of skipping.
+        let signed = matches!(item, Item::I32 { .. } | Item::F32 { .. } | Item::F64 { .. });
+        let skip = Some(opts.skip_sentinel.as_str())
+            .filter(|_| item.skippable())
+            .filter(|f| !(signed && matches!(*f, "-" | "+")));
+        let parsed = match item {
+            Item::Char { .. } => menu
+                .match_char(opts, item, line, skip)
+                .map(|value| (Value::Char(value), 1)),
+            Item::F32 { .. } => menu
+                .match_decimal(opts, item, line, skip)
+                .map(|value| (Value::F32(value as f32), (value as f32).to_string().len())),
+            Item::F64 { .. } => menu
+                .match_decimal(opts, item, line, skip)
+                .map(|value| (Value::F64(value), value.to_string().len())),
+            Item::I32 { .. } => menu
+                .match_input(opts, item, line, skip)
+                .map(|value: i32| (Value::I32(value), value.to_string().len())),
+            Item::U32 { .. } => menu
+                .match_input(opts, item, line, skip)
+                .map(|value: u32| (Value::U32(value), value.to_string().len())),
+            Item::NumberList { kind, .. } => {
+                menu.match_list(opts, item, line, skip, *kind)
+                    .map(|values| {
+                        let len = values.len();
+                        (Value::List(values), len)
+                    })
+            }
+            Item::ByteSize { .. } => menu
+                .match_input(opts, item, line, skip)
+                .map(|value: ByteSize| (Value::Bytes(value.0), value.0.to_string().len())),
+            Item::Color { .. } => menu
+                .match_input(opts, item, line, skip)
+                .map(|Rgb(r, g, b)| (Value::Color(r, g, b), 7)),
+            Item::Secret { .. } => menu.match_string(opts, item, line, skip).map(|value| {
+                let len = value.len();
+                (Value::Secret(Secret(value)), len)
+            }),
+            Item::Json { .. } => {
+                menu.match_json(opts, item, line, skip, input.height)
+                    .map(|value| {
+                        let len = value.len();
+                        (Value::String(value), len)
+                    })
+            }
+            _ => menu.match_string(opts, item, line, skip).map(|value| {
+                let len = value.len();
+                (Value::String(value), len)
+            }),
+        };
+        match parsed {
+            Parsed::Valid((value, len)) if item.confirms() => {
+                menu.ask_value(opts, &value);
+                self.prompt = Some(Prompt::Value(input, value, len));
+                None
+            }
+            Parsed::Valid(entry) => self.select(input, Some(entry)),
+            Parsed::Skipped => self.select(input, None),
+            Parsed::Invalid => {
+                input.attempt += 1;
+                self.prompt = Some(Prompt::Input(input));
+                None
+            }
+        }
+    }
+    /// Selects the input `Item` of the prompt with the value and the length of its text, or as skipped without one.
+    fn select(&mut self, input: InputPrompt, entry: Option<(Value, usize)>) -> Option<MenuOutcome> {
+        let level = self.top();
+        let name = level.menu.items[input.index].name().to_string();
+        let (value, len) = entry.map_or((None, None), |(value, len)| (Some(value), Some(len)));
+        let selection = Selection {
+            name: name.to_string(),
+            path: [level.path.as_slice(), &[name]].concat(),
+            indexes: vec![input.index],
+            skipped: value.is_none(),
+            value,
+            len,
+            attempt: Some(input.attempt),
+            item_path: vec![],
+        };
+        let mut term = level.opts.output.term();
+        // top line, name, prompt and input, however many attempts it took.
+        level.menu.clear_lines(&mut term, 3 + input.height);
+        term.flush().expect("flush");
+        self.finish(Ok(selection))
+    }
+    /// Prints what the `Menu` displays now with `opts`, as a `MenuSession` renders it, with the `lines` entered and the `edit` typed at a prompt.
+    fn render(&mut self, opts: &RunOptions, edit: &LineEdit, lines: &[String]) {
+        let level = self.levels.last_mut().expect("level");
+        let menu = &level.menu;
+        let opts = &RunOptions {
+            output: opts.output.clone(),
+            ..level.opts.clone()
+        };
+        let typed = |mask: bool| match mask {
+            true => "*".repeat(edit.chars.len()),
+            false => edit.chars.iter().collect::<String>(),
+        };
+        match &self.prompt {
+            Some(Prompt::Splash(_)) => {
+                menu.splash(opts);
+            }
+            Some(Prompt::Phrase(phrase, _)) => {
+                menu.print_phrase_prompt(opts, phrase);
+                outln!(opts, "{}{}", " ".repeat(opts.margin()), typed(false));
+            }
+            Some(Prompt::Input(input)) => {
+                let item = &menu.items[input.index];
+                menu.print_input_prompt(opts, &level.path, &level.state, input.index);
+                let mask = matches!(item, Item::Secret { .. });
+                for line in lines {
+                    outln!(opts, "{}{}", " ".repeat(opts.margin()), line);
+                }
+                outln!(opts, "{}{}", " ".repeat(opts.margin()), typed(mask));
+            }
+            Some(Prompt::Value(input, value, _)) => {
+                menu.print_input_prompt(opts, &level.path, &level.state, input.index);
+                outln!(
+                    opts,
+                    "{}{}",
+                    " ".repeat(opts.margin()),
+                    value.to_plain_string()
+                );
+                menu.ask_value(opts, value);
+                outln!(opts);
+            }
+            None | Some(Prompt::Confirm(..)) => {
+                let mut state = level.state.clone();
+                menu.fix_hover(opts, &mut state);
+                menu.print_header(opts, &level.path, &state);
+                menu.print_items(opts, &level.path, &mut state);
+                menu.print_bottom(opts, level.is_sub(), &mut state);
+                if let Some(Prompt::Confirm(question, _)) = &self.prompt {
+                    outln!(opts, "{}", question.as_str().with(opts.theme.error));
+                }
+            }
+        }
+    }
+}
+/// Callback of `RunOptions::confirm_exit`. Like `KeyHandler`, clones share the same function.
+#[derive(Clone)]
+pub struct ExitGuard(Rc<dyn Fn() -> bool>);
+impl ExitGuard {
+    /// Wraps the function telling whether exiting needs confirmation.
+    pub fn new(guard: impl Fn() -> bool + 'static) -> Self {
+        Self(Rc::new(guard))
+    }
+    /// Calls the function.
+    pub fn should_confirm(&self) -> bool {
+        (self.0)()
+    }
+}
+impl fmt::Debug for ExitGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "ExitGuard")
+    }
+}
+impl PartialEq for ExitGuard {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+/// Callback of `RunOptions::load_ui_state`. Like `KeyHandler`, clones share the same function.
+#[derive(Clone)]
+pub struct UiStateLoader(Rc<dyn Fn() -> UiState>);
+impl UiStateLoader {
+    /// Wraps the function giving the stored `UiState`.
+    pub fn new(loader: impl Fn() -> UiState + 'static) -> Self {
+        Self(Rc::new(loader))
+    }
+    /// Calls the function.
+    pub fn load(&self) -> UiState {
+        (self.0)()
+    }
+}
+impl fmt::Debug for UiStateLoader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "UiStateLoader")
+    }
+}
+impl PartialEq for UiStateLoader {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+/// Callback of `RunOptions::save_ui_state`. Like `KeyHandler`, clones share the same function.
+#[derive(Clone)]
+pub struct UiStateSaver(Rc<dyn Fn(&UiState)>);
+impl UiStateSaver {
+    /// Wraps the function storing the `UiState`.
+    pub fn new(saver: impl Fn(&UiState) + 'static) -> Self {
+        Self(Rc::new(saver))
+    }
+    /// Calls the function.
+    pub fn save(&self, ui_state: &UiState) {
+        (self.0)(ui_state)
+    }
+}
+impl fmt::Debug for UiStateSaver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "UiStateSaver")
+    }
+}
+impl PartialEq for UiStateSaver {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+/// Callback of `RunOptions::mask_exp`. Like `KeyHandler`, clones share the same function.
+#[derive(Clone)]
+pub struct ExpMask(Rc<dyn Fn(&Item) -> bool>);
+impl ExpMask {
+    /// Wraps the function telling whether the explanation of an `Item` is masked.
+    pub fn new(mask: impl Fn(&Item) -> bool + 'static) -> Self {
+        Self(Rc::new(mask))
+    }
+    /// Calls the function.
+    pub fn should_mask(&self, item: &Item) -> bool {
+        (self.0)(item)
+    }
+}
+impl fmt::Debug for ExpMask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "ExpMask")
+    }
+}
+impl PartialEq for ExpMask {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+/// Function of a `Normalizer`.
+type NormalizeFn = dyn Fn(&Item, String) -> String;
+/// Callback of `RunOptions::normalize`. Like `KeyHandler`, clones share the same function, so cloning `RunOptions` doesn't clone what the function captures.
+#[derive(Clone)]
+pub struct Normalizer(Rc<NormalizeFn>);
+impl Normalizer {
+    /// Wraps the function turning the input of an `Item` into the text that's parsed.
+    pub fn new(normalize: impl Fn(&Item, String) -> String + 'static) -> Self {
+        Self(Rc::new(normalize))
+    }
+    /// Calls the function.
+    pub fn normalize(&self, item: &Item, input: String) -> String {
+        (self.0)(item, input)
+    }
+}
+impl fmt::Debug for Normalizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "Normalizer")
+    }
+}
+impl PartialEq for Normalizer {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+/// Callback of `RunOptions::stay`. Like `KeyHandler`, clones share the same function.
+#[derive(Clone)]
+pub struct StayHandler(Rc<dyn Fn(&Selection) -> bool>);
+impl StayHandler {
+    /// Wraps the function telling whether the `Menu` stays open after the selection.
+    pub fn new(handler: impl Fn(&Selection) -> bool + 'static) -> Self {
+        Self(Rc::new(handler))
+    }
+    /// Calls the function.
+    pub fn should_stay(&self, selection: &Selection) -> bool {
+        (self.0)(selection)
+    }
+}
+impl fmt::Debug for StayHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "StayHandler")
+    }
+}
+impl PartialEq for StayHandler {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+/// What `Menu` does after a `KeyHandler` handles a key.
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeyFlow {
+    /// Exit `Menu`, returning `MenuError::Run` with the text.
+    Exit(String),
+    /// The key is handled. `Menu` is redrawn, in case the handler printed something.
+    Consume,
+    /// Nothing happens, as if the key wasn't pressed.
+    Ignore,
+}
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{:?}", self)
+    }
+}
+/// Starting point for creating a menu instance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Menu {
+    /// `Menu` name is displayed at the top.
+    pub name: String,
+    /// Optional explanation in gray color next to the menu name.
+    pub exp: Option<String>,
+    /// `Menu` items should be vector of `Item`s.
+    pub items: Vec<Item>,
+    /// Enable exiting menu by `Esc` hotkey. Usually set it to `true`. But it may be useful to set to `false` when you want to restrict the user from escaping without any selection.
+    pub esc: bool,
+}
+/// Iterates the top-level `Item`s, like `for item in &menu`. `Menu::walk()` visits `Item`s of `SubMenu`s too.
+impl<'a> IntoIterator for &'a Menu {
+    type Item = &'a Item;
+    type IntoIter = std::slice::Iter<'a, Item>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+/// Presentation and behavior options of a `Menu` run, given to `Menu::run_with()`. `RunOptions::default()` gives the options `Menu::run()` uses, so options can be set like `RunOptions { border: true, ..RunOptions::default() }`. Options are passed to `SubMenu`s.
+/// ```
+/// use rushterm::{Menu, RunOptions};
+///
+/// let menu = Menu {
+///     name: "Main".to_string(),
+///     exp: None,
+///     items: Vec::new(),
+///     esc: true,
+/// };
+/// let opts = RunOptions {
+///     border: true,
+///     ..RunOptions::default()
+/// };
+/// assert!(menu.render_to_string(&opts, 40).contains("Main"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunOptions {
+    /// Arrangement of `Item`s on the screen. `Layout::List` is the classic one item per line look.
+    pub layout: Layout,
+    /// Fill `Selection::item_path` with clones of the `Item`s along the selection, for dispatching on more than names. Each clone copies a whole `SubMenu` with all `Item`s under it, so it's costly for large trees, and `LazySubMenu`s are built again. `false` by default.
+    pub item_path: bool,
+    /// Render within this many columns even if the terminal is wider, so lines don't stretch across ultra-wide screens. `None` uses the whole width.
+    pub max_width: Option<usize>,
+    /// Where the `max_width` columns are placed on a wider terminal.
+    pub align: Align,
+    /// Columns assumed when the width of the terminal can't be determined, like when stdout isn't a terminal. Rendering to `Output::Writer` always assumes it, since the stream isn't the terminal whose size would be detected. `80` by default.
+    pub fallback_width: usize,
+    /// Terminal assumed instead of the real one, its size and `capabilities` alike, so the output doesn't depend on the machine, like for golden-file tests of layouts. `None` by default.
+    pub assumed_terminal: Option<AssumedTerminal>,
+    /// Replace middle segments of the path in the top line with this glyph, like `Main/…/Wireless/`, when the whole path doesn't fit in the terminal width. The first and last segments are always kept. `None`, the default, always shows the whole path.
+    pub path_ellipsis: Option<String>,
+    /// Cut the top line to the terminal width with an ellipsis instead of letting the terminal wrap it, so a long `Menu` name, path or explanation keeps the header on one line. The explanation is cut first, since it ends the line. Segments are elided by `path_ellipsis` before cutting, if it's set. `false` by default.
+    pub truncate_title: bool,
+    /// Show the position of the hovered `Item` among the displayed ones under the `Menu`, like `3/12`, to orient users in long menus. `false` by default.
+    pub position: bool,
+    /// Preview the names of the first this many `Item`s of the hovered `SubMenu` under the `Menu`, like `Wi-Fi, Ethernet, …`, so users see what it contains before entering it. `LazySubMenu`s aren't previewed, since their `Item`s are built on entry. `None` by default.
+    pub sub_preview: Option<usize>,
+    /// Restrict `Right` hotkey to descending into `SubMenu`s only, like file managers do. `Enter` still selects any `Item`. When `false`, `Right` also opens `Bool` inputs.
+    pub right_descends_only: bool,
+    /// Optional top-level `Item` selected by a dedicated key from anywhere in the `Menu`, whichever `Item` is hovered. Suits wizards with an obvious "Next" step. It isn't passed to `SubMenu`s. `None` by default.
+    pub default_action: Option<DefaultAction>,
+    /// `Enter`, the hotkey or the index number of a `SubMenu` selects it, returning a `Selection` named after it with no value, instead of descending into it. `Right` still descends. Suits "pick a category" flows. `false` by default.
+    pub submenu_selectable: bool,
+    /// Least number of `Action`s to check in `Menu::run_checklist()`. Selecting a checkable `Action` with fewer checked ones is blocked with `Locale::select_at_least`, even with none checked. The count and the limits are displayed next to the checking hint.
+    pub min_select: Option<usize>,
+    /// Most number of `Action`s to check in `Menu::run_checklist()`. Checking more is blocked with `Locale::select_at_most`.
+    pub max_select: Option<usize>,
+    /// Texts of the `Menu` interface. Set it to translate the interface into another language.
+    pub locale: Locale,
+    /// Display the number of `Item`s next to `SubMenu`s, like `+Settings (3)`. `LazySubMenu`s display `(…)` since their `Item`s are built on entry.
+    pub sub_counts: bool,
+    /// Enable searching `Item`s by typing `/` followed by a text. Only `Item`s whose names contain the text are displayed. `Backspace` erases the text before going back.
+    pub search: bool,
+    /// Underline the parts of `Item` names matching the search text, so it's clear why an `Item` is displayed. Enabled by default.
+    pub highlight_search: bool,
+    /// How the search text matches `Item` names. `SearchMode::Substring` by default.
+    pub search_mode: SearchMode,
+    /// Select the only `Item` left as soon as typing the search text narrows the `Item`s down to it, like a command palette. Typing further can't match any other `Item`, so nothing is selected while the text may still lead elsewhere. Erasing the text never selects. Otherwise the only `Item` is hovered, waiting for `Enter`. `false` by default.
+    pub auto_accept_single: bool,
+    /// Jump through `SubMenu`s by typing a dotted path of index numbers, like `2.0.1`, then `Enter`. Index numbers are typed instead of selecting at once, so numbers past 9 can be typed too, and `Backspace` erases and `Esc` drops the typed path. The path is checked before anything happens; the `Item` it leads to is selected as if its index number was pressed, with each `SubMenu` on the way entered, so going back from it returns to them. A path leading nowhere is displayed as a notice. `LazySubMenu`s on the path build their `Item`s to check it. Not available with `hide_indexes`. `false` by default.
+    pub path_jump: bool,
+    /// Hide index numbers before `Item`s. Index numbers can't select `Item`s then. Useful for menus relying on hotkeys only.
+    pub hide_indexes: bool,
+    /// Number only `Item`s that can be selected, skipping `Group` headers, so index numbers run without gaps and pressing one selects the `Item` displaying it. `Group`s are still folded by their hotkeys and by `Enter` or `Space`.
+    pub number_actionable: bool,
+    /// Maximum number of `Item` rows displayed at once in `Layout::List` and `Layout::Stacked`. `Item`s scroll as the hover moves, so long menus fit small terminals. All `Item`s are displayed if `None`, the default.
+    pub max_rows: Option<usize>,
+    /// Number of rows kept between the hover and the top or bottom edge while scrolling by `max_rows`, like `scrolloff` of vim, so the `Item`s around the hover stay in sight. Limited to half of `max_rows`. `0` by default.
+    pub scroll_margin: usize,
+    /// Draw a box around the `Menu` with the title in its top border.
+    pub border: bool,
+    /// Display the explanation of the `Menu` as a gray paragraph wrapped under the top line, instead of next to the name. Suits long explanations. `false` by default.
+    pub exp_below: bool,
+    /// Moving up from the first `Item` hovers the last one and vice versa. Enabled by default.
+    pub wrap_navigation: bool,
+    /// `Tab` moves the hover down and `Shift+Tab` moves it up, like moving between the fields of a form. They follow `wrap_navigation` as `Down` and `Up` do. Keys of `default_action` and `exp_toggle_key` take precedence. `false` by default, leaving `Tab` to `on_unhandled_key`.
+    pub tab_navigation: bool,
+    /// The `Menu` opens with no `Item` hovered, so that nothing is picked by pressing `Enter` right away. `Down` then hovers the first `Item` and `Up` the last one, and `Enter` does nothing until either is pressed. Hotkeys select as usual. Only the root `Menu` opens so; `SubMenu`s hover their first `Item`. `false` by default.
+    pub start_unfocused: bool,
+    /// Ignore a key repeating the previous one within the duration, so holding a key moves steadily instead of overshooting on terminals emitting rapid repeats. Different keys and the keys of `script` are never ignored. `None` by default.
+    pub debounce: Option<Duration>,
+    /// Pause on the cleared frame for the duration when entering and leaving `SubMenu`s, so the change of level is noticeable. It's cut to 250 milliseconds, so keys are never held back longer; keys pressed meanwhile are handled after it. `None` by default.
+    pub animation: Option<Duration>,
+    /// Called with keys `Menu` doesn't use, such as `F5` or `Tab`. Turns `Menu` into an extensible event loop. `None` by default.
+    pub on_unhandled_key: Option<KeyHandler>,
+    /// Number of lines of a detail region under `Item`s, displaying the whole explanation of the hovered `Item` as the hover moves. Longer explanations are cut. `None` by default.
+    pub detail_height: Option<usize>,
+    /// Input typed to skip a `skippable` input `Item`, telling the field is deliberately not applicable. `"-"` by default. A lone `-` or `+` is taken as an unfinished number by the signed `I32`, `F32` and `F64` instead, prompting again, so set another sentinel to skip those.
+    pub skip_sentinel: String,
+    /// Accept `inf`, `-inf` and `NaN` for `F32` and `F64` `Item`s. They parse as floats but are rarely meant, so they are reprompted with `Locale::finite_number` by default.
+    pub allow_non_finite: bool,
+    /// Turn `-0.0` typed for `F32` and `F64` `Item`s into `0.0`, so the sign doesn't show up in the value. `false` by default.
+    pub positive_zero: bool,
+    /// Whitespace removed from the ends of the input of `String` and `Secret` `Item`s, where leading or trailing spaces may be meaningful, like in passwords. `TrimPolicy::Both` by default.
+    pub trim: TrimPolicy,
+    /// How the type of value is displayed in the prompt of input `Item`s. `TypeHint::Raw` by default.
+    pub type_hint: TypeHint,
+    /// Called with each input read for an input `Item` and the `Item`, giving the text that's parsed and returned instead, for cleanups like uppercasing a code or stripping dashes from a card number. It runs after `trim`, and before the input is compared to `skip_sentinel`. Match on the `Item` to normalize only some of them. `None` by default.
+    pub normalize: Option<Normalizer>,
+    /// Text like `"> "` displayed before the hovered `Item`, and replaced by spaces before others. Keeps the hover visible without colors. `None` by default.
+    pub hover_marker: Option<String>,
+    /// Key hiding and showing the explanations of `Item`s, such as `KeyCode::F(1)`, for a compact view on narrow terminals. They stay hidden or shown in `SubMenu`s. The key is displayed in the bottom line. `None` by default.
+    pub exp_toggle_key: Option<KeyCode>,
+    /// Tells which `Item`s have explanations holding sensitive data, like a token hint, displayed with their middle characters replaced by `*` for screen sharing. `reveal_key` shows them as they are. `None` by default.
+    pub mask_exp: Option<ExpMask>,
+    /// Key revealing and masking again the explanations `mask_exp` masks, such as `KeyCode::F(2)`. They stay revealed or masked in `SubMenu`s. The key is displayed in the bottom line. `None` by default.
+    pub reveal_key: Option<KeyCode>,
+    /// Key undoing the last navigation, such as `KeyCode::F(3)`: it moves the hover back where it was, up to 32 moves, and once there are none left in a `SubMenu`, goes back out of it like `Backspace`, to the hover it was entered from. Unlike `Backspace`, pressing it repeatedly retraces moves within each level. The key is displayed in the bottom line. `None` by default.
+    pub undo_key: Option<KeyCode>,
+    /// What pressing a hotkey assigned to several displayed `Item`s does. `HotkeyPolicy::FirstMatch` by default.
+    pub hotkey_policy: HotkeyPolicy,
+    /// Whether hotkeys are displayed before the names of `Item`s, like `0.(S)  Save`, or after them in a column aligned past the longest name, like `0.  Save (S)`. `HotkeyPosition::Before` by default.
+    pub hotkey_position: HotkeyPosition,
+    /// Give each `Item` without a hotkey the first letter of its name not taken by another hotkey of its level, like the mnemonics of desktop menus. The letter is underlined in the name. An `Item` whose letters are all taken gets no hotkey. `false` by default.
+    pub auto_hotkeys: bool,
+    /// Called when `Esc` is pressed. If it returns `true`, like when there is unsaved work, `Menu` asks for confirmation and exits only if the yes key of `Locale`, `Y` by default, is pressed. If it returns `false`, `Menu` exits right away, so users aren't nagged when nothing needs saving. `None` by default.
+    pub confirm_exit: Option<ExitGuard>,
+    /// Gives the `UiState` stored by the last run, so `Group`s start collapsed or expanded as the user left them. It's called once per run, and `Group`s it doesn't record start as set on them. `None` by default.
+    pub load_ui_state: Option<UiStateLoader>,
+    /// Called with the `UiState` each time a `Group` is collapsed or expanded, to store it for `load_ui_state`. The state starts as `load_ui_state` gives it, so `Group`s stored by earlier runs are kept, or else empty. `None` by default.
+    pub save_ui_state: Option<UiStateSaver>,
+    /// Called with each `Selection` before it's returned. If it returns `true`, the `Menu` stays open and is redrawn instead, keeping hover, as if nothing was selected. Suits toggle-like `Action`s acting on the selection right away, like flipping a setting. In a `SubMenu`, the `Selection` holds the whole index path from the `Menu`, as returned. `Counter`s are adjusted without selecting anyway. `None` by default.
+    pub stay: Option<StayHandler>,
+    /// Make `Menu::run_loop()` run again in the `SubMenu` of the last selection, hovering the selected `Item`, instead of the top level, so related `Action`s are a key away. Going back works as if the user had descended. If `on_selection` removed the `SubMenu`, the top level runs. `false` by default.
+    pub stay_in_submenu: bool,
+    /// Skips the callbacks run on a selection, `stay` and the `on_selection` of `run_loop()`, while the `Selection` is still returned, to try a menu without side effects. With `DryRun::Report` the path of each skipped selection is printed after `Locale::would_run`. `confirm_phrase` and the `confirm` of input `Item`s are still asked, as they come before the selection.
+    pub dry_run: DryRun,
+    /// Ask to confirm going back from a `SubMenu` in which a value was entered and kept by `RunOptions::stay`, with `Locale::discard_entries`, so a stray `Backspace` in a wizard-style form doesn't lose the entries. Going back without entered values doesn't ask. `false` by default.
+    pub confirm_discard: bool,
+    /// Text shown once before the top level of the `Menu`, like a welcome screen, followed by `Locale::press_any_key`. Any key clears it and displays the `Menu`. It may span several lines and carry styles. `Menu::run_loop()` shows it only before the first run. `None` by default.
+    pub splash: Option<String>,
+    /// Colors of the interface. `Theme::dark()` by default. Set `Theme::auto()` to follow the background of the terminal.
+    pub theme: Theme,
+    /// What the terminal can display. `Capabilities::detect()` by default, so a dumb terminal gets plain ASCII without colors. Set it to override the detection.
+    pub capabilities: Capabilities,
+    /// Stream the `Menu` is rendered to. `Output::Stdout` by default.
+    pub output: Output,
+    /// Stream keys and lines are read from. `Input::Terminal` by default. With `Input::Reader` and `Output::Writer`, the `Menu` runs on another terminal than the process's own, like the pseudo terminal of an SSH session.
+    pub input: Input,
+    /// Actions read instead of the keys and lines typed in the terminal. See `Menu::run_with_stdin_values()`. The run returns `MenuError::ScriptEnded` if the `Menu` waits for an action after the last one. `None` by default.
+    pub script: Option<Script>,
+}
+impl RunOptions {
+    /// Columns left empty before each line to center the `max_width` columns, if `align` is `Align::Center`.
+    fn margin(&self) -> usize {
+        match (self.align, self.max_width) {
+            (Align::Center, Some(max_width)) => terminal_width(self).saturating_sub(max_width) / 2,
+            _ => 0,
+        }
+    }
+    /// Reads a key from the stream chosen by `input`, or `None` for other terminal events, like a resize.
+    fn read_key(&self) -> Option<KeyEvent> {
+        match &self.input {
+            Input::Terminal => match read() {
+                Ok(Event::Key(event)) => Some(event),
+                Ok(_) => None,
+                // gives `Esc` like a `Reader` that ended, and the run returns the error.
+                Err(err) => {
+                    TERMINAL_ERROR.with(|f| f.set(Some(err.kind())));
+                    Some(KeyEvent::from(KeyCode::Esc))
+                }
+            },
+            Input::Reader(reader) => Some(reader.key()),
+        }
+    }
+    /// Whether the `script` ended while the `Menu` waited for an action, or the `Reader` of `input` ended or failed, so every read gives up.
+    fn input_ended(&self) -> bool {
+        self.script.as_ref().is_some_and(Script::ended) || self.input.error().is_some()
+    }
+    /// Returns the error the run has to stop with: the failure of a write, the end of the `script`, or the end or failure of the `Reader` of `input`.
+    fn error(&self) -> Option<MenuError> {
+        match (self.output.error(), self.input.error()) {
+            (Some(kind), _) => Some(MenuError::Io(kind)),
+            (None, Some(kind)) => Some(MenuError::InputEnded(kind)),
+            (None, None) => match self.script.as_ref().and_then(Script::invalid_line) {
+                Some(line) => Some(MenuError::InvalidScriptLine(line)),
+                None if self.input_ended() => Some(MenuError::ScriptEnded),
+                None => None,
+            },
+        }
+    }
+    /// Copies the options for a run of its own, whose writes fail apart from any other run's.
+    fn for_run(&self) -> RunOptions {
+        RunOptions {
+            output: Output::Writer(self.output.writer()),
+            ..self.clone()
+        }
+    }
+    /// Starts the line at the margin, if there is one.
+    fn start_line(&self) {
+        let margin = self.margin();
+        if margin > 0 {
+            out!(self, "\r{}", " ".repeat(margin));
+        }
+    }
+    /// Gives the `capabilities`, or those of the `assumed_terminal` if it is set.
+    fn capabilities(&self) -> Capabilities {
+        self.assumed_terminal
+            .map_or(self.capabilities, |f| f.capabilities)
+    }
+    /// Gives the `max_rows`, or the height of the `assumed_terminal` if only it is set.
+    fn max_rows(&self) -> Option<usize> {
+        self.max_rows
+            .or(self.assumed_terminal.map(|f| f.height as usize))
+    }
+    /// Writes to the stream chosen by `output`, dropping the colors and text attributes if the terminal lacks `Capabilities::color`.
+    fn write_out(&self, args: fmt::Arguments) {
+        let mut term = self.output.term();
+        if self.capabilities().color {
+            term.write_fmt(args).expect("write")
+        } else {
+            term.write_all(strip_styles(&args.to_string()).as_bytes())
+                .expect("write")
+        }
+    }
+    /// Gives the `unicode` glyph, or its `ascii` replacement if the terminal lacks `Capabilities::unicode`.
+    fn glyph(&self, unicode: &'static str, ascii: &'static str) -> &'static str {
+        if self.capabilities().unicode {
+            unicode
+        } else {
+            ascii
+        }
+    }
+}
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            layout: Layout::List,
+            item_path: false,
+            max_width: None,
+            align: Align::Left,
+            fallback_width: 80,
+            assumed_terminal: None,
+            path_ellipsis: None,
+            truncate_title: false,
+            position: false,
+            sub_preview: None,
+            right_descends_only: false,
+            default_action: None,
+            submenu_selectable: false,
+            min_select: None,
+            max_select: None,
+            locale: Locale::default(),
+            sub_counts: false,
+            search: false,
+            highlight_search: true,
+            search_mode: SearchMode::Substring,
+            auto_accept_single: false,
+            path_jump: false,
+            hide_indexes: false,
+            number_actionable: false,
+            max_rows: None,
+            scroll_margin: 0,
+            border: false,
+            exp_below: false,
+            wrap_navigation: true,
+            tab_navigation: false,
+            start_unfocused: false,
+            debounce: None,
+            animation: None,
+            on_unhandled_key: None,
+            detail_height: None,
+            skip_sentinel: "-".to_string(),
+            allow_non_finite: false,
+            positive_zero: false,
+            trim: TrimPolicy::Both,
+            type_hint: TypeHint::Raw,
+            normalize: None,
+            hover_marker: None,
+            exp_toggle_key: None,
+            mask_exp: None,
+            reveal_key: None,
+            undo_key: None,
+            hotkey_policy: HotkeyPolicy::FirstMatch,
+            hotkey_position: HotkeyPosition::Before,
+            auto_hotkeys: false,
+            confirm_exit: None,
+            load_ui_state: None,
+            save_ui_state: None,
+            stay: None,
+            stay_in_submenu: false,
+            dry_run: DryRun::Off,
+            confirm_discard: false,
+            splash: None,
+            theme: Theme::dark(),
+            capabilities: Capabilities::detect(),
+            output: Output::Stdout,
+            input: Input::Terminal,
+            script: None,
+        }
+    }
+}
+/// Placement of hotkeys relative to the names of `Item`s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum HotkeyPosition {
+    #[default]
+    Before,
+    After,
+}
+/// What pressing a hotkey assigned to several displayed `Item`s does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum HotkeyPolicy {
+    /// The first of the `Item`s is selected.
+    #[default]
+    FirstMatch,
+    /// `Menu` exits, returning `MenuError::AmbiguousHotkey` with the hotkey. Makes the conflict loud while developing.
+    Error,
+    /// Each press moves the hover to the next of the `Item`s without selecting, so `Item`s grouped under one mnemonic are reached by pressing it repeatedly and `Enter`.
+    Cycle,
+}
+/// Whitespace removed from the ends of the input of `String` and `Secret` `Item`s. Other inputs are always trimmed before they are parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TrimPolicy {
+    /// Whitespace is removed from both ends.
+    #[default]
+    Both,
+    /// Whitespace is removed from the end only, keeping indentation.
+    End,
+    /// The input is kept as typed, without the line break ending it.
+    None,
+}
+impl TrimPolicy {
+    fn apply(self, text: &str) -> &str {
+        match self {
+            TrimPolicy::Both => text.trim(),
+            TrimPolicy::End => text.trim_end(),
+            TrimPolicy::None => text.trim_end_matches(['\n', '\r']),
+        }
+    }
+}
+/// Shell of `Menu::completion_script()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+}
+/// Matching of the search text against `Item` names, ignoring case.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SearchMode {
+    /// Names containing the text are displayed in their order.
+    #[default]
+    Substring,
+    /// Names containing the chars of the text in order, like `rt` in `Run Tests`, are displayed best match first, like fzf. A name scores 16 for each matched char, 8 more for each matched char at the start of a word or right after the previous matched char, and 1 less for each char skipped before and between them. The best scoring chars are taken for each name, and names of equal scores keep their order. Typing moves the hover to the best match.
+    Fuzzy,
+}
+/// Display of the type of value in the prompt of input `Item`s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TypeHint {
+    /// The variant name of the `Item`, like `I32`, as `Item::type_name()` gives it.
+    #[default]
+    Raw,
+    /// A label for people unfamiliar with Rust types, like `whole number`, from `Locale::type_labels`. `Item`s without a label fall back to the variant name.
+    Friendly,
+    /// No type, just `Locale::enter_value_untyped`.
+    Hidden,
+}
+/// Whether `RunOptions::dry_run` skips the callbacks run on a selection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DryRun {
+    /// Callbacks run.
+    #[default]
+    Off,
+    /// Callbacks are skipped.
+    Silent,
+    /// Callbacks are skipped, and the path of the selection is printed.
+    Report,
+}
+/// An `Item` of `Menu` selected by pressing `key`, regardless of hover.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DefaultAction {
+    /// Index of the `Item` in `Menu`.
+    pub index: usize,
+    /// Key to select the `Item`, such as `KeyCode::Tab` or `KeyCode::F(2)`. The hotkey is displayed in the bottom line.
+    pub key: KeyCode,
+}
+/// Built-in texts displayed by `Menu`. `Locale::default()` gives the English ones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Locale {
+    /// Navigation hint in the bottom line.
+    pub movement: String,
+    /// Selection hint in the bottom line.
+    pub select: String,
+    /// Going back hint in the bottom line of sub menus.
+    pub back: String,
+    /// Exit hint in the bottom line.
+    pub exit: String,
+    /// Hint displayed under the bottom line.
+    pub hint: String,
+    /// Hint displayed under the bottom line when index numbers are hidden.
+    pub hotkey_hint: String,
+    /// Prompt for input `Item`s, followed by the type of value.
+    pub enter_value: String,
+    /// Prompt for input `Item`s with `TypeHint::Hidden`.
+    pub enter_value_untyped: String,
+    /// Labels of the types of value by `Item::type_name()`, displayed with `TypeHint::Friendly`.
+    pub type_labels: HashMap<String, String>,
+    /// Displayed before the prompt when input can't be parsed.
+    pub invalid_entry: String,
+    /// Shown after the type of signed number inputs, telling a sign is accepted.
+    pub signed_hint: String,
+    /// Displayed before the search text, replacing the hint.
+    pub search: String,
+    /// Displayed before the prompt when input has more decimal places than allowed.
+    pub too_many_decimals: String,
+    /// Displayed before the prompt when a float input is infinite or not a number, unless `RunOptions::allow_non_finite` accepts it.
+    pub finite_number: String,
+    /// Checking hint in the bottom line of `Menu::run_checklist()`.
+    pub check: String,
+    /// Displayed before the invalid element and the prompt when a list input can't be parsed.
+    pub invalid_element: String,
+    /// Displayed before the prompt when a required input is empty.
+    pub required: String,
+    /// Explanations toggling hint in the bottom line.
+    pub explanations: String,
+    /// Revealing hint in the bottom line, for `RunOptions::reveal_key`.
+    pub reveal: String,
+    /// Undoing hint in the bottom line, for `RunOptions::undo_key`.
+    pub undo: String,
+    /// Question displayed under the `Menu` when `RunOptions::confirm_exit` asks for confirmation, followed by the `yes` and `no` keys, like `(y/n)`.
+    pub confirm_exit: String,
+    /// Key answering yes to `confirm()` and `RunOptions::confirm_exit`, in either case.
+    pub yes: char,
+    /// Key answering no to `confirm()`, in either case.
+    pub no: char,
+    /// Adjusting hint in the bottom line while a `Counter` is hovered.
+    pub adjust: String,
+    /// Collapsing and expanding hint in the bottom line while a `Group` is hovered.
+    pub fold: String,
+    /// Prompt followed by the phrase to type before an `Action` with a `confirm_phrase` is selected.
+    pub type_to_confirm: String,
+    /// Displayed before `RunOptions::min_select` when fewer `Action`s are checked.
+    pub select_at_least: String,
+    /// Displayed before `RunOptions::max_select` when checking more `Action`s.
+    pub select_at_most: String,
+    /// Displayed before the parsed value of an input `Item` with `confirm`.
+    pub you_entered: String,
+    /// Displayed after the parsed value of an input `Item` with `confirm`, before the yes and no keys.
+    pub confirm_entry: String,
+    /// Question displayed under a `SubMenu` when `RunOptions::confirm_discard` asks for confirmation, followed by the `yes` and `no` keys like `confirm_exit`.
+    pub discard_entries: String,
+    /// Displayed under `RunOptions::splash` until a key is pressed.
+    pub press_any_key: String,
+    /// Displayed before the path typed for `RunOptions::path_jump`.
+    pub go_to: String,
+    /// Displayed before a path typed for `RunOptions::path_jump` leading to no `Item`.
+    pub no_item_at: String,
+    /// Displayed before the path of a selection skipped by `DryRun::Report`.
+    pub would_run: String,
+}
+impl Default for Locale {
+    fn default() -> Self {
+        Self {
+            movement: "Move".to_string(),
+            select: "Select".to_string(),
+            back: "Back".to_string(),
+            exit: "Exit".to_string(),
+            hint: "Press an index number or a hotkey to select:".to_string(),
+            hotkey_hint: "Press a hotkey to select:".to_string(),
+            enter_value: "Enter a value. Type: ".to_string(),
+            enter_value_untyped: "Enter a value.".to_string(),
+            type_labels: [
+                ("Bool", "yes/no"),
+                ("Char", "single character"),
+                ("String", "text"),
+                ("Secret", "hidden text"),
+                ("Json", "JSON"),
+                ("F32", "decimal"),
+                ("F64", "decimal"),
+                ("I32", "whole number"),
+                ("U32", "whole number, 0 or more"),
+                ("NumberList", "list of numbers"),
+                ("ByteSize", "size, like 4KB"),
+                ("Color", "color, like #FF8800 or orange"),
+            ]
+            .into_iter()
+            .map(|(kind, label)| (kind.to_string(), label.to_string()))
+            .collect(),
+            invalid_entry: "Invalid entry: ".to_string(),
+            signed_hint: " (e.g. -5)".to_string(),
+            search: "Search: ".to_string(),
+            too_many_decimals: "Too many decimal places: ".to_string(),
+            finite_number: "Enter a finite number: ".to_string(),
+            check: "Check".to_string(),
+            invalid_element: "Invalid element: ".to_string(),
+            required: "This field is required: ".to_string(),
+            explanations: "Explanations".to_string(),
+            reveal: "Reveal".to_string(),
+            undo: "Undo".to_string(),
+            confirm_exit: "Exit anyway?".to_string(),
+            yes: 'y',
+            no: 'n',
+            adjust: "Adjust".to_string(),
+            fold: "Fold".to_string(),
+            type_to_confirm: "Type to confirm: ".to_string(),
+            select_at_least: "Select at least ".to_string(),
+            select_at_most: "Select at most ".to_string(),
+            you_entered: "You entered ".to_string(),
+            confirm_entry: " — confirm?".to_string(),
+            discard_entries: "Discard entries?".to_string(),
+            press_any_key: "Press any key to continue".to_string(),
+            go_to: "Go to: ".to_string(),
+            no_item_at: "No item at ".to_string(),
+            would_run: "Would run: ".to_string(),
+        }
+    }
+}
+impl Locale {
+    /// Appends the hint of the `yes` and `no` keys to the question.
+    fn yes_no(&self, question: &str) -> String {
+        format!("{} ({}/{})", question, self.yes, self.no)
+    }
+}
+/// Colors of the `Menu` interface. Colors readable on a dark background may be unreadable on a light one, like yellow hotkeys, so there is a `Theme` for each.
+/// ```
+/// use rushterm::{Background, Theme};
+///
+/// assert_eq!(Background::from_colorfgbg("0;15"), Some(Background::Light));
+/// assert_eq!(Theme::for_background(Background::Light), Theme::light());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// Explanations, hints, borders and punctuation.
+    pub muted: Color,
+    /// The hovered `Item` and the search text.
+    pub hover: Color,
+    /// Hotkeys and index numbers.
+    pub hotkey: Color,
+    /// Messages of rejected input.
+    pub error: Color,
+    /// Type of value in the input prompt.
+    pub kind: Color,
+    /// Current values of `Item`s.
+    pub value: Color,
+    /// Badges of `Item`s colored `Color::Reset`.
+    pub badge: Color,
+}
+impl Theme {
+    /// Colors for dark backgrounds.
+    pub fn dark() -> Self {
+        Self {
+            muted: Color::DarkGrey,
+            hover: Color::Cyan,
+            hotkey: Color::Yellow,
+            error: Color::DarkRed,
+            kind: Color::Blue,
+            value: Color::Green,
+            badge: Color::Magenta,
+        }
+    }
+    /// Colors for light backgrounds.
+    pub fn light() -> Self {
+        Self {
+            muted: Color::DarkGrey,
+            hover: Color::DarkBlue,
+            hotkey: Color::DarkMagenta,
+            error: Color::DarkRed,
+            kind: Color::DarkCyan,
+            value: Color::DarkGreen,
+            badge: Color::DarkYellow,
+        }
+    }
+    /// Colors for the background, detecting it for `Background::Auto`.
+    pub fn for_background(background: Background) -> Self {
+        match background {
+            Background::Light => Self::light(),
+            Background::Dark => Self::dark(),
+            Background::Auto => Self::for_background(Background::detect()),
+        }
+    }
+    /// Colors for the background the terminal reports. Same as `Theme::for_background(Background::Auto)`.
+    pub fn auto() -> Self {
+        Self::for_background(Background::Auto)
+    }
+}
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+/// Background of the terminal, choosing a `Theme`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Background {
+    Light,
+    Dark,
+    /// Detected by `Background::detect()`.
+    Auto,
+}
+impl Background {
+    /// Detects the background from the `COLORFGBG` environment variable many terminals set, like `15;0`. Gives `Background::Dark` if it's not set, since most terminals are dark.
+    pub fn detect() -> Background {
+        env::var("COLORFGBG")
+            .ok()
+            .and_then(|f| Background::from_colorfgbg(&f))
+            .unwrap_or(Background::Dark)
+    }
+    /// Parses a `COLORFGBG` value, whose last field is the background color number. Colors `7` and `9` to `15` are light, and other colors are dark. Gives `None` if the background isn't a number.
+    pub fn from_colorfgbg(value: &str) -> Option<Background> {
+        let background: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+        match background {
+            7 | 9..=15 => Some(Background::Light),
+            _ => Some(Background::Dark),
+        }
+    }
+}
+/// Terminal a `Menu` renders for instead of the real one, given by `RunOptions::assumed_terminal`. Neither its size nor its capabilities are queried, so the same `Menu` renders the same on any machine.
+/// ```
+/// use rushterm::{AssumedTerminal, Capabilities};
+///
+/// let terminal = AssumedTerminal::new(80, 24);
+/// assert_eq!(terminal.capabilities, Capabilities { unicode: true, color: false, hyperlinks: false });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AssumedTerminal {
+    /// Columns, used even instead of the `width` given to `Menu::render_to_string()`.
+    pub width: u16,
+    /// Rows. `Item` rows are limited to it as by `RunOptions::max_rows`, unless that is set.
+    pub height: u16,
+    /// What the terminal displays, used instead of `RunOptions::capabilities`.
+    pub capabilities: Capabilities,
+}
+impl AssumedTerminal {
+    /// Gives the terminal of the size, displaying Unicode without colors or hyperlinks, so golden files stay plain text.
+    pub fn new(width: u16, height: u16) -> AssumedTerminal {
+        AssumedTerminal {
+            width,
+            height,
+            capabilities: Capabilities {
+                unicode: true,
+                color: false,
+                hyperlinks: false,
+            },
+        }
+    }
+}
+/// What the terminal can display. Without `unicode`, box borders and markers are drawn in ASCII, like `+-` and `|`. Without `color`, text is written without colors and attributes. Without `hyperlinks`, `Item::Action::url` isn't linked.
+/// ```
+/// use rushterm::Capabilities;
+///
+/// let dumb = Capabilities::from_env(Some("dumb"), Some("en_US.UTF-8"));
+/// assert_eq!(dumb, Capabilities { unicode: false, color: false, hyperlinks: false });
+/// let posix = Capabilities::from_env(Some("xterm-256color"), Some("C"));
+/// assert_eq!(posix, Capabilities { unicode: false, color: true, hyperlinks: false });
+/// assert!(Capabilities::from_env(Some("xterm-kitty"), None).hyperlinks);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Whether Unicode glyphs like box drawing characters are displayed.
+    pub unicode: bool,
+    /// Whether colors and text attributes like bold are displayed.
+    pub color: bool,
+    /// Whether OSC 8 hyperlinks are clickable. Terminals without them may print the escape sequences, so only known terminals are assumed to have them.
+    pub hyperlinks: bool,
+}
+impl Capabilities {
+    /// Detects the capabilities from the `TERM` environment variable and the locale variables `LC_ALL`, `LC_CTYPE` and `LANG`, the first of them that is set. Colors are also turned off by `NO_COLOR`. Hyperlinks are also turned on by the variables some terminals set, like `TERM_PROGRAM`, `VTE_VERSION` and `WT_SESSION`.
+    pub fn detect() -> Capabilities {
+        let term = env::var("TERM").ok();
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|f| env::var(f).ok())
+            .find(|f| !f.is_empty());
+        let mut capabilities = Capabilities::from_env(term.as_deref(), locale.as_deref());
+        if env::var_os("NO_COLOR").is_some_and(|f| !f.is_empty()) {
+            capabilities.color = false;
+        }
+        let program = env::var("TERM_PROGRAM").ok();
+        let vte: Option<u32> = env::var("VTE_VERSION").ok().and_then(|f| f.parse().ok());
+        if matches!(
+            program.as_deref(),
+            Some("iTerm.app" | "WezTerm" | "vscode" | "ghostty")
+        ) || vte.is_some_and(|f| f >= 5000)
+            || env::var_os("WT_SESSION").is_some()
+        {
+            capabilities.hyperlinks = term.as_deref() != Some("dumb");
+        }
+        capabilities
+    }
+    /// Gives the capabilities for the `TERM` and locale values. A `dumb` terminal displays neither Unicode nor colors. A locale without `UTF-8`, like `C`, doesn't display Unicode. Unset values are assumed capable, except hyperlinks, which only the terminals known by their `TERM`, like `xterm-kitty`, display.
+    pub fn from_env(term: Option<&str>, locale: Option<&str>) -> Capabilities {
+        let dumb = term == Some("dumb");
+        let utf8 = locale.is_none_or(|f| {
+            let f = f.to_ascii_lowercase();
+            f.contains("utf-8") || f.contains("utf8")
+        });
+        let linking = [
+            "xterm-kitty",
+            "wezterm",
+            "foot",
+            "alacritty",
+            "xterm-ghostty",
+        ];
+        Capabilities {
+            unicode: !dumb && utf8,
+            color: !dumb,
+            hyperlinks: term.is_some_and(|f| linking.iter().any(|g| f.starts_with(g))),
+        }
+    }
+}
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self::detect()
+    }
+}
+/// Removes the escape sequences setting colors and text attributes, like `\x1b[1m`, keeping other ones like cursor moves.
+fn strip_styles(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("\x1b[") {
+        stripped.push_str(&rest[..start]);
+        let sequence = &rest[start + 2..];
+        let params = sequence
+            .find(|f: char| !f.is_ascii_digit() && f != ';')
+            .unwrap_or(sequence.len());
+        if sequence[params..].starts_with('m') {
+            rest = &sequence[params + 1..];
+        } else {
+            stripped.push_str(&rest[start..start + 2]);
+            rest = sequence;
+        }
+    }
+    stripped.push_str(rest);
+    stripped
+}
+/// Stream `Menu` is rendered to. Input is read as `RunOptions::input` chooses.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum Output {
+    #[default]
+    Stdout,
+    /// Keeps stdout clean for the output of the program, like piping it to another program while the menu is displayed on the terminal.
+    /// ```
+    /// use rushterm::{Capabilities, Item, Menu, Output, RunOptions, Script, Value};
+    /// use std::{env, process::Command};
+    ///
+    /// // The menu runs in a child process, so its stdout and stderr can be captured.
+    /// if env::var_os("RUSHTERM_STDERR_CHILD").is_some() {
+    ///     let menu = Menu {
+    ///         name: "Main".to_string(),
+    ///         exp: None,
+    ///         items: vec![Item::U32 {
+    ///             name: "Port".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             skippable: false,
+    ///             current: None,
+    ///             confirm: false,
+    ///             step: None,
+    ///         }],
+    ///         esc: true,
+    ///     };
+    ///     let opts = RunOptions {
+    ///         output: Output::Stderr,
+    ///         capabilities: Capabilities {
+    ///             unicode: false,
+    ///             color: false,
+    ///             hyperlinks: false,
+    ///         },
+    ///         ..RunOptions::default()
+    ///     };
+    ///     let selection = menu.run_with_stdin_values(&opts, &["Enter", "x", "8080"]).unwrap();
+    ///     assert_eq!(selection.value, Some(Value::U32(8080)));
+    ///     return;
+    /// }
+    /// let child = Command::new(env::current_exe().unwrap())
+    ///     .env("RUSHTERM_STDERR_CHILD", "1")
+    ///     .output()
+    ///     .unwrap();
+    /// assert!(child.status.success());
+    /// assert!(child.stdout.is_empty());
+    /// assert!(String::from_utf8_lossy(&child.stderr).contains("Main/"));
+    /// ```
+    Stderr,
+    /// Any other stream, like a pseudo terminal or a buffer in tests.
+    Writer(Writer),
+}
+impl Output {
+    fn term(&self) -> Term {
+        match self {
+            Output::Writer(writer) => Term(writer.clone()),
+            output => Term(output.writer()),
+        }
+    }
+    /// Returns a `Writer` of the stream with no write failed yet, for a run of its own.
+    fn writer(&self) -> Writer {
+        match self {
+            Output::Stdout => Writer(Rc::new(RefCell::new(stdout())), Rc::default(), true),
+            Output::Stderr => Writer(Rc::new(RefCell::new(stderr())), Rc::default(), true),
+            Output::Writer(writer) => Writer(writer.0.clone(), Rc::default(), writer.2),
+        }
+    }
+    /// Returns the error the first failed write to the stream failed with.
+    fn error(&self) -> Option<io::ErrorKind> {
+        match self {
+            Output::Writer(writer) => writer.1.get(),
+            _ => None,
+        }
+    }
+    /// Whether the stream is the terminal of the process, whose width can be queried.
+    fn is_terminal(&self) -> bool {
+        match self {
+            Output::Writer(writer) => writer.2,
+            _ => true,
+        }
+    }
+}
+/// Stream of `Output::Writer`. Like `KeyHandler`, clones share the same stream.
+///
+/// Each run of a `Menu` writes through a copy of its own, so a write failing in one run doesn't end another, even if the failed run was abandoned before it returned.
+/// ```
+/// use rushterm::{Item, Menu, MenuError, Output, RunOptions, Writer};
+/// use std::{
+///     cell::Cell,
+///     io::{self, ErrorKind, Write},
+///     rc::Rc,
+/// };
+///
+/// struct Flaky(Rc<Cell<bool>>);
+/// impl Write for Flaky {
+///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+///         match self.0.get() {
+///             true => Err(ErrorKind::BrokenPipe.into()),
+///             false => Ok(buf.len()),
+///         }
+///     }
+///     fn flush(&mut self) -> io::Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// let mut menu = Menu {
+///     name: "Main".to_string(),
+///     exp: None,
+///     items: vec![Item::Action {
+///         name: "Save".to_string(),
+///         hotkey: None,
+///         exp: None,
+///         badges: Vec::new(),
+///         confirm_phrase: None,
+///         global_hotkey: None,
+///         url: None,
+///         checkable: false,
+///     }],
+///     esc: true,
+/// };
+/// let broken = Rc::new(Cell::new(true));
+/// let opts = RunOptions {
+///     output: Output::Writer(Writer::new(Flaky(broken.clone()))),
+///     ..RunOptions::default()
+/// };
+/// let err = MenuError::Io(ErrorKind::BrokenPipe);
+/// assert_eq!(menu.run_with_stdin_values(&opts, &["Enter"]), Err(err.clone()));
+/// broken.set(false);
+/// assert!(menu.run_with_stdin_values(&opts, &["Enter"]).is_ok());
+/// // `run_loop()` returns the error of the run it failed in.
+/// let opts = RunOptions {
+///     script: Some(rushterm::Script::new(["Enter", "Enter"])),
+///     ..opts
+/// };
+/// let result = menu.run_loop(&opts, |_, _| broken.set(true));
+/// assert_eq!(result, Err(err));
+/// ```
+#[derive(Clone)]
+pub struct Writer(
+    Rc<RefCell<dyn Write>>,
+    /// First error writing to the stream, which drops every later write of the run.
+    Rc<Cell<Option<io::ErrorKind>>>,
+    /// Whether the stream is the terminal of the process.
+    bool,
+);
+impl Writer {
+    /// Wraps the stream.
+    pub fn new(writer: impl Write + 'static) -> Self {
+        Self(Rc::new(RefCell::new(writer)), Rc::default(), false)
+    }
+}
+impl fmt::Debug for Writer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "Writer")
+    }
+}
+impl PartialEq for Writer {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+/// Stream `Menu` reads keys and lines from.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum Input {
+    /// The terminal of the process, through crossterm. Lines are read from stdin in raw mode, with bracketed paste on. Like a `Reader`, if stdin ends or fails, the run returns `MenuError::InputEnded`, and so does every later run, since a closed terminal stays closed.
+    #[default]
+    Terminal,
+    /// Any other stream of the bytes a terminal sends, like a pseudo terminal. See `Reader`.
+    Reader(Reader),
+}
+impl Input {
+    /// Returns the error the `Reader` or the terminal ended or failed with.
+    fn error(&self) -> Option<io::ErrorKind> {
+        match self {
+            Input::Terminal => TERMINAL_ERROR.with(Cell::get),
+            Input::Reader(reader) => reader.error(),
+        }
+    }
+}
+/// Stream of `Input::Reader`. Like `Writer`, clones share the same stream.
+///
+/// The stream is read as a terminal in raw mode sends keys: UTF-8 characters, `\r` or `\n` for `Enter`, `DEL` or `BS` for `Backspace`, `\t` for `Tab`, control characters for `Ctrl` and a letter, and the ANSI escape sequences of the arrows, `Home`, `End`, `Delete`, `Shift+Tab` and `F1` to `F12`. The program puts the terminal into raw mode itself, as the `Menu` only switches the process's own. Typed lines are echoed to `RunOptions::output`, since a raw terminal doesn't echo. A lone `Esc` is told apart from the sequences starting with it by the byte after it, so it takes effect when the next key arrives. Its width is `RunOptions::fallback_width` unless the output is the process's terminal. If the stream ends or fails, the run returns `MenuError::InputEnded` with the error, `UnexpectedEof` at the end, since the `Menu` would wait forever.
+///
+/// Text pasted into a line between the bracketed paste marks `ESC [200~` and `ESC [201~` is inserted as literal text: its line breaks and tabs become spaces and other control characters are dropped, so a pasted newline doesn't submit the input. At the prompt of an `Item::Json`, which reads lines up to a blank one, pasted line breaks are kept instead. `Menu` turns bracketed paste on while reading a line from the process's terminal.
+/// ```
+/// use rushterm::{Input, Item, Menu, Output, Reader, RunOptions, Value, Writer};
+/// use std::io::Cursor;
+///
+/// let menu = Menu {
+///     name: "Main".to_string(),
+///     exp: None,
+///     items: vec![Item::String {
+///         name: "Note".to_string(),
+///         hotkey: None,
+///         exp: None,
+///         badges: Vec::new(),
+///         skippable: false,
+///         required: false,
+///         history: Vec::new(),
+///         current: None,
+///     }],
+///     esc: true,
+/// };
+/// // `Enter`, then "a", a paste of two lines, "d" and `Enter`.
+/// let typed = b"\ra\x1b[200~b\r\nc\x1b[201~d\r".to_vec();
+/// let opts = RunOptions {
+///     input: Input::Reader(Reader::new(Cursor::new(typed))),
+///     output: Output::Writer(Writer::new(Vec::new())),
+///     ..RunOptions::default()
+/// };
+/// let selection = menu.run_with(&opts).unwrap();
+/// assert_eq!(selection.value, Some(Value::String("ab  cd".to_string())));
+/// ```
+#[derive(Clone)]
+pub struct Reader(Rc<RefCell<ReadStream>>);
+/// Stream of `Reader`, with the bytes put back after reading ahead.
+struct ReadStream {
+    stream: Box<dyn io::Read>,
+    unread: VecDeque<u8>,
+    /// Error the stream ended or failed with, after which nothing more is read.
+    error: Option<io::ErrorKind>,
+}
+impl Reader {
+    /// Wraps the stream.
+    pub fn new(reader: impl io::Read + 'static) -> Self {
+        Self(Rc::new(RefCell::new(ReadStream {
+            stream: Box::new(reader),
+            unread: VecDeque::new(),
+            error: None,
+        })))
+    }
+    /// Reads the next byte, or the one put back. Gives `0` once the stream ended or failed, keeping its error.
+    fn byte(&self) -> u8 {
+        let mut inner = self.0.borrow_mut();
+        if let Some(byte) = inner.unread.pop_front() {
+            return byte;
+        }
+        if inner.error.is_some() {
+            return 0;
+        }
+        let mut byte = [0];
+        match inner.stream.read_exact(&mut byte) {
+            Ok(()) => byte[0],
+            Err(err) => {
+                inner.error = Some(err.kind());
+                0
+            }
+        }
+    }
+    /// Returns the error the stream ended or failed with, `UnexpectedEof` at its end.
+    fn error(&self) -> Option<io::ErrorKind> {
+        self.0.borrow().error
+    }
+    /// Puts the byte back to be read next.
+    fn unread(&self, byte: u8) {
+        self.0.borrow_mut().unread.push_front(byte);
+    }
+    /// Reads the bytes of the next key. Gives `Esc` once the stream ended, like a `Script` that ended.
+    fn key(&self) -> KeyEvent {
+        let code = match self.byte() {
+            _ if self.error().is_some() => KeyCode::Esc,
+            b'\r' | b'\n' => KeyCode::Enter,
+            0x7f | 0x08 => KeyCode::Backspace,
+            b'\t' => KeyCode::Tab,
+            0x1b => match self.byte() {
+                b'[' => self.csi(),
+                b'O' => match self.byte() {
+                    b'A' => KeyCode::Up,
+                    b'B' => KeyCode::Down,
+                    b'C' => KeyCode::Right,
+                    b'D' => KeyCode::Left,
+                    b'H' => KeyCode::Home,
+                    b'F' => KeyCode::End,
+                    byte @ b'P'..=b'S' => KeyCode::F(byte - b'P' + 1),
+                    _ => KeyCode::Null,
+                },
+                byte => {
+                    self.unread(byte);
+                    KeyCode::Esc
+                }
+            },
+            byte @ 0x01..=0x1a => {
+                let letter = char::from(byte - 1 + b'a');
+                return KeyEvent::new(KeyCode::Char(letter), KeyModifiers::CONTROL);
+            }
+            byte => {
+                // the lead byte tells how many bytes the char takes.
+                let len = match byte {
+                    0xf0..=0xff => 4,
+                    0xe0..=0xef => 3,
+                    0xc0..=0xdf => 2,
+                    _ => 1,
+                };
+                let mut bytes = vec![byte];
+                bytes.extend((1..len).map(|_| self.byte()));
+                match std::str::from_utf8(&bytes)
+                    .ok()
+                    .and_then(|f| f.chars().next())
+                {
+                    Some(chr) => KeyCode::Char(chr),
+                    None => KeyCode::Null,
+                }
+            }
+        };
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+    /// Reads the rest of a control sequence after `ESC [`.
+    fn csi(&self) -> KeyCode {
+        let mut params = String::new();
+        let last = loop {
+            match self.byte() {
+                _ if self.error().is_some() => return KeyCode::Null,
+                byte @ 0x40..=0x7e => break byte,
+                byte => params.push(char::from(byte)),
+            }
+        };
+        match (last, params.as_str()) {
+            (b'A', _) => KeyCode::Up,
+            (b'B', _) => KeyCode::Down,
+            (b'C', _) => KeyCode::Right,
+            (b'D', _) => KeyCode::Left,
+            (b'H', _) | (b'~', "1" | "7") => KeyCode::Home,
+            (b'F', _) | (b'~', "4" | "8") => KeyCode::End,
+            (b'Z', _) => KeyCode::BackTab,
+            (b'~', "3") => KeyCode::Delete,
+            (b'~', "15") => KeyCode::F(5),
+            (b'~', "17") => KeyCode::F(6),
+            (b'~', "18") => KeyCode::F(7),
+            (b'~', "19") => KeyCode::F(8),
+            (b'~', "20") => KeyCode::F(9),
+            (b'~', "21") => KeyCode::F(10),
+            (b'~', "23") => KeyCode::F(11),
+            (b'~', "24") => KeyCode::F(12),
+            _ => KeyCode::Null,
+        }
+    }
+    /// Reads text pasted between bracketed paste marks if the stream is at one, as it was pasted. Other bytes read are put back.
+    fn paste(&self) -> Option<String> {
+        let mut read = vec![];
+        for mark in PASTE_START.bytes() {
+            read.push(self.byte());
+            if read.last() != Some(&mark) {
+                read.iter().rev().for_each(|f| self.unread(*f));
+                return None;
+            }
+        }
+        let mut pasted = vec![];
+        while !pasted.ends_with(PASTE_END.as_bytes()) {
+            pasted.push(self.byte());
+            if self.error().is_some() {
+                return None;
+            }
+        }
+        pasted.truncate(pasted.len() - PASTE_END.len());
+        Some(String::from_utf8_lossy(&pasted).into_owned())
+    }
+    /// Reads keys up to `Enter` as a line, echoing them to the output as typed, followed by a line break.
+    fn line(&self, opts: &RunOptions) -> String {
+        let line = self.read_line(opts);
+        outln!(opts);
+        line
+    }
+    /// Reads keys up to `Enter` as a line, echoing them to the output as typed. Pasted text is inserted as literal text, so its line breaks don't end the line.
+    fn read_line(&self, opts: &RunOptions) -> String {
+        let mut line = String::new();
+        while self.error().is_none() {
+            if let Some(pasted) = self.paste() {
+                let pasted = literal_paste(&pasted);
+                line.push_str(&pasted);
+                out!(opts, "{}", pasted);
+                opts.output.term().flush().expect("flush");
+                continue;
+            }
+            let event = self.key();
+            match event.code {
+                KeyCode::Enter => break,
+                KeyCode::Backspace => {
+                    if line.pop().is_some() {
+                        out!(opts, "\x08 \x08");
+                    }
+                }
+                KeyCode::Char(chr) if event.modifiers.is_empty() => {
+                    line.push(chr);
+                    out!(opts, "{}", chr);
+                }
+                _ => continue,
+            }
+            opts.output.term().flush().expect("flush");
+        }
+        line
+    }
+    /// Reads lines up to a blank one as a block, echoing them to the output as typed, followed by a line break.
+    fn block(&self, opts: &RunOptions) -> String {
+        let block = self.read_block(opts);
+        outln!(opts);
+        block
+    }
+    /// Reads lines up to a blank one as a block, echoing them to the output as typed. Pasted text keeps its line breaks, and `Backspace` doesn't go past the start of a line.
+    fn read_block(&self, opts: &RunOptions) -> String {
+        let mut block = String::new();
+        while self.error().is_none() {
+            if let Some(pasted) = self.paste() {
+                let pasted = block_paste(&pasted);
+                block.push_str(&pasted);
+                out!(opts, "{}", pasted.replace('\n', "\r\n"));
+                opts.output.term().flush().expect("flush");
+                continue;
+            }
+            let event = self.key();
+            match event.code {
+                KeyCode::Enter if block.is_empty() || block.ends_with('\n') => break,
+                KeyCode::Enter => {
+                    block.push('\n');
+                    out!(opts, "\r\n");
+                }
+                KeyCode::Backspace => {
+                    if !block.ends_with('\n') && block.pop().is_some() {
+                        out!(opts, "\x08 \x08");
+                    }
+                }
+                KeyCode::Char(chr) if event.modifiers.is_empty() => {
+                    block.push(chr);
+                    out!(opts, "{}", chr);
+                }
+                _ => continue,
+            }
+            opts.output.term().flush().expect("flush");
+        }
+        block.trim_end_matches('\n').to_string()
+    }
+}
+impl fmt::Debug for Reader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "Reader")
+    }
+}
+impl PartialEq for Reader {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+/// Handle of the stream chosen by `Output`.
+///
+/// Stops on the first write error: the error is kept in the `Writer` of the run, and every later write and flush of the run is dropped. A frame that failed half way is thereby not followed by cursor moves and clears that would corrupt the terminal further, and the `Menu` returns `MenuError::Io` before reading the next key.
+struct Term(Writer);
+impl Term {
+    /// Runs the operation on the stream unless a write already failed, keeping its error.
+    fn fuse<T>(
+        &mut self,
+        dropped: T,
+        operation: impl FnOnce(&mut dyn Write) -> io::Result<T>,
+    ) -> io::Result<T> {
+        let Writer(stream, error, _) = &self.0;
+        if error.get().is_some() {
+            return Ok(dropped);
+        }
+        let result = operation(&mut *stream.borrow_mut());
+        match result {
+            Err(err) if err.kind() != io::ErrorKind::Interrupted => {
+                error.set(Some(err.kind()));
+                Ok(dropped)
+            }
+            result => result,
+        }
+    }
+}
+impl Write for Term {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.fuse(buf.len(), |term| term.write(buf))
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.fuse((), |term| term.flush())
+    }
+}
+/// Placement of the columns of `RunOptions::max_width` on a wider terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+}
+/// Arrangement of `Item`s in `Menu`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Layout {
+    /// One `Item` per line with its explanation next to it.
+    #[default]
+    List,
+    /// `Item`s are filled column by column into a grid. Useful for menus with many short items. Explanations are not displayed. `Left` and `Right` keys move between columns instead of going back and selecting.
+    Grid {
+        /// Maximum number of columns. Less columns are used when the terminal is not wide enough.
+        columns: usize,
+    },
+    /// One `Item` per line like `List`, with its explanation on a dimmed line of its own under it, indented to the name. Suits menus with long explanations.
+    /// ```
+    /// use rushterm::{Item, Layout, Menu, RunOptions};
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![
+    ///         Item::Action {
+    ///             name: "Backup".to_string(),
+    ///             hotkey: Some('b'),
+    ///             exp: Some("Copies files to the backup drive".to_string()),
+    ///             badges: Vec::new(),
+    ///             confirm_phrase: None,
+    ///             global_hotkey: None,
+    ///             url: None,
+    ///             checkable: false,
+    ///         },
+    ///         Item::Action {
+    ///             name: "Quit".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             confirm_phrase: None,
+    ///             global_hotkey: None,
+    ///             url: None,
+    ///             checkable: false,
+    ///         },
+    ///     ],
+    ///     esc: true,
+    /// };
+    /// let list = menu.render_to_string(&RunOptions::default(), 80);
+    /// let lines: Vec<&str> = list.lines().skip(1).take(2).collect();
+    /// assert_eq!(lines, ["0.(B)  Backup Copies files to the backup drive", "1.     Quit"]);
+    ///
+    /// let opts = RunOptions {
+    ///     layout: Layout::Stacked,
+    ///     ..RunOptions::default()
+    /// };
+    /// let stacked = menu.render_to_string(&opts, 80);
+    /// let lines: Vec<&str> = stacked.lines().skip(1).take(3).collect();
+    /// assert_eq!(lines, ["0.(B)  Backup", "       Copies files to the backup drive", "1.     Quit"]);
+    /// assert_eq!(stacked.lines().count(), list.lines().count() + 1);
+    /// ```
+    Stacked,
+}
+/// Gives the data of the selection made in the menu.
+///
+/// With the `serde` feature, it implements `Serialize`, so selections can be logged as JSON. `item_path` is not serialized.
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use rushterm::{Selection, Value};
+/// let selection = Selection {
+///     name: "Volume".to_string(),
+///     path: vec!["Settings".to_string()],
+///     indexes: vec![0],
+///     value: Some(Value::I64(42)),
+///     len: None,
+///     attempt: None,
+///     skipped: false,
+///     item_path: vec![],
+/// };
+/// assert_eq!(
+///     serde_json::to_string(&selection).unwrap(),
+///     r#"{"name":"Volume","path":["Settings"],"indexes":[0],"value":{"type":"I64","value":42},"len":null,"attempt":null,"skipped":false}"#
+/// );
+/// # }
+/// ```
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Selection {
+    /// Name of selected `Item`.
+    pub name: String,
+    /// Vector containing direction of the selected item in the menu tree.
+    pub path: Vec<String>,
+    /// Index path of the selected `Item`, like the one `Menu::select_by_path()` takes. Unlike `path`, it tells apart `Item`s of the same name.
+    pub indexes: Vec<usize>,
+    /// Input by user, if it exists. An empty line entered for a `String` is `Some(Value::String(""))`; only a skipped input or an `Item` without input, like an `Action`, has `None`.
+    pub value: Option<Value>,
+    /// Length of value.
+    pub len: Option<usize>,
+    /// Number of attempts of input. Each rejected attempt replaces the prompt of the previous one, so the rejections don't pile up on the screen.
+    pub attempt: Option<i32>,
+    /// Whether the input was deliberately skipped by `RunOptions::skip_sentinel`, or by giving no value to `Menu::select_by_path()`. `value` is `None` then. Every `skippable` input `Item` is skipped alike, so `skipped` tells "nothing entered" apart from an empty value entered.
+    /// ```
+    /// use rushterm::{Item, Menu, RunOptions, Value};
+    ///
+    /// let menu = Menu {
+    ///     name: "Profile".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::String {
+    ///         name: "Nickname".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         skippable: true,
+    ///         required: false,
+    ///         history: vec![],
+    ///         current: None,
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions::default();
+    /// let skipped = menu.run_with_stdin_values(&opts, &["Enter", "-"]).unwrap();
+    /// assert_eq!((skipped.value, skipped.skipped), (None, true));
+    /// let empty = menu.run_with_stdin_values(&opts, &["Enter", ""]).unwrap();
+    /// assert_eq!((empty.value, empty.skipped), (Some(Value::String(String::new())), false));
+    ///
+    /// let skipped = menu.select_by_path(&[0], None).unwrap();
+    /// assert_eq!((skipped.value, skipped.skipped), (None, true));
+    /// ```
+    pub skipped: bool,
+    /// Clones of the `Item`s along `indexes`, from the top-level one to the selected one, if `RunOptions::item_path` is set. Empty otherwise.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub item_path: Vec<Item>,
+}
+/// Input by user. New kinds of values may be added, so matches on it need a wildcard arm.
+///
+/// With the `serde` feature, it serializes tagged by its kind, like `{"type":"I64","value":42}`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[non_exhaustive]
+pub enum Value {
+    Bool(bool),
+    Char(char),
+    String(String),
+    F32(f32),
+    F64(f64),
+    I32(i32),
+    U32(u32),
+    /// Number of bytes input by `Item::ByteSize`.
+    Bytes(u64),
+    /// Numbers input by `Item::NumberList`, all of its `NumberKind`.
+    List(Vec<Value>),
+    /// Red, green and blue of the color input by `Item::Color`.
+    Color(u8, u8, u8),
+    /// Value adjusted by `Item::Counter`.
+    I64(i64),
+    /// Input of `Item::Secret`.
+    Secret(Secret),
+}
+/// Sensitive text input by `Item::Secret`. `Debug` and serialization give `***` instead of the text, so it isn't logged by accident. Read it by `expose()`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+impl Secret {
+    /// Wraps the text.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self(text.into())
+    }
+    /// Returns the text.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "***")
+    }
+}
+#[cfg(feature = "serde")]
+impl serde::Serialize for Secret {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str("***")
+    }
+}
+/// Type of the numbers of `Item::NumberList`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NumberKind {
+    F32,
+    F64,
+    I32,
+    U32,
+}
+impl NumberKind {
+    /// Parses a number of this type into its `Value`.
+    fn parse(&self, input: &str) -> Option<Value> {
+        match self {
+            NumberKind::F32 => input.parse().ok().map(Value::F32),
+            NumberKind::F64 => input.parse().ok().map(Value::F64),
+            NumberKind::I32 => input.parse().ok().map(Value::I32),
+            NumberKind::U32 => input.parse().ok().map(Value::U32),
+        }
+    }
+    /// Whether the `Value` is of this type.
+    fn matches(&self, value: &Value) -> bool {
+        matches!(
+            (self, value),
+            (NumberKind::F32, Value::F32(_))
+                | (NumberKind::F64, Value::F64(_))
+                | (NumberKind::I32, Value::I32(_))
+                | (NumberKind::U32, Value::U32(_))
+        )
+    }
+    /// Parses numbers separated by commas or spaces. Gives the first invalid element in `Err()`.
+    fn parse_list(&self, input: &str) -> Result<Vec<Value>, String> {
+        input
+            .split(|f: char| f == ',' || f.is_whitespace())
+            .filter(|f| !f.is_empty())
+            .map(|f| self.parse(f).ok_or_else(|| f.to_string()))
+            .collect()
+    }
+}
+impl Value {
+    /// Tolerance suggested for `approx_eq()`. Small enough for typed input, big enough for arithmetic errors of `f64` like `0.1 + 0.2`. `F32` values get the wider `F32_EPSILON` anyway.
+    pub const DEFAULT_EPSILON: f64 = 1e-9;
+    /// Tolerance `approx_eq()` allows at least for `F32` values, relative to the bigger of them: four times `f32::EPSILON`, a few roundings of `f32` arithmetic.
+    pub const F32_EPSILON: f32 = 4.0 * f32::EPSILON;
+    /// Compares `F32` and `F64` values within `epsilon`, and other values exactly. Values of different variants are never equal. Useful for asserting numeric input in tests, where `Value::F64(0.1 + 0.2) == Value::F64(0.3)` is `false`. As an `f64` tolerance is finer than `f32` can tell, `F32` values are also equal within `F32_EPSILON` times the bigger magnitude.
+    /// ```
+    /// use rushterm::Value;
+    ///
+    /// let epsilon = Value::DEFAULT_EPSILON;
+    /// assert_ne!(Value::F64(0.1 + 0.2), Value::F64(0.3));
+    /// assert!(Value::F64(0.1 + 0.2).approx_eq(&Value::F64(0.3), epsilon));
+    /// assert!(!Value::F64(0.3001).approx_eq(&Value::F64(0.3), epsilon));
+    /// assert!(Value::F64(0.3001).approx_eq(&Value::F64(0.3), 1e-3));
+    /// // `f32` arithmetic errs by more than `DEFAULT_EPSILON`.
+    /// assert_ne!(Value::F32(1.1 + 2.2), Value::F32(3.3));
+    /// assert!(Value::F32(1.1 + 2.2).approx_eq(&Value::F32(3.3), epsilon));
+    /// assert!(Value::F32(0.3 + 0.6).approx_eq(&Value::F32(0.9), epsilon));
+    /// assert!(Value::F32(1e6 + 0.1).approx_eq(&Value::F32(1e6), epsilon));
+    /// assert!(!Value::F32(0.3001).approx_eq(&Value::F32(0.3), epsilon));
+    /// // Lists are compared element by element, other values exactly.
+    /// let list = |a, b| Value::List(vec![Value::F64(a), Value::F64(b)]);
+    /// assert!(list(0.1 + 0.2, 1.0).approx_eq(&list(0.3, 1.0), epsilon));
+    /// assert!(!list(0.1 + 0.2, 1.0).approx_eq(&Value::List(vec![Value::F64(0.3)]), epsilon));
+    /// assert!(Value::I32(3).approx_eq(&Value::I32(3), epsilon));
+    /// assert!(!Value::I32(3).approx_eq(&Value::F64(3.0), epsilon));
+    /// ```
+    pub fn approx_eq(&self, other: &Value, epsilon: f64) -> bool {
+        match (self, other) {
+            (Value::F32(a), Value::F32(b)) => {
+                let relative = Value::F32_EPSILON * a.abs().max(b.abs());
+                a == b || (a - b).abs() <= relative || (*a as f64 - *b as f64).abs() <= epsilon
+            }
+            (Value::F64(a), Value::F64(b)) => a == b || (a - b).abs() <= epsilon,
+            (Value::List(a), Value::List(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.approx_eq(b, epsilon))
+            }
+            (a, b) => a == b,
+        }
+    }
+    /// Formats the bare value without its variant, like `42`, `true` or `3.14`, for messages and logs. Floats take the shortest form giving back the same number. `Bytes` is the number of bytes, `List` joins its values by `, `, `Color` is a hex code like `#FF8800`, and `Secret` stays redacted as `***`.
+    /// ```
+    /// use rushterm::{Secret, Value};
+    ///
+    /// assert_eq!(Value::Bool(true).to_plain_string(), "true");
+    /// assert_eq!(Value::Char('y').to_plain_string(), "y");
+    /// assert_eq!(Value::String("host".to_string()).to_plain_string(), "host");
+    /// assert_eq!(Value::F32(3.14).to_plain_string(), "3.14");
+    /// assert_eq!(Value::F64(0.5).to_plain_string(), "0.5");
+    /// assert_eq!(Value::I32(-5).to_plain_string(), "-5");
+    /// assert_eq!(Value::U32(7).to_plain_string(), "7");
+    /// assert_eq!(Value::Bytes(1024).to_plain_string(), "1024");
+    /// assert_eq!(Value::List(vec![Value::I32(1), Value::I32(2)]).to_plain_string(), "1, 2");
+    /// assert_eq!(Value::Color(255, 136, 0).to_plain_string(), "#FF8800");
+    /// assert_eq!(Value::I64(42).to_plain_string(), "42");
+    /// assert_eq!(Value::Secret(Secret::new("hunter2")).to_plain_string(), "***");
+    /// ```
+    pub fn to_plain_string(&self) -> String {
+        match self {
+            Value::Bool(value) => value.to_string(),
+            Value::Char(value) => value.to_string(),
+            Value::String(value) => value.to_string(),
+            Value::F32(value) => value.to_string(),
+            Value::F64(value) => value.to_string(),
+            Value::I32(value) => value.to_string(),
+            Value::U32(value) => value.to_string(),
+            Value::Bytes(value) => value.to_string(),
+            Value::List(values) => values
+                .iter()
+                .map(Value::to_plain_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            Value::Color(r, g, b) => format!("#{:02X}{:02X}{:02X}", r, g, b),
+            Value::I64(value) => value.to_string(),
+            Value::Secret(value) => format!("{:?}", value),
+        }
+    }
+    /// Same as `to_plain_string()`, followed by the type of the value in parentheses, like `42 (i64)`.
+    /// ```
+    /// use rushterm::Value;
+    ///
+    /// assert_eq!(Value::I64(42).to_string_typed(), "42 (i64)");
+    /// assert_eq!(Value::F64(3.14).to_string_typed(), "3.14 (f64)");
+    /// assert_eq!(Value::Bytes(1024).to_string_typed(), "1024 (bytes)");
+    /// ```
+    pub fn to_string_typed(&self) -> String {
+        let kind = match self {
+            Value::Bool(_) => "bool",
+            Value::Char(_) => "char",
+            Value::String(_) => "string",
+            Value::F32(_) => "f32",
+            Value::F64(_) => "f64",
+            Value::I32(_) => "i32",
+            Value::U32(_) => "u32",
+            Value::Bytes(_) => "bytes",
+            Value::List(_) => "list",
+            Value::Color(..) => "color",
+            Value::I64(_) => "i64",
+            Value::Secret(_) => "secret",
+        };
+        format!("{} ({})", self.to_plain_string(), kind)
+    }
+    /// Gives a `String` value as a filesystem path, or `None` for other values and empty text. A leading `~` is expanded to the home directory from the `HOME` environment variable. The path is normalized without touching the filesystem: `.` parts are dropped and `..` parts remove the part before them, so symbolic links are not resolved.
+    /// ```
+    /// use rushterm::Value;
+    /// use std::path::PathBuf;
+    ///
+    /// let value = Value::String("logs/./old/../today.txt".to_string());
+    /// assert_eq!(value.as_path(), Some(PathBuf::from("logs/today.txt")));
+    /// assert_eq!(Value::U32(1).as_path(), None);
+    /// ```
+    pub fn as_path(&self) -> Option<PathBuf> {
+        let text = match self {
+            Value::String(text) if !text.is_empty() => text,
+            _ => return None,
+        };
+        let path = match (text.strip_prefix('~'), env::var_os("HOME")) {
+            (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+                PathBuf::from(home).join(rest.trim_start_matches('/'))
+            }
+            _ => PathBuf::from(text),
+        };
+        let mut normalized = PathBuf::new();
+        for component in path.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir
+                    if matches!(
+                        normalized.components().next_back(),
+                        Some(Component::Normal(_))
+                    ) =>
+                {
+                    normalized.pop();
+                }
+                component => normalized.push(component),
+            }
+        }
+        if normalized.as_os_str().is_empty() {
+            normalized.push(".");
+        }
+        Some(normalized)
+    }
+}
+/// Number of bytes parsed from a size like `512`, `4KB`, `1.5MiB` or `2G`. SI units (`KB`, `MB`, `GB`, `TB`) are powers of 1000. Binary units (`KiB`, `MiB`, `GiB`, `TiB`) and their single letter forms (`K`, `M`, `G`, `T`) are powers of 1024. Units are case-insensitive and `B` alone means bytes. Sizes that don't make a whole number of bytes, like `1.5B`, are rejected as ambiguous.
+/// ```
+/// use rushterm::{ByteSize, Item, Menu, RunOptions, Value};
+///
+/// assert_eq!("512".parse(), Ok(ByteSize(512)));
+/// assert_eq!("4KB".parse(), Ok(ByteSize(4_000)));
+/// assert_eq!("1.5MiB".parse(), Ok(ByteSize(1_572_864)));
+/// assert_eq!("2g".parse(), Ok(ByteSize(2_147_483_648)));
+/// assert!("1.5B".parse::<ByteSize>().is_err());
+/// assert!("4XB".parse::<ByteSize>().is_err());
+///
+/// let menu = Menu {
+///     name: "Main".to_string(),
+///     exp: None,
+///     items: vec![Item::ByteSize {
+///         name: "Cache".to_string(),
+///         hotkey: None,
+///         exp: None,
+///         badges: Vec::new(),
+///         skippable: false,
+///         current: None,
+///     }],
+///     esc: true,
+/// };
+/// // the unknown unit is prompted again.
+/// let selection = menu.run_with_stdin_values(&RunOptions::default(), &["Enter", "4XB", "4KB"]).unwrap();
+/// assert_eq!(selection.value, Some(Value::Bytes(4_000)));
+/// assert_eq!(selection.attempt, Some(2));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ByteSize(pub u64);
+impl FromStr for ByteSize {
+    type Err = String;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        let split = input
+            .find(|c: char| c.is_alphabetic())
+            .unwrap_or(input.len());
+        let (number, unit) = input.split_at(split);
+        let multiplier: u128 = match unit.trim().to_lowercase().as_str() {
+            "" | "b" => 1,
+            "kb" => 1000,
+            "mb" => 1000_u128.pow(2),
+            "gb" => 1000_u128.pow(3),
+            "tb" => 1000_u128.pow(4),
+            "k" | "kib" => 1024,
+            "m" | "mib" => 1024_u128.pow(2),
+            "g" | "gib" => 1024_u128.pow(3),
+            "t" | "tib" => 1024_u128.pow(4),
+            _ => return Err(format!("unknown unit `{}`", unit)),
+        };
+        let number = number.trim();
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if whole.is_empty() && fraction.is_empty()
+            || !whole.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit())
+            || fraction.len() > 18
+        {
+            return Err(format!("invalid number `{}`", number));
+        }
+        let whole: u128 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().map_err(|_| "size is too large".to_string())?
+        };
+        let scale = 10_u128.pow(fraction.len() as u32);
+        let fraction: u128 = if fraction.is_empty() {
+            0
+        } else {
+            fraction.parse().expect("fraction digits parse")
+        };
+        if !(fraction * multiplier).is_multiple_of(scale) {
+            return Err("size is not a whole number of bytes".to_string());
+        }
+        let bytes = whole
+            .checked_mul(multiplier)
+            .map(|f| f + fraction * multiplier / scale)
+            .filter(|f| *f <= u64::MAX as u128)
+            .ok_or_else(|| "size is too large".to_string())?;
+        Ok(ByteSize(bytes as u64))
+    }
+}
+/// Red, green and blue of a color parsed from a hex code like `#FF8800`, with or without `#`, or from a name. Names are case-insensitive: `black`, `white`, `grey`, `red`, `green`, `blue`, `yellow`, `cyan`, `magenta`, `orange` and `purple`. Convert it to the color type of the terminal library in use, like `crossterm::style::Color::Rgb`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+impl Rgb {
+    /// Colors accepted by name.
+    pub const NAMED: [(&'static str, Rgb); 11] = [
+        ("black", Rgb(0, 0, 0)),
+        ("white", Rgb(255, 255, 255)),
+        ("grey", Rgb(128, 128, 128)),
+        ("red", Rgb(255, 0, 0)),
+        ("green", Rgb(0, 128, 0)),
+        ("blue", Rgb(0, 0, 255)),
+        ("yellow", Rgb(255, 255, 0)),
+        ("cyan", Rgb(0, 255, 255)),
+        ("magenta", Rgb(255, 0, 255)),
+        ("orange", Rgb(255, 165, 0)),
+        ("purple", Rgb(128, 0, 128)),
+    ];
+}
+impl FromStr for Rgb {
+    type Err = String;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        if let Some((_, rgb)) = Rgb::NAMED
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(input))
+        {
+            return Ok(*rgb);
+        }
+        let hex = input.strip_prefix('#').unwrap_or(input);
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("invalid color `{}`", input));
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).expect("hex channel");
+        Ok(Rgb(channel(0), channel(2), channel(4)))
+    }
+}
+/// Position of the user in a `Menu`, kept from one run to the next by `Menu::run_with_history()`. Starts at the first `Item`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MenuState {
+    hover: usize,
+}
+impl MenuState {
+    /// Index of the top-level `Item` hovered when the next run starts.
+    pub fn hover(&self) -> usize {
+        self.hover
+    }
+}
+/// Collapse states of `Group`s kept from one program run to the next by `RunOptions::load_ui_state` and `RunOptions::save_ui_state`. Each `Group` is keyed by the names leading to it joined by `/`, like `Main/Settings/Network`, with `\` and `/` in names escaped by a `\`, like `Main/Read\/Write`. The program stores it where it likes, such as a file, reading `groups()` or serializing it with the `serde` feature.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UiState {
+    groups: BTreeMap<String, bool>,
+}
+impl UiState {
+    /// Whether the `Group` at the key is collapsed, or `None` if it isn't recorded.
+    pub fn collapsed(&self, key: &str) -> Option<bool> {
+        self.groups.get(key).copied()
+    }
+    /// Records whether the `Group` at the key is collapsed.
+    pub fn set_collapsed(&mut self, key: impl Into<String>, collapsed: bool) {
+        self.groups.insert(key.into(), collapsed);
+    }
+    /// Collapse states by the keys of the `Group`s.
+    pub fn groups(&self) -> &BTreeMap<String, bool> {
+        &self.groups
+    }
+}
+/// Line edited in raw mode: `Left`, `Right`, `Home` and `End` move the cursor, `Backspace` and `Delete` remove the character before or after it, and `Up` and `Down` replace the line by an entry of the history, like a shell does.
+#[derive(Default)]
+struct LineEdit {
+    chars: Vec<char>,
+    /// Index of the character the cursor is at, or the length of the line at its end.
+    cursor: usize,
+    /// Index of the recalled history entry, if any.
+    recalled: Option<usize>,
+}
+impl LineEdit {
+    /// Applies the key, returning `false` if it's not an editing key.
+    fn key(&mut self, code: KeyCode, history: &[String]) -> bool {
+        match code {
+            KeyCode::Up | KeyCode::Down => {
+                self.recalled = recall(history.len(), self.recalled, code == KeyCode::Up);
+                self.chars = self
+                    .recalled
+                    .map_or(vec![], |f| history[f].chars().collect());
+                self.cursor = self.chars.len();
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.chars.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.chars.len(),
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.chars.remove(self.cursor);
+            }
+            KeyCode::Delete if self.cursor < self.chars.len() => {
+                self.chars.remove(self.cursor);
+            }
+            KeyCode::Backspace | KeyCode::Delete => {}
+            KeyCode::Char(chr) => {
+                self.chars.insert(self.cursor, chr);
+                self.cursor += 1;
+            }
+            _ => return false,
+        }
+        true
+    }
+    /// Applies the key like `key()`, except that `Up` and `Down` adjust a number by the `step` of the `Item`, leaving the line as is if it's not a number.
+    fn edit_key(&mut self, item: &Item, code: KeyCode, history: &[String]) -> bool {
+        let up = match code {
+            KeyCode::Up => true,
+            KeyCode::Down => false,
+            _ => return self.key(code, history),
+        };
+        if item.stepped("", up).is_none() {
+            return self.key(code, history);
+        }
+        if let Some(text) = item.stepped(&self.text(TrimPolicy::Both), up) {
+            self.chars = text.chars().collect();
+            self.cursor = self.chars.len();
+        }
+        true
+    }
+    /// Returns the line, trimmed by the policy like other inputs.
+    fn text(&self, trim: TrimPolicy) -> String {
+        trim.apply(&self.chars.iter().collect::<String>())
+            .to_string()
+    }
+}
+/// Interactive state of a displayed `Menu`.
+#[derive(Clone, Debug, Default)]
+struct State {
+    /// Index of the hovered `Item`.
+    hover: usize,
+    /// Search text typed after `/`, if searching.
+    filter: Option<String>,
+    /// Position of the first displayed `Item` among the ones the filter displays, for `RunOptions::max_rows`.
+    scroll: usize,
+    /// Adjusted values of `Counter`s by their indexes.
+    counters: HashMap<usize, i64>,
+    /// Toggled collapse states of `Group`s by their indexes.
+    collapsed: HashMap<usize, bool>,
+    /// Last accepted key and its time, for `RunOptions::debounce`.
+    last_key: Option<(KeyCode, Instant)>,
+    /// Checked `Action`s if run by `Menu::run_checklist()`. Clones of the state share them.
+    checked: Option<Rc<RefCell<Checklist>>>,
+    /// Whether explanations of `Item`s are hidden by `RunOptions::exp_toggle_key`. Clones of the state and `SubMenu`s entered from it share it.
+    hide_exp: Rc<Cell<bool>>,
+    /// Whether explanations masked by `RunOptions::mask_exp` are revealed by `RunOptions::reveal_key`. Shared like `hide_exp`.
+    revealed: Rc<Cell<bool>>,
+    /// Index paths of `Action`s by their `global_hotkey`s, set on the top level. `SubMenu`s entered from the state share them.
+    globals: Rc<HashMap<char, Vec<usize>>>,
+    /// Message displayed instead of the hint until the next key, like a blocked checklist confirmation.
+    notice: Option<String>,
+    /// Whether a value was entered in the `Menu` and kept by `RunOptions::stay`, for `RunOptions::confirm_discard`.
+    entered: bool,
+    /// Dotted path of index numbers typed for `RunOptions::path_jump`.
+    typed_path: Option<String>,
+    /// Index keys left to replay for a `RunOptions::path_jump`. `SubMenu`s entered from the state share them.
+    jump: Rc<RefCell<VecDeque<String>>>,
+    /// Whether the key being handled is replayed from `jump`, so it isn't typed into a path again.
+    jumping: bool,
+    /// Hovers before the last moves, latest last, for `RunOptions::undo_key`.
+    moves: Vec<usize>,
+    /// Collapse states of `Group`s loaded by `RunOptions::load_ui_state` and saved by `RunOptions::save_ui_state`. `SubMenu`s entered from the state share them.
+    ui_state: Option<Rc<RefCell<UiState>>>,
+    /// Hotkeys assigned by `RunOptions::auto_hotkeys` by the indexes of the `Item`s, set on first use, as the `Item`s of a level don't change while it's displayed.
+    hotkeys: OnceCell<Vec<Option<char>>>,
+}
+/// Checked `Action`s of `Menu::run_checklist()`.
+#[derive(Debug, Default)]
+struct Checklist {
+    /// Indexes of the checked `Action`s, in the order they were checked.
+    indexes: Vec<usize>,
+    /// Whether selecting a checkable `Action` confirmed them.
+    confirmed: bool,
+}
+/// Errors of `Menu` operations.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MenuError {
+    /// There is no `Item` at the index path.
+    InvalidPath(Vec<usize>),
+    /// The `Item` at the index path is a `SubMenu` or a `Group`, which can't be selected itself.
+    NotSelectable(Vec<usize>),
+    /// The input `Item` at the index path needs a value, as it isn't `skippable`.
+    MissingValue(Vec<usize>),
+    /// The value given for the input `Item` at the index path is not of its type.
+    ValueMismatch(Vec<usize>),
+    /// The value given for the `F64` `Item` at the index path has more decimal places than allowed.
+    TooManyDecimals(Vec<usize>),
+    /// The hotkey pressed is assigned to several displayed `Item`s, with `HotkeyPolicy::Error`.
+    AmbiguousHotkey(char),
+    /// Writing to the `Output` failed with the error, like `io::ErrorKind::BrokenPipe` when the terminal is gone. The `Menu` stops writing at the first failure, so the partially rendered frame is left as is.
+    /// ```
+    /// use rushterm::{Item, Menu, MenuError, Output, RunOptions, Writer};
+    /// use std::io::{self, ErrorKind, Write};
+    ///
+    /// struct Broken(usize);
+    /// impl Write for Broken {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         if self.0 < buf.len() {
+    ///             return Err(ErrorKind::BrokenPipe.into());
+    ///         }
+    ///         self.0 -= buf.len();
+    ///         Ok(buf.len())
+    ///     }
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::Action {
+    ///         name: "Save".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         confirm_phrase: None,
+    ///         global_hotkey: None,
+    ///         url: None,
+    ///         checkable: false,
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions {
+    ///     output: Output::Writer(Writer::new(Broken(10))),
+    ///     ..RunOptions::default()
+    /// };
+    /// let err = menu.run_with_stdin_values(&opts, &[]).unwrap_err();
+    /// assert!(matches!(err, MenuError::Io(ErrorKind::BrokenPipe)));
+    /// ```
+    Io(io::ErrorKind),
+    /// The current value of the input `Item` at the index path is not a valid input of its type.
+    InvalidCurrent { path: Vec<usize>, value: String },
+    /// Reading the script file given to `Script::from_file()` failed.
+    ScriptUnreadable(io::ErrorKind),
+    /// The line of the script, starting from 1, is not an action: it names no key and doesn't type a text after `>`. A run returns it too for an action it reads as a key that names none, with its line, or its position among the actions of `Script::new()`.
+    InvalidScriptLine(usize),
+    /// The `RunOptions::script` ended while the `Menu` waited for another action.
+    /// ```
+    /// use rushterm::{Item, Menu, MenuError, RunOptions};
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::U32 {
+    ///         name: "Port".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         skippable: false,
+    ///         current: None,
+    ///         confirm: false,
+    ///         step: None,
+    ///     }],
+    ///     esc: false,
+    /// };
+    /// let err = MenuError::ScriptEnded;
+    /// let opts = RunOptions::default();
+    /// // Waits for a key.
+    /// assert_eq!(menu.run_with_stdin_values(&opts, &["Down"]), Err(err.clone()));
+    /// // Waits for the value of `Port`.
+    /// assert_eq!(menu.run_with_stdin_values(&opts, &["Enter"]), Err(err.clone()));
+    /// // Waits for a valid value after an invalid one.
+    /// assert_eq!(menu.run_with_stdin_values(&opts, &["Enter", "eighty"]), Err(err));
+    /// assert!(menu.run_with_stdin_values(&opts, &["Enter", "80"]).is_ok());
+    /// ```
+    ScriptEnded,
+    /// The `Reader` of `RunOptions::input`, or the terminal of the process, ended, with `io::ErrorKind::UnexpectedEof`, or failed with the error while the `Menu` waited for a key or a line.
+    /// ```
+    /// use rushterm::{Input, Item, Menu, MenuError, Output, Reader, RunOptions, Writer};
+    /// use std::io::{Cursor, ErrorKind};
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::U32 {
+    ///         name: "Port".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         skippable: false,
+    ///         current: None,
+    ///         confirm: false,
+    ///         step: None,
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let run = |typed: &[u8]| {
+    ///     let opts = RunOptions {
+    ///         input: Input::Reader(Reader::new(Cursor::new(typed.to_vec()))),
+    ///         output: Output::Writer(Writer::new(Vec::new())),
+    ///         ..RunOptions::default()
+    ///     };
+    ///     menu.run_with(&opts)
+    /// };
+    /// let err = MenuError::InputEnded(ErrorKind::UnexpectedEof);
+    /// // Waits for a key, and for the rest of the line of `Port`.
+    /// assert_eq!(run(b""), Err(err.clone()));
+    /// assert_eq!(run(b"\r80"), Err(err));
+    /// assert!(run(b"\r80\r").is_ok());
+    /// ```
+    InputEnded(io::ErrorKind),
+    /// The `Menu` was exited by `Esc` in the root `Menu`, like after backing out of every `SubMenu`.
+    Exited,
+    /// The `Menu` was exited by `Esc` in the `SubMenu`, or the choice of the `Bool`, at the index path, without backing out to the root `Menu` first.
+    Escaped(Vec<usize>),
+    /// A `KeyHandler` ended the `Menu` by `KeyFlow::Exit` with the text.
+    Run(String),
+}
+impl fmt::Display for MenuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            MenuError::InvalidPath(path) => write!(f, "no item at path {:?}", path),
+            MenuError::NotSelectable(path) => {
+                write!(f, "item at path {:?} is a sub menu or a group", path)
+            }
+            MenuError::MissingValue(path) => write!(f, "item at path {:?} needs a value", path),
+            MenuError::ValueMismatch(path) => {
+                write!(f, "value for item at path {:?} is not of its type", path)
+            }
+            MenuError::TooManyDecimals(path) => {
+                write!(
+                    f,
+                    "value for item at path {:?} has too many decimal places",
+                    path
+                )
+            }
+            MenuError::AmbiguousHotkey(hotkey) => {
+                write!(f, "hotkey '{}' is assigned to several items", hotkey)
+            }
+            MenuError::InvalidCurrent { path, value } => {
+                write!(
+                    f,
+                    "current value `{}` of item at path {:?} is not of its type",
+                    value, path
+                )
+            }
+            MenuError::Io(kind) => write!(f, "writing the menu failed: {}", io::Error::from(*kind)),
+            MenuError::ScriptUnreadable(kind) => {
+                write!(f, "reading the script failed: {}", io::Error::from(*kind))
+            }
+            MenuError::InvalidScriptLine(line) => {
+                write!(f, "line {} of the script is not an action", line)
+            }
+            MenuError::ScriptEnded => write!(f, "the script ended before the menu"),
+            MenuError::InputEnded(kind) => {
+                write!(f, "reading the input failed: {}", io::Error::from(*kind))
+            }
+            MenuError::Exited => write!(f, "the menu was exited"),
+            MenuError::Escaped(path) => write!(f, "the menu was exited at path {:?}", path),
+            MenuError::Run(text) => write!(f, "{}", text),
+        }
+    }
+}
+impl std::error::Error for MenuError {}
+/// Structural problem of a `Menu` found by `Menu::validate_all()`. The `Menu` still runs, but it may not behave as intended.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuWarning {
+    /// Index path of the `Item` having the problem. Empty for the `Menu` itself.
+    pub path: Vec<usize>,
+    /// Description of the problem.
+    pub description: String,
+}
+impl fmt::Display for MenuWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "path {:?}: {}", self.path, self.description)
+    }
+}
+impl Menu {
+    /// Prints out `Item`s, executes the `Menu` and returns `Result`. Exiting by `Esc` returns `MenuError::Exited` in the root `Menu`, and `MenuError::Escaped` with the index path in a `SubMenu`. Going back from a `SubMenu` by `Backspace` or `Left` returns to its parent, and doing so in the root `Menu` does nothing, so backing out of every `SubMenu` keeps the `Menu` open instead of ending it.
+    pub fn run(&self) -> Result<Selection, MenuError> {
+        self.run_with(&RunOptions::default())
+    }
+    /// Same as `run()`, but presents and behaves as set by `opts`. The keys it reads advance the levels of the `Menu` as they would a `MenuSession`, drawing each change as it goes.
+    pub fn run_with(&self, opts: &RunOptions) -> Result<Selection, MenuError> {
+        let opts = &opts.for_run();
+        let state = State {
+            hover: if opts.start_unfocused { UNFOCUSED } else { 0 },
+            ..State::default()
+        };
+        let selection = MenuRun::start(vec![self.level(opts, state)], true).drive()?;
+        Ok(self.trace(opts, selection))
+    }
+    /// Fills `Selection::item_path` if `RunOptions::item_path` is set.
+    fn trace(&self, opts: &RunOptions, mut selection: Selection) -> Selection {
+        if !opts.item_path {
+            return selection;
+        }
+        for index in &selection.indexes {
+            let item = match selection.item_path.last() {
+                Some(parent) => parent.sub_items().and_then(|f| f.into_iter().nth(*index)),
+                None => self.items.get(*index).cloned(),
+            };
+            match item {
+                Some(item) => selection.item_path.push(item),
+                None => break,
+            }
+        }
+        selection
+    }
+    /// Same as `run_with()`, but driven by the `actions` instead of the terminal, like `["Down", "Enter", "42"]` hovering the second `Item` and typing `42` into it. See `Script` for the actions. Suits tests and documentation examples. Returns `MenuError::ScriptEnded` if the actions end before the `Menu` does, and `MenuError::InvalidScriptLine` with the position of an action, from 1, that is read as a key but names none.
+    /// ```
+    /// use rushterm::{Item, Menu, MenuError, RunOptions, Value};
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![
+    ///         Item::Action {
+    ///             name: "Save".to_string(),
+    ///             hotkey: Some('s'),
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             confirm_phrase: None,
+    ///             global_hotkey: None,
+    ///             url: None,
+    ///             checkable: false,
+    ///         },
+    ///         Item::U32 {
+    ///             name: "Count".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             skippable: false,
+    ///             current: None,
+    ///             confirm: false,
+    ///             step: None,
+    ///         },
+    ///     ],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions::default();
+    /// let selection = menu.run_with_stdin_values(&opts, &["Down", "Enter", "42"]);
+    /// assert_eq!(selection.unwrap().value, Some(Value::U32(42)));
+    /// // "hello" is read as a key, while `Item`s are displayed.
+    /// let err = MenuError::InvalidScriptLine(2);
+    /// assert_eq!(menu.run_with_stdin_values(&opts, &["Down", "hello"]), Err(err));
+    /// ```
+    pub fn run_with_stdin_values(
+        &self,
+        opts: &RunOptions,
+        actions: &[&str],
+    ) -> Result<Selection, MenuError> {
+        let opts = RunOptions {
+            script: Some(Script::new(actions.iter().copied())),
+            ..opts.clone()
+        };
+        self.run_with(&opts)
+    }
+    /// Same as `run_with()`, but driven by the actions of the script file at `path` instead of the terminal, one per line, as `Script::from_file()` reads them. Replays recorded sessions for reproducible demos and tests. Returns the `MenuError` of `Script::from_file()` if the file can't be read or has a malformed line.
+    pub fn run_script(
+        &self,
+        opts: &RunOptions,
+        path: impl AsRef<Path>,
+    ) -> Result<Selection, MenuError> {
+        let script = Script::from_file(path)?;
+        let opts = RunOptions {
+            script: Some(script),
+            ..opts.clone()
+        };
+        self.run_with(&opts)
+    }
+    /// Starts a `MenuSession` of the `Menu` presented as set by `opts`, writing its frames to the `writer` instead of `RunOptions::output`. `RunOptions::script` is left out, since the session gives the keys.
+    pub fn session(&self, opts: &RunOptions, writer: Writer) -> MenuSession<'_> {
+        let opts = RunOptions {
+            output: Output::Writer(writer),
+            script: None,
+            ..opts.clone()
+        };
+        let run_opts = RunOptions {
+            output: Output::Writer(Writer::new(io::sink())),
+            ..opts.clone()
+        };
+        let state = State {
+            hover: if opts.start_unfocused { UNFOCUSED } else { 0 },
+            ..State::default()
+        };
+        let run = MenuRun::start(vec![self.level(&run_opts, state)], true);
+        MenuSession {
+            menu: self,
+            opts,
+            run: Some(run),
+            edit: LineEdit::default(),
+            lines: vec![],
+            height: 0,
+        }
+    }
+    /// Same as `run_with()`, but hover starts where `history` left it, and `history` is updated to where the user was when the `Menu` ended: the top-level `Item` selected or containing the selection, or else the last hovered one. Passing the same `MenuState` to each run of a repeatedly shown `Menu` saves navigating back to the same place every time.
+    pub fn run_with_history(
+        &self,
+        opts: &RunOptions,
+        history: &mut MenuState,
+    ) -> Result<Selection, MenuError> {
+        let opts = &opts.for_run();
+        let state = State {
+            hover: history.hover,
+            ..State::default()
+        };
+        let mut run = MenuRun::start(vec![self.level(opts, state)], false);
+        let result = run.drive().map(|f| self.trace(opts, f));
+        let hover = run.levels[0].state.hover;
+        history.hover = match &result {
+            Ok(selection) => selection.indexes.first().copied().unwrap_or(hover),
+            Err(_) => hover,
+        };
+        result
+    }
+    /// Runs the `Menu` again and again, calling `on_selection` with each `Selection` and the `Menu` itself between runs, until it is exited by `Esc`. Suits stateful wizards, where earlier selections change the `Item`s offered next, like selecting "Advanced mode" adding advanced `Item`s. Other errors are returned in `Err()`.
+    ///
+    /// `on_selection` runs while nothing is displayed, so it may change anything of the `Menu`: add, remove or replace `Item`s, or change its name. Hover starts at the first `Item` on each run, unless `RunOptions::stay_in_submenu` is set. Keep `RunOptions::default_action` pointing to an existing `Item`. If `esc` is `false`, `on_selection` should set it at some point to let the loop end.
+    pub fn run_loop(
+        &mut self,
+        opts: &RunOptions,
+        mut on_selection: impl FnMut(&Selection, &mut Menu),
+    ) -> Result<(), MenuError> {
+        let mut last: Vec<usize> = vec![];
+        let mut opts = opts.clone();
+        if opts.dry_run != DryRun::Off {
+            // the loop reports each selection once, in place of `on_selection`.
+            opts.stay = None;
+        }
+        loop {
+            let result = match last.split_last() {
+                Some((hover, path)) if !path.is_empty() && self.is_sub_path(path) => {
+                    self.run_at_hover(&opts, path, *hover)
+                }
+                _ => self.run_with(&opts),
+            };
+            // shown once, before the first run.
+            opts.splash = None;
+            match result {
+                Ok(selection) => {
+                    if opts.stay_in_submenu {
+                        last = selection.indexes.to_vec();
+                    }
+                    if opts.dry_run == DryRun::Off {
+                        on_selection(&selection, self)
+                    } else {
+                        report_dry_run(&opts, &selection);
+                    }
+                }
+                Err(MenuError::Exited | MenuError::Escaped(_)) => return Ok(()),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+    /// Whether the index path points to a `SubMenu` or a `LazySubMenu` reached through `SubMenu`s.
+    fn is_sub_path(&self, path: &[usize]) -> bool {
+        let mut found = false;
+        self.walk(|f, item| {
+            if f == path && item.sub_items().is_some() {
+                found = true;
+            }
+        });
+        found
+    }
+    /// Same as `run_with()`, but opens directly in the `SubMenu` at the index path, like `Settings > Network`. Going back returns up the path as if the user had descended it. Returns `MenuError::InvalidPath` if an index doesn't point to a `SubMenu`.
+    pub fn run_at(&self, opts: &RunOptions, path: &[usize]) -> Result<Selection, MenuError> {
+        self.run_at_hover(opts, path, 0)
+    }
+    /// Same as `run_at()`, but hovering the `Item` at the index of the `SubMenu`.
+    fn run_at_hover(
+        &self,
+        opts: &RunOptions,
+        path: &[usize],
+        hover: usize,
+    ) -> Result<Selection, MenuError> {
+        let opts = &opts.for_run();
+        let levels = self.levels_at(opts, path, hover)?;
+        let selection = MenuRun::start(levels, false).drive()?;
+        Ok(self.trace(opts, selection))
+    }
+    /// Returns the levels of the `SubMenu`s at the index `path` one by one, each hovering the `Item` entering the next, and the last one `hover`. Nothing is printed, so an invalid index can still be reported.
+    fn levels_at(
+        &self,
+        opts: &RunOptions,
+        path: &[usize],
+        hover: usize,
+    ) -> Result<Vec<Level<'_>>, MenuError> {
+        let state = State {
+            hover: path.first().copied().unwrap_or(hover),
+            ..State::default()
+        };
+        let mut levels = vec![self.level(opts, state)];
+        for (n, index) in path.iter().enumerate() {
+            let parent = levels.last().expect("level");
+            let items = (parent.menu.items.get(*index))
+                .and_then(Item::sub_items)
+                .ok_or_else(|| MenuError::InvalidPath(path.to_vec()))?;
+            let opts = sub_opts(&parent.opts);
+            let mut level =
+                (parent.menu).sub_level(opts, &parent.path, &parent.state, *index, items);
+            level.state.hover = path.get(n + 1).copied().unwrap_or(hover);
+            levels.push(*level);
+        }
+        Ok(levels)
+    }
+    /// Same as `run_with()`, but top-level `Action`s that are `checkable` can be checked by `Space`. Selecting a checkable `Action` while some are checked confirms and returns all checked `Action`s in their order. Without checked `Action`s, and for other `Item`s, the single selection is returned as usual. Suits batch operations like "run these tasks".
+    pub fn run_checklist(&self, opts: &RunOptions) -> Result<Vec<Selection>, MenuError> {
+        let opts = &opts.for_run();
+        let checklist = Rc::new(RefCell::new(Checklist::default()));
+        let state = State {
+            checked: Some(checklist.clone()),
+            ..State::default()
+        };
+        let selection = MenuRun::start(vec![self.level(opts, state)], false).drive()?;
+        let checklist = checklist.borrow();
+        if !checklist.confirmed {
+            return Ok(vec![self.trace(opts, selection)]);
+        }
+        let mut checked = checklist.indexes.clone();
+        checked.sort_unstable();
+        Ok(checked
+            .iter()
+            .map(|f| self.select_by_path(&[*f], None).expect("checked action"))
+            .map(|f| self.trace(opts, f))
+            .collect())
+    }
+    /// Same as `run_with()`, but also gives the selected `Item` of the `Menu` itself, found by `Selection::indexes`, to dispatch on it instead of matching its name. `Item`s of a `LazySubMenu` are built on entry and not kept, so the `LazySubMenu` is given for them. Errors of the run are given as by `run_with()`.
+    /// ```
+    /// use rushterm::{Item, Menu, MenuError, RunOptions, Script};
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![
+    ///         Item::Action {
+    ///             name: "Save".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             confirm_phrase: None,
+    ///             global_hotkey: None,
+    ///             url: None,
+    ///             checkable: false,
+    ///         },
+    ///         Item::Action {
+    ///             name: "Save".to_string(),
+    ///             hotkey: None,
+    ///             exp: Some("Saves a copy.".to_string()),
+    ///             badges: Vec::new(),
+    ///             confirm_phrase: None,
+    ///             global_hotkey: None,
+    ///             url: None,
+    ///             checkable: false,
+    ///         },
+    ///     ],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions {
+    ///     script: Some(Script::new(["1"])),
+    ///     ..RunOptions::default()
+    /// };
+    /// let (_, item) = menu.run_returning_item(&opts).unwrap();
+    /// assert!(std::ptr::eq(item, &menu.items[1]));
+    /// let opts = RunOptions {
+    ///     script: Some(Script::new(["Esc"])),
+    ///     ..RunOptions::default()
+    /// };
+    /// assert_eq!(menu.run_returning_item(&opts).unwrap_err(), MenuError::Exited);
+    /// ```
+    pub fn run_returning_item(&self, opts: &RunOptions) -> Result<(Selection, &Item), MenuError> {
+        let selection = self.run_with(opts)?;
+        let invalid = || MenuError::InvalidPath(selection.indexes.to_vec());
+        let mut items = &self.items;
+        let mut item = None;
+        for index in &selection.indexes {
+            let found = items.get(*index).ok_or_else(invalid)?;
+            item = Some(found);
+            match found {
+                Item::SubMenu {
+                    items: sub_items, ..
+                } => items = sub_items,
+                _ => break,
+            }
+        }
+        let item = item.ok_or_else(invalid)?;
+        Ok((selection, item))
+    }
+    /// Runs the `Menu` like `run_with()` and gives only the `Value` of the selection, trimming the `Selection` for a prompt built as a single input `Item`. Selecting an `Item` without a value, like an `Action`, or skipping a `skippable` input gives `Ok(None)`. Errors of the run are given as by `run_with()`, like `MenuError::Exited` for `Esc`.
+    /// ```
+    /// use rushterm::{Item, Menu, MenuError, RunOptions, Script, Value};
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![
+    ///         Item::U32 {
+    ///             name: "Port".to_string(),
+    ///             hotkey: Some('p'),
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             skippable: false,
+    ///             current: None,
+    ///             confirm: false,
+    ///             step: None,
+    ///         },
+    ///         Item::Action {
+    ///             name: "Cancel".to_string(),
+    ///             hotkey: Some('c'),
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             confirm_phrase: None,
+    ///             global_hotkey: None,
+    ///             url: None,
+    ///             checkable: false,
+    ///         },
+    ///     ],
+    ///     esc: true,
+    /// };
+    /// let run = |keys: &[&str]| {
+    ///     let opts = RunOptions {
+    ///         script: Some(Script::new(keys.iter().copied())),
+    ///         ..RunOptions::default()
+    ///     };
+    ///     menu.run_returning_value_only(&opts)
+    /// };
+    /// assert_eq!(run(&["p", "8080"]), Ok(Some(Value::U32(8080))));
+    /// assert_eq!(run(&["c"]), Ok(None));
+    /// assert_eq!(run(&["Esc"]), Err(MenuError::Exited));
+    /// ```
+    pub fn run_returning_value_only(&self, opts: &RunOptions) -> Result<Option<Value>, MenuError> {
+        self.run_with(opts).map(|f| f.value)
+    }
+    /// Renders the first frame of the `Menu`, as `run_with()` would display it on a terminal `width` columns wide, or the width of `RunOptions::assumed_terminal` if set, to plain text without colors. Lines longer than `width` are left for the terminal to wrap.
+    /// ```
+    /// use rushterm::{Item, Menu, RunOptions};
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::Action {
+    ///         name: "Build".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         confirm_phrase: None,
+    ///         global_hotkey: None,
+    ///         url: None,
+    ///         checkable: false,
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions::default();
+    /// let wide = menu.render_to_string(&opts, 80);
+    /// assert!(wide.contains("(Up)(Down) Move, (Enter) Select, (Esc) Exit"));
+    /// // the legend wraps between its entries on a narrow terminal.
+    /// let narrow = menu.render_to_string(&opts, 30);
+    /// let legend: Vec<&str> = narrow.lines().skip(2).take(2).collect();
+    /// assert_eq!(legend, ["(Up)(Down) Move", "(Enter) Select, (Esc) Exit"]);
+    /// // the hint is left for the terminal to wrap, taking two lines.
+    /// assert!(narrow.lines().all(|f| f.chars().count() <= 30 || f.starts_with("Press")));
+    /// assert_eq!(menu.rendered_height(&opts, 30) as usize, narrow.lines().count() + 1);
+    /// ```
+    pub fn render_to_string(&self, opts: &RunOptions, width: u16) -> String {
+        let buffer = Rc::new(RefCell::new(Vec::new()));
+        let opts = RunOptions {
+            capabilities: Capabilities {
+                color: false,
+                ..opts.capabilities()
+            },
+            output: Output::Writer(Writer(buffer.clone(), Rc::default(), false)),
+            ..opts.clone()
+        };
+        with_width(width, || {
+            let path = vec![self.name.to_string()];
+            let mut state = State::default();
+            self.fix_hover(&opts, &mut state);
+            self.print_header(&opts, &path, &state);
+            self.print_items(&opts, &path, &mut state);
+            self.print_bottom(&opts, false, &mut state);
+        });
+        let text = String::from_utf8_lossy(&buffer.borrow()).into_owned();
+        text
+    }
+    /// Erases the first frame of the `Menu` from `RunOptions::output`, moving the cursor up over the lines `rendered_height()` counts and clearing them. It must be called right after the frame is printed, like the `render_to_string()` text written to the output, with the cursor still below it. Returns `MenuError::Io` if writing fails.
+    pub fn clear(&self, opts: &RunOptions) -> Result<(), MenuError> {
+        let opts = &opts.for_run();
+        let mut term = opts.output.term();
+        let mut state = State::default();
+        self.fix_hover(opts, &mut state);
+        self.clear_menu(opts, &mut term, false, &state);
+        term.flush().expect("flush");
+        match opts.error() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+    /// Returns the number of terminal lines the first frame of the `Menu` occupies on a terminal `width` columns wide, counting wrapped explanations, the legend and the hint as displayed. Lets a program embedding the `Menu` in a larger layout reserve its space before running it.
+    pub fn rendered_height(&self, opts: &RunOptions, width: u16) -> u16 {
+        with_width(width, || {
+            let mut state = State::default();
+            self.fix_hover(opts, &mut state);
+            self.height(opts, false, &state) as u16
+        })
+    }
+    /// Builds a flat `Menu` of every `Action` of the `Menu` and its `SubMenu`s, named by their path like `Settings/Network/Reset` to tell apart `Action`s of the same name. Explanations are kept and hotkeys are dropped. `LazySubMenu`s are left out, since their `Item`s are built on entry. See `run_command_palette()` for running it.
+    pub fn command_palette(&self) -> Menu {
+        self.palette().0
+    }
+    /// Runs the `command_palette()` searching right away, like `Ctrl+P` of code editors, so any `Action` is a few letters away whatever its depth. Searching matches the whole path. Gives the `Selection` selecting the `Action` in the `Menu` itself would give.
+    /// ```
+    /// use rushterm::{Item, Menu, RunOptions, Script};
+    ///
+    /// let reset = Item::Action {
+    ///     name: "Reset".to_string(),
+    ///     hotkey: None,
+    ///     exp: None,
+    ///     badges: Vec::new(),
+    ///     confirm_phrase: None,
+    ///     global_hotkey: None,
+    ///     url: None,
+    ///     checkable: false,
+    /// };
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![
+    ///         reset.clone(),
+    ///         Item::SubMenu {
+    ///             name: "Network".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             items: vec![reset],
+    ///         },
+    ///     ],
+    ///     esc: true,
+    /// };
+    /// let opts = RunOptions {
+    ///     script: Some(Script::new(["n", "e", "t", "Enter"])),
+    ///     ..RunOptions::default()
+    /// };
+    /// let selection = menu.run_command_palette(&opts).unwrap();
+    /// assert_eq!(selection.indexes, vec![1, 0]);
+    /// ```
+    pub fn run_command_palette(&self, opts: &RunOptions) -> Result<Selection, MenuError> {
+        let (palette, paths) = self.palette();
+        let opts = RunOptions {
+            search: true,
+            ..opts.for_run()
+        };
+        let state = State {
+            filter: Some(String::new()),
+            ..State::default()
+        };
+        let selection = MenuRun::start(vec![palette.level(&opts, state)], false).drive()?;
+        let path = &paths[selection.indexes[0]];
+        let selection = self.select_by_path(path, None).expect("palette action");
+        Ok(self.trace(&opts, selection))
+    }
+    /// Returns the path of every `Action` of the `Menu` and its `SubMenu`s, like `Settings/Network/Reset`, the names `command_palette()` gives them, for a program taking them as arguments to offer to shell completion. `LazySubMenu`s are left out. See `completion_script()` for a script registering them.
+    pub fn completions(&self) -> Vec<String> {
+        let (palette, _) = self.palette();
+        palette.items.iter().map(|f| f.name().to_string()).collect()
+    }
+    /// Returns a script registering `completions()` as the completions of the first argument of `program` in the shell, to be sourced by the shell, like the output of `program completions bash`. Paths are quoted, so names may hold spaces.
+    pub fn completion_script(&self, shell: Shell, program: &str) -> String {
+        let quote = |f: &str| format!("'{}'", f.replace('\'', "'\\''"));
+        let function = program.replace(|f: char| !f.is_ascii_alphanumeric(), "_");
+        match shell {
+            Shell::Bash => {
+                let words: Vec<String> = self.completions().iter().map(|f| quote(f)).collect();
+                format!(
+                    "_{function}() {{\n    local actions=({words}) action\n    COMPREPLY=()\n    [ \"$COMP_CWORD\" -eq 1 ] || return\n    # quotes the chosen path, like spaces in it.\n    compopt -o filenames 2>/dev/null\n    for action in \"${{actions[@]}}\"; do\n        [[ $action == \"${{COMP_WORDS[1]}}\"* ]] && COMPREPLY+=(\"$action\")\n    done\n}}\ncomplete -F _{function} {program}\n",
+                    function = function,
+                    words = words.join(" "),
+                    program = program,
+                )
+            }
+            Shell::Zsh => {
+                // `_describe` takes what follows a colon as a description.
+                let words: Vec<String> = self
+                    .completions()
+                    .iter()
+                    .map(|f| quote(&f.replace(':', "\\:")))
+                    .collect();
+                format!(
+                    "#compdef {program}\nlocal -a actions\nactions=({words})\n_arguments '1:action:{{_describe action actions}}'\n",
+                    program = program,
+                    words = words.join(" "),
+                )
+            }
+        }
+    }
+    /// Returns the `command_palette()` and the index paths of its `Action`s in the `Menu`.
+    fn palette(&self) -> (Menu, Vec<Vec<usize>>) {
+        let mut items = vec![];
+        let mut paths = vec![];
+        self.walk(|path, item| {
+            if let Item::Action {
+                exp,
+                confirm_phrase,
+                ..
+            } = item
+            {
+                let mut names = vec![];
+                let mut level = &self.items;
+                for index in path {
+                    names.push(level[*index].name());
+                    if let Item::SubMenu { items, .. } = &level[*index] {
+                        level = items;
+                    }
+                }
+                items.push(Item::Action {
+                    name: names.join("/"),
+                    hotkey: None,
+                    exp: exp.clone(),
+                    badges: Vec::new(),
+                    confirm_phrase: confirm_phrase.clone(),
+                    global_hotkey: None,
+                    url: None,
+                    checkable: false,
+                });
+                paths.push(path.to_vec());
+            }
+        });
+        let palette = Menu {
+            name: self.name.to_string(),
+            exp: self.exp.clone(),
+            items,
+            esc: self.esc,
+        };
+        (palette, paths)
+    }
+    /// Visits every `Item` of the `Menu` depth first, `SubMenu`s before their `Item`s, with the index path of the `Item`. `LazySubMenu`s are visited, but their `Item`s are not built.
+    pub fn walk<'a>(&'a self, mut visit: impl FnMut(&[usize], &'a Item)) {
+        fn walk_items<'a>(
+            items: &'a [Item],
+            path: &mut Vec<usize>,
+            visit: &mut dyn FnMut(&[usize], &'a Item),
+        ) {
+            for (i, item) in items.iter().enumerate() {
+                path.push(i);
+                visit(path, item);
+                if let Item::SubMenu { items, .. } = item {
+                    walk_items(items, path, visit);
+                }
+                path.pop();
+            }
+        }
+        walk_items(&self.items, &mut vec![], &mut visit);
+    }
+    /// Returns every level of the `Menu` as an indented tree, like the output of `tree`, with the hotkey and the type of each `Item`. Unlike `render_to_string()`, which renders a single frame, `SubMenu`s are expanded, so it can document the whole `Menu` in the help text of a program. `LazySubMenu`s are listed without their `Item`s, since those are built on entry.
+    pub fn tree_string(&self) -> String {
+        let mut tree = format!("{}\n", self.name);
+        self.walk(|path, item| {
+            let mut level = &self.items;
+            for (depth, index) in path.iter().enumerate() {
+                let last = *index + 1 == level.len();
+                let branch = match (depth + 1 == path.len(), last) {
+                    (true, true) => "└── ",
+                    (true, false) => "├── ",
+                    (false, true) => "    ",
+                    (false, false) => "│   ",
+                };
+                tree.push_str(branch);
+                if let Item::SubMenu { items, .. } = &level[*index] {
+                    level = items;
+                }
+            }
+            if let Some(hotkey) = item.hotkey() {
+                tree.push_str(&format!("({}) ", hotkey_text(hotkey)));
+            }
+            let name = item.name().replace('\n', " ");
+            tree.push_str(&format!("{} [{}]\n", name, item.type_name()));
+        });
+        tree
+    }
+    /// Orders the `Item`s of the `Menu` and of its `SubMenu`s by the key `key` extracts, like a priority instead of the name. The sort is stable, so `Item`s of equal keys keep their order. `Selection.indexes` and `RunOptions::default_action` refer to the sorted order. `LazySubMenu`s are left unsorted, since their `Item`s are built on entry.
+    pub fn sort_by_key<K: Ord>(&mut self, mut key: impl FnMut(&Item) -> K) {
+        fn sort_items<K: Ord>(
+            items: &mut Vec<Item>,
+            key: &mut dyn FnMut(&Item) -> K,
+        ) -> Vec<usize> {
+            let mut order: Vec<usize> = (0..items.len()).collect();
+            order.sort_by_key(|i| key(&items[*i]));
+            let mut old: Vec<Option<Item>> = items.drain(..).map(Some).collect();
+            items.extend(order.iter().map(|i| old[*i].take().expect("sorted item")));
+            for item in items.iter_mut() {
+                if let Item::SubMenu { items, .. } = item {
+                    sort_items(items, key);
+                }
+            }
+            order
+        }
+        sort_items(&mut self.items, &mut key);
+    }
+    /// Sets the current values of the `Item`s of the `Menu` and of its `SubMenu`s back to those of the `Item`s at the same index paths in `initial`, like a clone taken before the settings were changed, for a "Reset to defaults" action. `Item`s missing in `initial` or of another kind keep their current values. `LazySubMenu`s are left as is, since their `Item`s are built on entry. `Counter`s start from `initial` on every run anyway.
+    /// ```
+    /// use rushterm::{Item, Menu};
+    ///
+    /// let toggle = |name: &str, current: &str| Item::Bool {
+    ///     name: name.to_string(),
+    ///     hotkey: None,
+    ///     exp: None,
+    ///     badges: Vec::new(),
+    ///     current: Some(current.to_string()),
+    /// };
+    /// let mut menu = Menu {
+    ///     name: "Settings".to_string(),
+    ///     exp: None,
+    ///     items: vec![
+    ///         toggle("Sound", "true"),
+    ///         Item::SubMenu {
+    ///             name: "Display".to_string(),
+    ///             hotkey: None,
+    ///             exp: None,
+    ///             badges: Vec::new(),
+    ///             items: vec![toggle("Dark", "false"), toggle("Compact", "true")],
+    ///         },
+    ///     ],
+    ///     esc: true,
+    /// };
+    /// let initial = menu.clone();
+    /// let currents = |menu: &Menu| {
+    ///     let mut currents = vec![];
+    ///     menu.walk(|_, item| currents.extend(item.current().map(str::to_string)));
+    ///     currents
+    /// };
+    ///
+    /// let flip = |item: &mut Item| {
+    ///     if let Item::Bool { current, .. } = item {
+    ///         *current = current.as_deref().map(|f| (f != "true").to_string());
+    ///     }
+    /// };
+    /// flip(&mut menu.items[0]);
+    /// if let Item::SubMenu { items, .. } = &mut menu.items[1] {
+    ///     items.iter_mut().for_each(flip);
+    /// }
+    /// assert_eq!(currents(&menu), ["false", "true", "false"]);
+    ///
+    /// menu.reset_values(&initial);
+    /// assert_eq!(currents(&menu), ["true", "false", "true"]);
+    /// ```
+    pub fn reset_values(&mut self, initial: &Menu) {
+        fn reset_items(items: &mut [Item], initial: &[Item]) {
+            for (item, initial) in items.iter_mut().zip(initial) {
+                if mem::discriminant(item) != mem::discriminant(initial) {
+                    continue;
+                }
+                if let (Item::SubMenu { items, .. }, Item::SubMenu { items: initial, .. }) =
+                    (&mut *item, initial)
+                {
+                    reset_items(items, initial);
+                } else if let Some(current) = item.current_mut() {
+                    *current = initial.current().map(str::to_string);
+                }
+            }
+        }
+        reset_items(&mut self.items, &initial.items);
+    }
+    /// Checks that the current value of each input `Item` of the `Menu` and its `SubMenu`s is a valid input of its type, like `42` for `I32`, so a value loaded from a config file that the user couldn't type is caught before running. `Bool` and `String` values are displayed as they are, so they aren't checked. Returns `MenuError::InvalidCurrent` for the first invalid one.
+    /// ```
+    /// use rushterm::{Item, Menu, MenuError};
+    ///
+    /// let menu = Menu {
+    ///     name: "Main".to_string(),
+    ///     exp: None,
+    ///     items: vec![Item::I32 {
+    ///         name: "Count".to_string(),
+    ///         hotkey: None,
+    ///         exp: None,
+    ///         badges: Vec::new(),
+    ///         skippable: false,
+    ///         current: Some("abc".to_string()),
+    ///         confirm: false,
+    ///         step: None,
+    ///     }],
+    ///     esc: true,
+    /// };
+    /// let err = MenuError::InvalidCurrent {
+    ///     path: vec![0],
+    ///     value: "abc".to_string(),
+    /// };
+    /// assert_eq!(menu.validate_currents(), Err(err));
+    /// ```
+    pub fn validate_currents(&self) -> Result<(), MenuError> {
+        let mut invalid = None;
+        self.walk(|path, item| {
+            let value = match item.current() {
+                Some(value) if invalid.is_none() => value,
+                _ => return,
+            };
+            let valid = match item {
+                Item::Char {
+                    allowed,
+                    ignore_case,
+                    ..
+                } => value
+                    .parse()
+                    .is_ok_and(|f| allowed_char(allowed.as_deref(), *ignore_case, f).is_some()),
+                Item::F32 { .. } => value.parse::<f32>().is_ok(),
+                Item::F64 {
+                    decimals, round, ..
+                } => value
+                    .parse()
+                    .ok()
+                    .and_then(|f| fit_decimals(f, *decimals, *round))
+                    .is_some(),
+                Item::I32 { .. } => value.parse::<i32>().is_ok(),
+                Item::U32 { .. } => value.parse::<u32>().is_ok(),
+                Item::NumberList { kind, .. } => kind.parse_list(value).is_ok(),
+                Item::ByteSize { .. } => value.parse::<ByteSize>().is_ok(),
+                Item::Color { .. } => value.parse::<Rgb>().is_ok(),
+                _ => true,
+            };
+            if !valid {
+                invalid = Some(MenuError::InvalidCurrent {
+                    path: path.to_vec(),
+                    value: value.to_string(),
+                });
+            }
+        });
+        invalid.map_or(Ok(()), Err)
+    }
+    /// Collects every structural problem of the `Menu` and its `SubMenu`s at once: duplicate hotkeys, hotkeys colliding with index numbers, hotkeys that can't be typed, and empty `SubMenu`s. Useful for auditing a big data-driven `Menu`, e.g. in tests.
+    pub fn validate_all(&self) -> Vec<MenuWarning> {
+        let mut warnings = vec![];
+        let mut warn = |path: &[usize], description: String| {
+            warnings.push(MenuWarning {
+                path: path.to_vec(),
+                description,
+            })
+        };
+        let mut levels = vec![(vec![], self.items.as_slice())];
+        self.walk(|path, item| {
+            if let Item::SubMenu { items, .. } = item {
+                levels.push((path.to_vec(), items.as_slice()));
+            }
+        });
+        for (parent, items) in levels {
+            if items.is_empty() && !parent.is_empty() {
+                warn(&parent, "sub menu has no items".to_string());
+            }
+            for (i, item) in items.iter().enumerate() {
+                let mut path = parent.clone();
+                path.push(i);
+                let hotkey = match item.hotkey() {
+                    Some(hotkey) => hotkey,
+                    None => continue,
+                };
+                if hotkey.to_lowercase().to_string() != hotkey.to_string() {
+                    warn(
+                        &path,
+                        format!(
+                            "hotkey '{}' can't be typed, hotkeys are matched in lowercase",
+                            hotkey
+                        ),
+                    );
+                }
+                if let Some(first) = items[..i].iter().position(|f| f.hotkey() == Some(hotkey)) {
+                    warn(&path, format!("hotkey '{}' is already used by item at index {}, so it is unreachable by hotkey", hotkey, first));
+                }
+                if hotkey
+                    .to_digit(10)
+                    .is_some_and(|digit| (digit as usize) < items.len())
+                {
+                    warn(
+                        &path,
+                        format!(
+                            "hotkey '{}' collides with the index number of an item",
+                            hotkey
+                        ),
+                    );
+                }
+            }
+        }
+        warnings
+    }
+    /// Returns the keys free to assign as hotkeys to `Item`s of the level at the index path, letters first, then digits: those not taken by a hotkey of an `Item` of the level, nor by a global hotkey, nor by the index number of an `Item`. Hotkeys are compared in lowercase, as they are matched. An empty `path` is the `Menu` itself, and each index points to a `SubMenu` of the level pointed by the previous index. Helps builders assign hotkeys to generated `Item`s without collisions.
+    pub fn available_hotkeys(&self, path: &[usize]) -> Result<Vec<char>, MenuError> {
+        let mut items = self.items.clone();
+        for index in path {
+            items = items
+                .get(*index)
+                .and_then(|f| f.sub_items())
+                .ok_or_else(|| MenuError::InvalidPath(path.to_vec()))?;
+        }
+        let globals = self.global_hotkeys();
+        let taken = |chr: char| {
+            items
+                .iter()
+                .filter_map(|f| f.hotkey())
+                .chain(globals.keys().copied())
+                .any(|f| f.to_lowercase().eq(chr.to_lowercase()))
+        };
+        let indexes = items
+            .iter()
+            .filter(|f| !matches!(f, Item::Label { .. }))
+            .count();
+        Ok(('a'..='z')
+            .chain('0'..='9')
+            .filter(|f| !taken(*f))
+            .filter(|f| f.to_digit(10).is_none_or(|f| f as usize >= indexes))
+            .collect())
+    }
+    /// Gives the `Selection` that selecting the `Item` at the index path would return, without any terminal interaction. Each index of `path` points to an `Item` of the `Menu` or of the `SubMenu` pointed by the previous index. Input `Item`s need a `value` of their type, which is ignored for `Action`s. `skippable` ones are skipped without a `value`, as if `RunOptions::skip_sentinel` was typed. Useful for testing the code dispatching selections.
+    pub fn select_by_path(
+        &self,
+        path: &[usize],
+        value: Option<Value>,
+    ) -> Result<Selection, MenuError> {
+        let mut names = vec![self.name.to_string()];
+        let mut lazy_items: Vec<Item>;
+        let mut items = &self.items;
+        for (depth, index) in path.iter().enumerate() {
+            let item = items
+                .get(*index)
+                .ok_or_else(|| MenuError::InvalidPath(path.to_vec()))?;
+            let is_last = depth + 1 == path.len();
+            match item {
+                Item::SubMenu {
+                    name,
+                    items: sub_items,
+                    ..
+                } if !is_last => {
+                    names.push(name.to_string());
+                    items = sub_items;
+                }
+                Item::LazySubMenu { name, provider, .. } if !is_last => {
+                    names.push(name.to_string());
+                    lazy_items = provider.items();
+                    items = &lazy_items;
+                }
+                Item::SubMenu { .. }
+                | Item::LazySubMenu { .. }
+                | Item::Group { .. }
+                | Item::Label { .. } => return Err(MenuError::NotSelectable(path.to_vec())),
+                _ if !is_last => return Err(MenuError::InvalidPath(path.to_vec())),
+                Item::Action { name, .. } => {
+                    names.push(name.to_string());
+                    return Ok(Selection {
+                        name: name.to_string(),
+                        path: names,
+                        indexes: path.to_vec(),
+                        value: None,
+                        len: None,
+                        attempt: None,
+                        skipped: false,
+                        item_path: vec![],
+                    });
+                }
+                Item::Bool { name, .. } => {
+                    names.push(name.to_string());
+                    return match value {
+                        Some(Value::Bool(value)) => Ok(Selection {
+                            name: value.to_string(),
+                            path: names,
+                            indexes: path.to_vec(),
+                            value: Some(Value::Bool(value)),
+                            len: None,
+                            attempt: None,
+                            skipped: false,
+                            item_path: vec![],
+                        }),
+                        Some(_) => Err(MenuError::ValueMismatch(path.to_vec())),
+                        None => Err(MenuError::MissingValue(path.to_vec())),
+                    };
+                }
+                Item::Counter { name, min, max, .. } => {
+                    names.push(name.to_string());
+                    return match value {
+                        Some(Value::I64(value)) if (*min..=*max).contains(&value) => {
+                            Ok(Selection {
+                                name: name.to_string(),
+                                path: names,
+                                indexes: path.to_vec(),
+                                value: Some(Value::I64(value)),
+                                len: Some(value.to_string().len()),
+                                attempt: None,
+                                skipped: false,
+                                item_path: vec![],
+                            })
+                        }
+                        Some(_) => Err(MenuError::ValueMismatch(path.to_vec())),
+                        None => Err(MenuError::MissingValue(path.to_vec())),
+                    };
+                }
+                Item::Json { name, .. } => {
+                    names.push(name.to_string());
+                    let json = match value {
+                        Some(Value::String(value)) => compact_json(&value)
+                            .map_err(|_| MenuError::ValueMismatch(path.to_vec()))?,
+                        Some(_) => return Err(MenuError::ValueMismatch(path.to_vec())),
+                        None if item.skippable() => return Ok(skipped_selection(names, path)),
+                        None => return Err(MenuError::MissingValue(path.to_vec())),
+                    };
+                    return Ok(Selection {
+                        name: name.to_string(),
+                        path: names,
+                        indexes: path.to_vec(),
+                        len: Some(json.len()),
+                        value: Some(Value::String(json)),
+                        attempt: Some(1),
+                        skipped: false,
+                        item_path: vec![],
+                    });
+                }
+                Item::Char { name, .. }
+                | Item::String { name, .. }
+                | Item::Secret { name, .. }
+                | Item::F32 { name, .. }
+                | Item::F64 { name, .. }
+                | Item::I32 { name, .. }
+                | Item::U32 { name, .. }
+                | Item::NumberList { name, .. }
+                | Item::ByteSize { name, .. }
+                | Item::Color { name, .. } => {
+                    names.push(name.to_string());
+                    let value = match (item, value) {
+                        (
+                            Item::F64 {
+                                decimals, round, ..
+                            },
+                            Some(Value::F64(value)),
+                        ) => Some(Value::F64(
+                            fit_decimals(value, *decimals, *round)
+                                .ok_or_else(|| MenuError::TooManyDecimals(path.to_vec()))?,
+                        )),
+                        (_, value) => value,
+                    };
+                    let len = match (item, &value) {
+                        (
+                            Item::Char {
+                                allowed,
+                                ignore_case,
+                                ..
+                            },
+                            Some(Value::Char(value)),
+                        ) if allowed_char(allowed.as_deref(), *ignore_case, *value).is_some() => 1,
+                        (Item::String { .. }, Some(Value::String(value))) => value.len(),
+                        (Item::Secret { .. }, Some(Value::Secret(value))) => value.0.len(),
+                        (Item::F32 { .. }, Some(Value::F32(value))) => value.to_string().len(),
+                        (Item::F64 { .. }, Some(Value::F64(value))) => value.to_string().len(),
+                        (Item::I32 { .. }, Some(Value::I32(value))) => value.to_string().len(),
+                        (Item::U32 { .. }, Some(Value::U32(value))) => value.to_string().len(),
+                        (Item::ByteSize { .. }, Some(Value::Bytes(value))) => {
+                            value.to_string().len()
+                        }
+                        (Item::Color { .. }, Some(Value::Color(..))) => 7,
+                        (Item::NumberList { kind, .. }, Some(Value::List(values)))
+                            if values.iter().all(|f| kind.matches(f)) =>
+                        {
+                            values.len()
+                        }
+                        (_, None) if item.skippable() => return Ok(skipped_selection(names, path)),
+                        (_, None) => return Err(MenuError::MissingValue(path.to_vec())),
+                        _ => return Err(MenuError::ValueMismatch(path.to_vec())),
+                    };
+                    return Ok(Selection {
+                        name: name.to_string(),
+                        path: names,
+                        indexes: path.to_vec(),
+                        value,
+                        len: Some(len),
+                        attempt: Some(1),
+                        skipped: false,
+                        item_path: vec![],
+                    });
+                }
+            }
+        }
+        Err(MenuError::InvalidPath(path.to_vec()))
+    }
+    /// Returns the level running the `Menu` itself with the state, finding the global hotkeys of its `Item`s.
+    fn level(&self, opts: &RunOptions, mut state: State) -> Level<'_> {
+        state.globals = self.global_hotkeys();
+        Level {
+            menu: Cow::Borrowed(self),
+            opts: opts.clone(),
+            path: vec![self.name.to_string()],
+            state,
+            index: None,
+            choice: false,
+        }
+    }
+    /// Returns the level the `Item` at the index enters with the `items`, from the level of this `Menu` at the path with the state.
+    fn sub_level(
+        &self,
+        opts: RunOptions,
+        path: &[String],
+        parent: &State,
+        index: usize,
+        items: Vec<Item>,
+    ) -> Box<Level<'static>> {
+        let item = &self.items[index];
+        Box::new(Level {
+            menu: Cow::Owned(self.sub_menu(item.name(), item.exp(), items)),
+            opts,
+            path: [path, &[item.name().to_string()]].concat(),
+            state: State {
+                hide_exp: parent.hide_exp.clone(),
+                revealed: parent.revealed.clone(),
+                globals: parent.globals.clone(),
+                jump: parent.jump.clone(),
+                ui_state: parent.ui_state.clone(),
+                ..State::default()
+            },
+            index: Some(index),
+            choice: matches!(item, Item::Bool { .. }),
+        })
+    }
+    /// Prints `RunOptions::splash`, if set, returning the number of lines it takes.
+    fn splash(&self, opts: &RunOptions) -> Option<u16> {
+        let splash = opts.splash.as_ref()?;
+        let width = self.term_width(opts);
+        let mut lines = 1;
+        for line in splash.split('\n') {
+            outln!(opts, "{}", line);
+            lines += strip_styles(line).chars().count().div_ceil(width).max(1);
+        }
+        outln!(
+            opts,
+            "{}",
+            opts.locale.press_any_key.as_str().with(opts.theme.muted)
+        );
+        opts.output.term().flush().expect("flush");
+        Some(lines as u16)
+    }
+    /// Returns the index paths of `Action`s by their `global_hotkey`s.
+    fn global_hotkeys(&self) -> Rc<HashMap<char, Vec<usize>>> {
+        let mut globals = HashMap::new();
+        self.walk(|path, item| {
+            if let Item::Action {
+                global_hotkey: Some(chr),
+                ..
+            } = item
+            {
+                globals.insert(*chr, path.to_vec());
+            }
+        });
+        Rc::new(globals)
+    }
+    /// Selects the `Action` of a global hotkey that a level of the `Menu` gave up as `Unwind::Global`.
+    fn resolve_global(&self, res: LevelOutcome) -> LevelOutcome {
+        match res {
+            Err(Unwind::Global(path)) => Ok(self.select_by_path(&path, None)?),
+            res => res,
+        }
+    }
+    /// Remembers the hover a key moved away from, for `RunOptions::undo_key`. Only the latest `MAX_UNDO` moves are kept.
+    fn record_move(
+        &self,
+        opts: &RunOptions,
+        hover: usize,
+        key: &Option<String>,
+        step: &Step,
+        state: &mut State,
+    ) {
+        if opts.undo_key.is_none()
+            || state.hover == hover
+            || *key == Some("Undo".to_string())
+            || !matches!(step, Step::Wait)
+        {
+            return;
+        }
+        if state.moves.len() == MAX_UNDO {
+            state.moves.remove(0);
+        }
+        state.moves.push(hover);
+    }
+    /// Pauses on the cleared frame between levels for `RunOptions::animation`, at most `MAX_ANIMATION`.
+    fn transition(&self, opts: &RunOptions) {
+        if let Some(duration) = opts.animation {
+            opts.output.term().flush().expect("flush");
+            thread::sleep(duration.min(MAX_ANIMATION));
+        }
+    }
+    /// Whether `RunOptions::stay` keeps the `Menu` open after the result. Only selections made in this `Menu`, not in its `SubMenu`s, are asked about, with the index path from the root `Menu` through the `entered` indexes of the `SubMenu`s down to this one.
+    fn stays(&self, opts: &RunOptions, res: &mut LevelOutcome, entered: &[usize]) -> bool {
+        match (res, &opts.stay) {
+            (Ok(selection), Some(_)) if opts.dry_run != DryRun::Off => {
+                if selection.indexes.len() == 1 {
+                    report_dry_run(opts, selection);
+                }
+                false
+            }
+            (Ok(selection), Some(stay)) if selection.indexes.len() == 1 => {
+                selection.indexes.splice(0..0, entered.iter().copied());
+                let stays = stay.should_stay(selection);
+                selection.indexes.drain(..entered.len());
+                stays
+            }
+            _ => false,
+        }
+    }
+    fn print_top(&self, opts: &RunOptions, path: &[String]) {
+        opts.start_line();
+        let mut title = self.title(opts, path);
+        let width = self.term_width(opts);
+        if opts.truncate_title && title.chars().count() > width {
+            let ellipsis = opts.glyph("…", "...");
+            let kept = width.saturating_sub(ellipsis.chars().count());
+            title = title.chars().take(kept).collect::<String>() + ellipsis;
+        }
+        let split = self.dirs(opts, path).chars().count();
+        let dirs: String = title.chars().take(split).collect();
+        let exp: String = title.chars().skip(split).collect();
+        outln!(opts, "{}{}", dirs, exp.with(opts.theme.muted));
+    }
+    /// Prints the top line, or the top border with the title when `border` is enabled, followed by the explanation paragraph if `exp_below` is enabled.
+    fn print_header(&self, opts: &RunOptions, path: &[String], state: &State) {
+        opts.start_line();
+        if !opts.border {
+            self.print_top(opts, path);
+            for line in self.exp_lines(opts) {
+                outln!(opts, "{}", line.with(opts.theme.muted));
+            }
+            return;
+        }
+        let inner = self.inner_width(opts, path, state);
+        let title: String = self
+            .title(opts, path)
+            .chars()
+            .take(inner.saturating_sub(1))
+            .collect();
+        let rest = inner.saturating_sub(title.chars().count() + 1);
+        out!(opts, "{}", opts.glyph("┌─ ", "+- ").with(opts.theme.muted));
+        let dirs = self.dirs(opts, path);
+        if title.chars().count() > dirs.chars().count() {
+            let exp: String = title.chars().skip(dirs.chars().count()).collect();
+            out!(opts, "{}{}", dirs, exp.with(opts.theme.muted));
+        } else {
+            out!(opts, "{}", title);
+        }
+        outln!(
+            opts,
+            "{}",
+            (" ".to_string() + &opts.glyph("─", "-").repeat(rest) + opts.glyph("┐", "+"))
+                .with(opts.theme.muted)
+        );
+        for line in self.exp_lines(opts) {
+            out!(
+                opts,
+                "{}{}",
+                opts.glyph("│ ", "| ").with(opts.theme.muted),
+                line.as_str().with(opts.theme.muted)
+            );
+            out!(
+                opts,
+                "{}",
+                " ".repeat(inner.saturating_sub(line.chars().count()))
+            );
+            outln!(opts, "{}", opts.glyph(" │", " |").with(opts.theme.muted));
+        }
+    }
+    /// Returns the path as displayed in the top line, like `Main/Settings/`, eliding middle segments by `RunOptions::path_ellipsis` if it's too wide.
+    fn dirs(&self, opts: &RunOptions, path: &[String]) -> String {
+        let join = |segments: &[&str]| segments.iter().map(|f| f.to_string() + "/").collect();
+        let mut segments: Vec<&str> = path.iter().map(String::as_str).collect();
+        let ellipsis = match &opts.path_ellipsis {
+            Some(ellipsis) if segments.len() > 2 => ellipsis.as_str(),
+            _ => return join(&segments),
+        };
+        let border = if opts.border { 4 } else { 0 };
+        let width = self.term_width(opts).saturating_sub(border);
+        let mut dirs: String = join(&segments);
+        while dirs.chars().count() > width && segments.len() > 2 {
+            segments.remove(1);
+            let mut elided = segments.clone();
+            elided.insert(1, ellipsis);
+            dirs = join(&elided);
+        }
+        dirs
+    }
+    /// Returns the text of the top line.
+    fn title(&self, opts: &RunOptions, path: &[String]) -> String {
+        let mut title = self.dirs(opts, path);
+        if let Some(exp) = self.exp.as_ref().filter(|_| !opts.exp_below) {
+            title = title + " " + exp;
+        }
+        title
+    }
+    /// Returns the width inside the border, fitting the title and the widest row into the terminal.
+    fn inner_width(&self, opts: &RunOptions, path: &[String], state: &State) -> usize {
+        let rows = match opts.layout {
+            Layout::List | Layout::Stacked => (0..self.items.len())
+                .map(|i| {
+                    let (hotkey, name, offset, exp) = self.label(opts, state, i);
+                    let exp = exp.filter(|_| !state.hide_exp.get());
+                    let cell = self
+                        .cell_text(opts, state, i, hotkey, offset, &name)
+                        .chars()
+                        .count();
+                    match (opts.layout, exp) {
+                        (Layout::Stacked, Some(exp)) => {
+                            cell.max(self.exp_indent(opts, i, offset) + exp.chars().count())
+                        }
+                        (_, exp) => cell + exp.map_or(0, |f| f.chars().count() + 1),
+                    }
+                })
+                .max()
+                .unwrap_or(0),
+            Layout::Grid { columns } => {
+                self.cell_width(opts, state) * columns.min(self.items.len())
+            }
+        };
+        let title = self.title(opts, path).chars().count() + 2;
+        let exp = self
+            .exp_lines(opts)
+            .iter()
+            .map(|f| f.chars().count())
+            .max()
+            .unwrap_or(0);
+        rows.max(title)
+            .max(exp)
+            .min(self.term_width(opts).saturating_sub(4))
+    }
+    /// Returns the lines of the explanation paragraph under the top line, wrapping the explanation of the `Menu` to the terminal width, or inside the border. Empty unless `exp_below` is enabled.
+    fn exp_lines(&self, opts: &RunOptions) -> Vec<String> {
+        match &self.exp {
+            Some(exp) if opts.exp_below => {
+                let border = if opts.border { 4 } else { 0 };
+                wrap(exp, self.term_width(opts).saturating_sub(border).max(1))
+            }
+            _ => vec![],
+        }
+    }
+    fn print_items(&self, opts: &RunOptions, path: &[String], state: &mut State) {
+        self.restore_groups(opts, path, state);
+        match opts.layout {
+            Layout::List | Layout::Stacked => {
+                let inner = self.inner_width(opts, path, state);
+                for i in self.scroll(opts, state) {
+                    if let Item::Label { text } = &self.items[i] {
+                        self.print_text(opts, inner, text);
+                        continue;
+                    }
+                    let (hotkey, name, offset, exp) = self.label(opts, state, i);
+                    let exp = exp.filter(|_| !state.hide_exp.get());
+                    let mut exp = exp.map(|f| self.shown_exp(opts, state, i, f));
+                    let under = exp.take_if(|_| opts.layout == Layout::Stacked);
+                    if opts.border {
+                        out!(opts, "{}", opts.glyph("│ ", "| ").with(opts.theme.muted));
+                        let used = self
+                            .cell_text(opts, state, i, hotkey, offset, &name)
+                            .chars()
+                            .count();
+                        let room = inner.saturating_sub(used + 1);
+                        let ellipsis = opts.glyph("…", "...");
+                        let exp = exp.as_ref().filter(|_| room > ellipsis.len()).map(|f| {
+                            if f.chars().count() > room {
+                                let kept = room - ellipsis.chars().count();
+                                f.chars().take(kept).collect::<String>() + ellipsis
+                            } else {
+                                f.to_string()
+                            }
+                        });
+                        self.print_marker(opts, &i, &state.hover);
+                        self.print_hotkey(opts, &i, hotkey);
+                        self.print_name_exp(opts, &i, state, offset, &name, exp.as_deref());
+                        let used = used + exp.map_or(0, |f| f.chars().count() + 1);
+                        out!(opts, "{}", " ".repeat(inner.saturating_sub(used)));
+                        outln!(opts, "{}", opts.glyph(" │", " |").with(opts.theme.muted));
+                    } else {
+                        self.print_marker(opts, &i, &state.hover);
+                        self.print_hotkey(opts, &i, hotkey);
+                        self.print_name_exp(opts, &i, state, offset, &name, exp.as_deref());
+                        outln!(opts);
+                    }
+                    if let Some(under) = under {
+                        let indent = self.exp_indent(opts, i, offset);
+                        self.print_under(opts, inner, indent, &under);
+                    }
+                }
+            }
+            Layout::Grid { .. } => self.print_grid(opts, path, state),
+        }
+        if opts.border {
+            let inner = self.inner_width(opts, path, state);
+            outln!(
+                opts,
+                "{}",
+                (opts.glyph("└", "+").to_string()
+                    + &opts.glyph("─", "-").repeat(inner + 2)
+                    + opts.glyph("┘", "+"))
+                .with(opts.theme.muted)
+            );
+        }
+    }
+    /// Prints the explanation of an `Item` on its own row after the indent, for `Layout::Stacked`. It's cut to fit inside the border.
+    fn print_under(&self, opts: &RunOptions, inner: usize, indent: usize, exp: &str) {
+        if opts.border {
+            let room = inner.saturating_sub(indent);
+            let exp = if exp.chars().count() > room {
+                let ellipsis = opts.glyph("…", "...");
+                let kept = room.saturating_sub(ellipsis.chars().count());
+                exp.chars().take(kept).collect::<String>() + ellipsis
+            } else {
+                exp.to_string()
+            };
+            out!(opts, "{}", opts.glyph("│ ", "| ").with(opts.theme.muted));
+            out!(
+                opts,
+                "{}{}",
+                " ".repeat(indent),
+                exp.as_str().with(opts.theme.muted)
+            );
+            let used = indent + exp.chars().count();
+            out!(opts, "{}", " ".repeat(inner.saturating_sub(used)));
+            outln!(opts, "{}", opts.glyph(" │", " |").with(opts.theme.muted));
+        } else {
+            outln!(opts, "{}{}", " ".repeat(indent), exp.with(opts.theme.muted));
+        }
+    }
+    /// Returns the number of columns before the name of the `Item` at the index, where `Layout::Stacked` indents its explanation.
+    fn exp_indent(&self, opts: &RunOptions, index: usize, offset: bool) -> usize {
+        let marker = opts.hover_marker.as_ref().map_or(0, |f| f.chars().count());
+        let index = if opts.hide_indexes {
+            0
+        } else {
+            self.index_text(opts, index).chars().count()
+        };
+        let hotkey = match opts.hotkey_position {
+            HotkeyPosition::Before => 3,
+            HotkeyPosition::After => 0,
+        };
+        let space = if offset { 1 } else { 2 };
+        marker + index + hotkey + space
+    }
+    /// Prints the text of a `Label`, a row for each line, after as many spaces as `RunOptions::hover_marker` takes.
+    fn print_text(&self, opts: &RunOptions, inner: usize, text: &str) {
+        let marker = opts.hover_marker.as_ref().map_or(0, |f| f.chars().count());
+        for line in label_lines(opts, text) {
+            let line = " ".repeat(marker) + &line;
+            if opts.border {
+                out!(opts, "{}", opts.glyph("│ ", "| ").with(opts.theme.muted));
+                out!(opts, "{}", line.as_str().with(opts.theme.muted));
+                out!(
+                    opts,
+                    "{}",
+                    " ".repeat(inner.saturating_sub(line.chars().count()))
+                );
+                outln!(opts, "{}", opts.glyph(" │", " |").with(opts.theme.muted));
+            } else {
+                outln!(opts, "{}", line.with(opts.theme.muted));
+            }
+        }
+    }
+    fn print_grid(&self, opts: &RunOptions, path: &[String], state: &mut State) {
+        let (rows, columns) = self.grid_shape(opts, state);
+        let width = self.cell_width(opts, state);
+        let visible = self.visible(opts, state);
+        let inner = self.inner_width(opts, path, state);
+        for row in 0..rows {
+            let mut used = 0;
+            if opts.border {
+                out!(opts, "{}", opts.glyph("│ ", "| ").with(opts.theme.muted));
+            }
+            for column in 0..columns {
+                if let Some(&i) = visible.get(column * rows + row) {
+                    let (hotkey, name, offset, _) = self.label(opts, state, i);
+                    if let Item::Label { .. } = self.items[i] {
+                        let text = self.cell_text(opts, state, i, hotkey, offset, &name);
+                        out!(opts, "{}", text.as_str().with(opts.theme.muted));
+                        let text = text.chars().count();
+                        out!(opts, "{}", " ".repeat(width.saturating_sub(text)));
+                        used += width.max(text);
+                        continue;
+                    }
+                    self.print_marker(opts, &i, &state.hover);
+                    self.print_hotkey(opts, &i, hotkey);
+                    let space = if offset { " " } else { "  " };
+                    out!(opts, "{}", space);
+                    self.print_label_name(opts, &i, state, &name);
+                    self.print_hotkey_after(opts, state, i, offset, &name);
+                    let text = self
+                        .cell_text(opts, state, i, hotkey, offset, &name)
+                        .chars()
+                        .count();
+                    out!(opts, "{}", " ".repeat(width.saturating_sub(text)));
+                    used += width.max(text);
+                }
+            }
+            if opts.border {
+                out!(opts, "{}", " ".repeat(inner.saturating_sub(used)));
+                out!(opts, "{}", opts.glyph(" │", " |").with(opts.theme.muted));
+            }
+            outln!(opts);
+        }
+    }
+    fn label(
+        &self,
+        opts: &RunOptions,
+        state: &State,
+        index: usize,
+    ) -> (Option<char>, String, bool, Option<&str>) {
+        let item = &self.items[index];
+        let checked = self.check_mark(state, index);
+        let counter = self.counter(state, index);
+        let name = item.name();
+        let text = match item {
+            Item::Action { .. } => match checked {
+                Some(true) => format!("[x] {}", name),
+                Some(false) => format!("[ ] {}", name),
+                None => name.to_string(),
+            },
+            Item::SubMenu { items, .. } if opts.sub_counts => {
+                format!("+{} ({})", name, items.len())
+            }
+            Item::LazySubMenu { .. } if opts.sub_counts => {
+                format!("+{} ({})", name, opts.glyph("…", "..."))
+            }
+            Item::SubMenu { .. } | Item::LazySubMenu { .. } => format!("+{}", name),
+            Item::Bool { .. } => format!("+{}={}", name, item.current().unwrap_or("")),
+            Item::Counter { .. } => format!("{}=<{}>", name, counter.unwrap_or_default()),
+            Item::Group { .. } if self.is_collapsed(state, index) => {
+                format!("{} {}", opts.glyph("▸", ">"), name)
+            }
+            Item::Group { .. } => format!("{} {}", opts.glyph("▾", "v"), name),
+            Item::Label { text } => text.to_string(),
+            _ => format!("{}={}", name, item.current().unwrap_or("")),
+        };
+        let text = text + &badge_text(item);
+        let offset = matches!(
+            item,
+            Item::SubMenu { .. } | Item::LazySubMenu { .. } | Item::Bool { .. }
+        );
+        (self.hotkey_at(opts, state, index), text, offset, item.exp())
+    }
+    fn cell_text(
+        &self,
+        opts: &RunOptions,
+        state: &State,
+        index: usize,
+        hotkey: Option<char>,
+        offset: bool,
+        name: &str,
+    ) -> String {
+        let hotkey = match hotkey {
+            Some(chr) => format!("({})", hotkey_text(chr)),
+            None => "   ".to_string(),
+        };
+        let space = if offset { " " } else { "  " };
+        let marker = match &opts.hover_marker {
+            Some(marker) => " ".repeat(marker.chars().count()),
+            None => String::new(),
+        };
+        if let Some(Item::Label { .. }) = self.items.get(index) {
+            let widest = label_lines(opts, name)
+                .into_iter()
+                .max_by_key(|f| f.chars().count());
+            return marker + &widest.unwrap_or_default();
+        }
+        let label = match opts.hotkey_position {
+            HotkeyPosition::Before => format!("{}{}{}", hotkey, space, name),
+            HotkeyPosition::After => {
+                let column = self.name_column(opts, state);
+                format!("{:<2$} {}", space.to_string() + name, hotkey, column)
+            }
+        };
+        if opts.hide_indexes {
+            format!("{}{}", marker, label)
+        } else {
+            format!("{}{}{}", marker, self.index_text(opts, index), label)
+        }
+    }
+    /// Returns the width of the widest name part of the labels, with the space before it, which hotkeys are aligned after with `HotkeyPosition::After`.
+    fn name_column(&self, opts: &RunOptions, state: &State) -> usize {
+        (0..self.items.len())
+            .filter(|f| !matches!(self.items[*f], Item::Label { .. }))
+            .map(|i| {
+                let (_, name, offset, _) = self.label(opts, state, i);
+                name.chars().count() + if offset { 1 } else { 2 }
+            })
+            .max()
+            .unwrap_or(0)
+    }
+    /// Returns the explanation of the `Item` at the index as displayed: masked if `RunOptions::mask_exp` masks it and it's not revealed.
+    fn shown_exp(&self, opts: &RunOptions, state: &State, index: usize, exp: &str) -> String {
+        let masked = opts
+            .mask_exp
+            .as_ref()
+            .is_some_and(|f| f.should_mask(&self.items[index]));
+        if masked && !state.revealed.get() {
+            mask_middle(exp)
+        } else {
+            exp.to_string()
+        }
+    }
+    /// Whether the `Item` at the index is checked, or `None` if it can't be checked.
+    fn check_mark(&self, state: &State, index: usize) -> Option<bool> {
+        match (&state.checked, self.items.get(index)) {
+            (
+                Some(checked),
+                Some(Item::Action {
+                    checkable: true, ..
+                }),
+            ) => Some(checked.borrow().indexes.contains(&index)),
+            _ => None,
+        }
+    }
+    /// Value of the `Counter` at the index, or `None` if it isn't a `Counter`.
+    fn counter(&self, state: &State, index: usize) -> Option<i64> {
+        match self.items.get(index) {
+            Some(Item::Counter {
+                min, max, initial, ..
+            }) => Some(
+                state
+                    .counters
+                    .get(&index)
+                    .copied()
+                    .unwrap_or_else(|| (*initial).clamp(*min, (*max).max(*min))),
+            ),
+            _ => None,
+        }
+    }
+    /// Collapses and expands the `Group`s of the level as `RunOptions::load_ui_state` gives them, unless toggled since. The state is loaded once per run.
+    fn restore_groups(&self, opts: &RunOptions, path: &[String], state: &mut State) {
+        let Some(loader) = &opts.load_ui_state else {
+            return;
+        };
+        let ui_state = state
+            .ui_state
+            .get_or_insert_with(|| Rc::new(RefCell::new(loader.load())))
+            .clone();
+        for (i, item) in self.items.iter().enumerate() {
+            if let Item::Group { name, .. } = item {
+                if let Some(collapsed) = ui_state.borrow().collapsed(&group_key(path, name)) {
+                    state.collapsed.entry(i).or_insert(collapsed);
+                }
+            }
+        }
+    }
+    /// Records the toggled `Group` in the state loaded by `RunOptions::load_ui_state`, or else in an empty one, and calls `RunOptions::save_ui_state` with it.
+    fn save_group(
+        &self,
+        opts: &RunOptions,
+        path: &[String],
+        state: &mut State,
+        name: &str,
+        collapsed: bool,
+    ) {
+        let Some(saver) = &opts.save_ui_state else {
+            return;
+        };
+        let ui_state = state.ui_state.get_or_insert_with(|| {
+            let loaded = opts.load_ui_state.as_ref().map(UiStateLoader::load);
+            Rc::new(RefCell::new(loaded.unwrap_or_default()))
+        });
+        ui_state
+            .borrow_mut()
+            .set_collapsed(group_key(path, name), collapsed);
+        saver.save(&ui_state.borrow());
+    }
+    /// Whether the `Group` at the index is collapsed, as toggled or else as set on it.
+    fn is_collapsed(&self, state: &State, index: usize) -> bool {
+        match self.items.get(index) {
+            Some(Item::Group { collapsed, .. }) => {
+                state.collapsed.get(&index).copied().unwrap_or(*collapsed)
+            }
+            _ => false,
+        }
+    }
+    /// Returns indexes of the displayed `Item`s: those matching the search while searching, except `Label`s, or else those not under a collapsed `Group`.
+    fn visible(&self, opts: &RunOptions, state: &State) -> Vec<usize> {
+        let filter = state.filter.as_ref().map(|f| f.to_lowercase());
+        if let (Some(filter), SearchMode::Fuzzy) = (&filter, opts.search_mode) {
+            let mut scored: Vec<(i64, usize)> = (0..self.items.len())
+                .filter(|f| !matches!(self.items[*f], Item::Label { .. }))
+                .filter_map(|f| Some((fuzzy_match(self.items[f].name(), filter)?.0, f)))
+                .collect();
+            scored.sort_by_key(|f| std::cmp::Reverse(f.0));
+            return scored.into_iter().map(|(_, f)| f).collect();
+        }
+        let mut collapsed = false;
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(i, item)| match &filter {
+                Some(_) if matches!(item, Item::Label { .. }) => false,
+                Some(filter) => item.name().to_lowercase().contains(filter),
+                None => {
+                    if let Item::Group { .. } = item {
+                        collapsed = self.is_collapsed(state, *i);
+                        true
+                    } else {
+                        !collapsed
+                    }
+                }
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+    /// Returns indexes of the displayed `Item`s hover can move to, leaving out `Label`s.
+    fn navigable(&self, opts: &RunOptions, state: &State) -> Vec<usize> {
+        self.visible(opts, state)
+            .into_iter()
+            .filter(|f| !matches!(self.items[*f], Item::Label { .. }))
+            .collect()
+    }
+    /// Returns indexes of the displayed `Item`s within the rows of `RunOptions::max_rows`, scrolling to keep hover `RunOptions::scroll_margin` rows away from the edges when possible.
+    fn scroll(&self, opts: &RunOptions, state: &mut State) -> Vec<usize> {
+        let visible = self.visible(opts, state);
+        let rows = match opts.max_rows() {
+            Some(rows) if rows > 0 && rows < visible.len() => rows,
+            _ => return visible,
+        };
+        let margin = opts.scroll_margin.min((rows - 1) / 2);
+        let position = visible.iter().position(|f| *f == state.hover).unwrap_or(0);
+        let mut top = state.scroll;
+        if position < top + margin {
+            top = position.saturating_sub(margin);
+        }
+        if position + margin >= top + rows {
+            top = position + margin + 1 - rows;
+        }
+        state.scroll = top.min(visible.len() - rows);
+        self.shown(opts, state)
+    }
+    /// Returns indexes of the displayed `Item`s within the rows of `RunOptions::max_rows`, from the row `scroll()` last scrolled to.
+    fn shown(&self, opts: &RunOptions, state: &State) -> Vec<usize> {
+        let visible = self.visible(opts, state);
+        match opts.max_rows() {
+            Some(rows) if rows > 0 && rows < visible.len() => {
+                let top = state.scroll.min(visible.len() - rows);
+                visible[top..top + rows].to_vec()
+            }
+            _ => visible,
+        }
+    }
+    /// Returns the number of rows the shown `Label`s take beyond one each in `Layout::List`, and the shown explanations take in `Layout::Stacked`.
+    fn label_rows(&self, opts: &RunOptions, state: &State) -> usize {
+        if let Layout::Grid { .. } = opts.layout {
+            return 0;
+        }
+        let stacked = opts.layout == Layout::Stacked && !state.hide_exp.get();
+        self.shown(opts, state)
+            .into_iter()
+            .map(|f| match &self.items[f] {
+                Item::Label { text } => text.split('\n').count() - 1,
+                item if stacked && item.exp().is_some() => 1,
+                _ => 0,
+            })
+            .sum()
+    }
+    /// Moves hover to the best match of the search text with `SearchMode::Fuzzy`, as the order of the matches changes while typing.
+    fn hover_best(&self, opts: &RunOptions, state: &mut State) {
+        if opts.search_mode == SearchMode::Fuzzy {
+            if let Some(best) = self.navigable(opts, state).first() {
+                state.hover = *best;
+            }
+        }
+    }
+    /// Moves hover to a displayed `Item` other than a `Label` if the hovered one is not one: to the last one if hover is past the end of `Item`s, which may shrink between renders, or else to the first one.
+    fn fix_hover(&self, opts: &RunOptions, state: &mut State) {
+        if state.hover == UNFOCUSED {
+            return;
+        }
+        let visible = self.navigable(opts, state);
+        if !visible.contains(&state.hover) {
+            let fixed = if state.hover >= self.items.len() {
+                visible.last()
+            } else {
+                visible.first()
+            };
+            state.hover = fixed.copied().unwrap_or(0);
+        }
+    }
+    fn cell_width(&self, opts: &RunOptions, state: &State) -> usize {
+        let widest = (0..self.items.len())
+            .map(|i| {
+                let (hotkey, name, offset, _) = self.label(opts, state, i);
+                self.cell_text(opts, state, i, hotkey, offset, &name)
+                    .chars()
+                    .count()
+            })
+            .max()
+            .unwrap_or(0);
+        widest + 2
+    }
+    /// Returns number of rows and columns displayed `Item`s are spread over.
+    fn grid_shape(&self, opts: &RunOptions, state: &State) -> (usize, usize) {
+        let len = self.visible(opts, state).len();
+        match opts.layout {
+            Layout::List | Layout::Stacked => (opts.max_rows().map_or(len, |f| len.min(f)), 1),
+            Layout::Grid { columns } => {
+                let fit = (self.term_width(opts) / self.cell_width(opts, state)).max(1);
+                let columns = columns.min(fit).min(len).max(1);
+                let rows = len.div_ceil(columns);
+                (rows, columns)
+            }
+        }
+    }
+    fn print_bottom(&self, opts: &RunOptions, is_sub: bool, state: &mut State) {
+        for line in self.detail_lines(opts, state) {
+            outln!(opts, "{}", line.with(opts.theme.muted));
+        }
+        for line in self.legend_lines(opts, is_sub, state) {
+            for (n, (keys, text)) in line.iter().enumerate() {
+                if n > 0 {
+                    out!(opts, "{}", ", ".with(opts.theme.muted));
+                }
+                for key in keys {
+                    out!(
+                        opts,
+                        "{}{}{}",
+                        "(".with(opts.theme.muted),
+                        key.as_str().with(opts.theme.hotkey),
+                        ")".with(opts.theme.muted)
+                    );
+                }
+                out!(opts, " {}", text);
+            }
+            outln!(opts);
+        }
+        if let Some(preview) = self.preview(opts, state) {
+            outln!(opts, "{}", preview.with(opts.theme.muted));
+        }
+        if let Some(position) = self.position(opts, state) {
+            outln!(opts, "{}", position.with(opts.theme.muted));
+        }
+        if let Some(filter) = &state.filter {
+            outln!(
+                opts,
+                "{}{}",
+                opts.locale.search.as_str().with(opts.theme.muted),
+                filter.as_str().with(opts.theme.hover)
+            );
+        } else if let Some(typed) = &state.typed_path {
+            outln!(
+                opts,
+                "{}{}",
+                opts.locale.go_to.as_str().with(opts.theme.muted),
+                typed.as_str().with(opts.theme.hover)
+            );
+        } else if let Some(notice) = &state.notice {
+            outln!(opts, "{}", notice.as_str().with(opts.theme.error));
+        } else {
+            outln!(opts, "{}", self.hint(opts).with(opts.theme.muted));
+        }
+    }
+    /// Returns the names of the first `Item`s of the hovered `SubMenu`, like `Wi-Fi, Ethernet, …`, cut to the terminal width, if `RunOptions::sub_preview` is set.
+    fn preview(&self, opts: &RunOptions, state: &State) -> Option<String> {
+        let count = opts.sub_preview?;
+        let items = match self.items.get(state.hover) {
+            Some(Item::SubMenu { items, .. }) if !items.is_empty() => items,
+            _ => return None,
+        };
+        let mut names: Vec<&str> = items.iter().take(count).map(Item::name).collect();
+        if items.len() > count {
+            names.push(opts.glyph("…", "..."));
+        }
+        Some(
+            names
+                .join(", ")
+                .chars()
+                .take(self.term_width(opts))
+                .collect(),
+        )
+    }
+    /// Returns the position of hover among the displayed `Item`s, like `3/12`, if `RunOptions::position` is set.
+    fn position(&self, opts: &RunOptions, state: &State) -> Option<String> {
+        if !opts.position {
+            return None;
+        }
+        let navigable = self.navigable(opts, state);
+        let hover = navigable
+            .iter()
+            .position(|f| *f == state.hover)
+            .map_or(0, |f| f + 1);
+        Some(format!("{}/{}", hover, navigable.len()))
+    }
+    /// Returns the lines of the detail region, wrapping the explanation of the hovered `Item` to the terminal width.
+    fn detail_lines(&self, opts: &RunOptions, state: &State) -> Vec<String> {
+        let height = match opts.detail_height {
+            Some(height) => height,
+            None => return vec![],
+        };
+        let exp = self.items.get(state.hover).and_then(|f| f.exp());
+        let exp = exp.map(|f| self.shown_exp(opts, state, state.hover, f));
+        let mut lines = wrap(exp.as_deref().unwrap_or(""), self.term_width(opts));
+        lines.resize(height, String::new());
+        lines
+    }
+    /// Returns hotkeys and their descriptions displayed in the bottom line.
+    fn legend(&self, opts: &RunOptions, is_sub: bool, state: &State) -> Vec<(Vec<String>, String)> {
+        let is_grid = matches!(opts.layout, Layout::Grid { .. });
+        let mut legend = vec![];
+        if is_grid {
+            legend.push((
+                vec![
+                    "Up".to_string(),
+                    "Down".to_string(),
+                    "Left".to_string(),
+                    "Right".to_string(),
+                ],
+                opts.locale.movement.to_string(),
+            ));
+        } else {
+            legend.push((
+                vec!["Up".to_string(), "Down".to_string()],
+                opts.locale.movement.to_string(),
+            ));
+        }
+        if !is_grid
+            && (matches!(
+                self.items.get(state.hover),
+                Some(Item::SubMenu { .. } | Item::LazySubMenu { .. })
+            ) || (!opts.right_descends_only
+                && matches!(self.items.get(state.hover), Some(Item::Bool { .. }))))
+        {
+            legend.push((
+                vec!["Enter".to_string(), "Right".to_string()],
+                opts.locale.select.to_string(),
+            ));
+        } else {
+            legend.push((vec!["Enter".to_string()], opts.locale.select.to_string()));
+        }
+        if is_sub && is_grid {
+            legend.push((vec!["Backspace".to_string()], opts.locale.back.to_string()));
+        } else if is_sub {
+            legend.push((
+                vec!["Backspace".to_string(), "Left".to_string()],
+                opts.locale.back.to_string(),
+            ));
+        }
+        if let Some(checked) = &state.checked {
+            let count = checked.borrow().indexes.len();
+            let text = match (opts.min_select, opts.max_select) {
+                (None, None) => opts.locale.check.to_string(),
+                (Some(min), Some(max)) => {
+                    format!("{} {} ({}-{})", opts.locale.check, count, min, max)
+                }
+                (Some(min), None) => format!("{} {} ({}+)", opts.locale.check, count, min),
+                (None, Some(max)) => format!("{} {} (0-{})", opts.locale.check, count, max),
+            };
+            legend.push((vec!["Space".to_string()], text));
+        }
+        if matches!(self.items.get(state.hover), Some(Item::Counter { .. })) {
+            legend.push((
+                vec!["Left".to_string(), "Right".to_string()],
+                opts.locale.adjust.to_string(),
+            ));
+        }
+        if matches!(self.items.get(state.hover), Some(Item::Group { .. })) {
+            legend.push((vec!["Space".to_string()], opts.locale.fold.to_string()));
+        }
+        if let Some(default_action) = opts.default_action {
+            if default_action.index < self.items.len() {
+                legend.push((
+                    vec![key_name(default_action.key)],
+                    self.label(opts, state, default_action.index).1,
+                ));
+            }
+        }
+        if let Some(key) = opts.exp_toggle_key {
+            legend.push((vec![key_name(key)], opts.locale.explanations.to_string()));
+        }
+        if let Some(key) = opts.reveal_key.filter(|_| opts.mask_exp.is_some()) {
+            legend.push((vec![key_name(key)], opts.locale.reveal.to_string()));
+        }
+        if let Some(key) = opts.undo_key {
+            legend.push((vec![key_name(key)], opts.locale.undo.to_string()));
+        }
+        if self.esc {
+            legend.push((vec!["Esc".to_string()], opts.locale.exit.to_string()));
+        }
+        legend
+    }
+    /// Splits the legend into lines fitting the terminal width.
+    fn legend_lines(
+        &self,
+        opts: &RunOptions,
+        is_sub: bool,
+        state: &State,
+    ) -> Vec<Vec<(Vec<String>, String)>> {
+        let width = self.term_width(opts);
+        let mut lines: Vec<Vec<(Vec<String>, String)>> = vec![vec![]];
+        let mut used = 0;
+        for (keys, text) in self.legend(opts, is_sub, state) {
+            let entry_width = keys.iter().map(|f| f.chars().count() + 2).sum::<usize>()
+                + 1
+                + text.chars().count();
+            if used == 0 {
+                used = entry_width;
+            } else if used + 2 + entry_width > width {
+                lines.push(vec![]);
+                used = entry_width;
+            } else {
+                used += 2 + entry_width;
+            }
+            lines
+                .last_mut()
+                .expect("legend last line")
+                .push((keys, text));
+        }
+        lines
+    }
+    /// Returns the number of lines `print_bottom` prints.
+    fn bottom_height(&self, opts: &RunOptions, is_sub: bool, state: &State) -> usize {
+        let hint = match &state.filter {
+            Some(filter) => opts.locale.search.chars().count() + filter.chars().count(),
+            None => self.hint(opts).chars().count(),
+        };
+        let hint = hint.div_ceil(self.term_width(opts));
+        let preview = self.preview(opts, state).map_or(0, |_| 1);
+        let position = self.position(opts, state).map_or(0, |_| 1);
+        opts.detail_height.unwrap_or(0)
+            + self.legend_lines(opts, is_sub, state).len()
+            + preview
+            + position
+            + hint.max(1)
+    }
+    /// Returns the index number displayed for the `Item` at the index, or `None` for a `Label`, and for a `Group` with `RunOptions::number_actionable`.
+    fn display_index(&self, opts: &RunOptions, index: usize) -> Option<usize> {
+        let numbered = |item: &Item| match item {
+            Item::Label { .. } => false,
+            Item::Group { .. } => !opts.number_actionable,
+            _ => true,
+        };
+        if !self.items.get(index).is_none_or(numbered) {
+            return None;
+        }
+        Some(self.items[..index].iter().filter(|f| numbered(f)).count())
+    }
+    /// Returns the index number with its dot, or as many spaces as the number of the next `Item` would take if there is none.
+    fn index_text(&self, opts: &RunOptions, index: usize) -> String {
+        match self.display_index(opts, index) {
+            Some(number) => format!("{}.", number),
+            None => {
+                let next = self.display_index(opts, index + 1).unwrap_or(0);
+                " ".repeat(next.to_string().len() + 1)
+            }
+        }
+    }
+    /// Types the key into the path of `RunOptions::path_jump`, or starts replaying the typed path on `Enter`. Returns whether the key was taken.
+    fn match_path(
+        &self,
+        opts: &RunOptions,
+        key: &Option<String>,
+        stdout_ins: &mut Term,
+        path: &[String],
+        state: &mut State,
+    ) -> bool {
+        if !opts.path_jump || opts.hide_indexes || state.jumping || state.filter.is_some() {
+            return false;
+        }
+        let previous = state.clone();
+        match key.as_deref() {
+            Some(chr)
+                if chr == "." || (chr.len() == 1 && chr.chars().all(|f| f.is_ascii_digit())) =>
+            {
+                state
+                    .typed_path
+                    .get_or_insert_with(String::new)
+                    .push_str(chr);
+            }
+            Some("Back") if state.typed_path.is_some() => {
+                let typed = state.typed_path.as_mut().expect("typed path");
+                typed.pop();
+                if typed.is_empty() {
+                    state.typed_path = None;
+                }
+            }
+            Some("Exit") if state.typed_path.is_some() => state.typed_path = None,
+            Some("Enter") if state.typed_path.is_some() => {
+                let typed = state.typed_path.take().expect("typed path");
+                match self.path_keys(opts, state, &typed) {
+                    Some(keys) => {
+                        state.jump.borrow_mut().extend(keys);
+                        return true;
+                    }
+                    None => state.notice = Some(format!("{}{}", opts.locale.no_item_at, typed)),
+                }
+            }
+            _ => return false,
+        }
+        self.redraw(opts, stdout_ins, path, previous, state);
+        true
+    }
+    /// Returns the index keys leading through `SubMenu`s to the `Item` at the dotted path of index numbers, or `None` if there is no such `Item`.
+    fn path_keys(&self, opts: &RunOptions, state: &State, typed: &str) -> Option<Vec<String>> {
+        let segments: Vec<&str> = typed.split('.').collect();
+        let mut level: Option<Menu> = None;
+        let mut visible = self.visible(opts, state);
+        let mut keys = vec![];
+        for (n, segment) in segments.iter().enumerate() {
+            let menu = level.as_ref().unwrap_or(self);
+            let number: usize = segment.parse().ok()?;
+            let index = visible
+                .iter()
+                .copied()
+                .find(|f| menu.display_index(opts, *f) == Some(number))?;
+            keys.push(number.to_string());
+            if n + 1 < segments.len() {
+                if opts.submenu_selectable {
+                    return None;
+                }
+                let item = &menu.items[index];
+                let sub_menu = menu.sub_menu(item.name(), None, item.sub_items()?);
+                visible = sub_menu.visible(opts, &State::default());
+                level = Some(sub_menu);
+            }
+        }
+        Some(keys)
+    }
+    /// Returns the hotkey of the `Item` at the index, or with `RunOptions::auto_hotkeys` the one `auto_hotkeys()` assigns to it if it has none, which the state keeps for the level.
+    fn hotkey_at(&self, opts: &RunOptions, state: &State, index: usize) -> Option<char> {
+        let item = self.items.get(index)?;
+        if !opts.auto_hotkeys || item.hotkey().is_some() || matches!(item, Item::Label { .. }) {
+            return item.hotkey();
+        }
+        let assigned = state.hotkeys.get_or_init(|| self.auto_hotkeys(state));
+        assigned.get(index).copied().flatten()
+    }
+    /// Returns the hotkeys `RunOptions::auto_hotkeys` assigns by the indexes of the `Item`s having none: the first letter of the name not taken by the hotkeys of the level, global hotkeys or `Item`s before it.
+    fn auto_hotkeys(&self, state: &State) -> Vec<Option<char>> {
+        let mut taken: Vec<char> = self
+            .items
+            .iter()
+            .filter_map(|f| f.hotkey())
+            .chain(state.globals.keys().copied())
+            .flat_map(char::to_lowercase)
+            .collect();
+        let assign = |item: &Item| {
+            if item.hotkey().is_some() || matches!(item, Item::Label { .. }) {
+                return None;
+            }
+            let assigned = item
+                .name()
+                .chars()
+                .filter(|f| f.is_alphabetic())
+                .flat_map(char::to_lowercase)
+                .find(|f| !taken.contains(f));
+            taken.extend(assigned);
+            assigned
+        };
+        self.items.iter().map(assign).collect()
+    }
+    /// Whether the key is the hotkey of the `Item` at the index.
+    fn is_hotkey(
+        &self,
+        opts: &RunOptions,
+        key: &Option<String>,
+        state: &State,
+        index: usize,
+    ) -> bool {
+        self.hotkey_at(opts, state, index).map(|f| f.to_string()) == *key
+    }
+    /// Whether the key is the index number displayed for the `Item` at the index.
+    fn is_index_key(&self, opts: &RunOptions, key: &Option<String>, index: usize) -> bool {
+        !opts.hide_indexes
+            && self
+                .display_index(opts, index)
+                .is_some_and(|f| *key == Some(f.to_string()))
+    }
+    fn hint<'a>(&self, opts: &'a RunOptions) -> &'a str {
+        if opts.hide_indexes {
+            &opts.locale.hotkey_hint
+        } else {
+            &opts.locale.hint
+        }
+    }
+    fn term_width(&self, opts: &RunOptions) -> usize {
+        let width = terminal_width(opts);
+        opts.max_width.map_or(width, |f| width.min(f.max(1)))
+    }
+    fn poll_read(&self, opts: &RunOptions, state: &mut State) -> KeyEvent {
+        if let Some(script) = &opts.script {
+            return script.next_key();
+        }
+        loop {
+            if let Some(event) = opts.read_key() {
+                let code = event.code;
+                let now = Instant::now();
+                if let (Some(window), Some((last, at))) = (opts.debounce, state.last_key) {
+                    if last == code && now.duration_since(at) < window {
+                        continue;
+                    }
+                }
+                state.last_key = Some((code, now));
+                break event;
+            }
+        }
+    }
+    /// Asks to type the `phrase` of an `Action`, for the line the `Menu` waits for.
+    fn print_phrase_prompt(&self, opts: &RunOptions, phrase: &str) {
+        outln!(
+            opts,
+            "{}{}",
+            opts.locale.type_to_confirm.as_str().with(opts.theme.error),
+            phrase.with(opts.theme.hover).bold()
+        );
+    }
+    fn match_keycode(&self, opts: &RunOptions, keycode: KeyCode) -> Option<String> {
+        if let Some(default_action) = opts.default_action {
+            if keycode == default_action.key {
+                return Some(String::from("Accept"));
+            }
+        }
+        if opts.exp_toggle_key == Some(keycode) {
+            return Some(String::from("ToggleExp"));
+        }
+        if opts.reveal_key == Some(keycode) {
+            return Some(String::from("Reveal"));
+        }
+        if opts.undo_key == Some(keycode) {
+            return Some(String::from("Undo"));
+        }
+        match keycode {
+            KeyCode::Up => Some(String::from("Up")),
+            KeyCode::Down => Some(String::from("Down")),
+            KeyCode::Tab if opts.tab_navigation => Some(String::from("Down")),
+            KeyCode::BackTab if opts.tab_navigation => Some(String::from("Up")),
+            KeyCode::Left => Some(String::from("Left")),
+            KeyCode::Right => Some(String::from("Right")),
+            KeyCode::Enter => Some(String::from("Enter")),
+            KeyCode::Esc => Some(String::from("Exit")),
+            KeyCode::Backspace => Some(String::from("Back")),
+            KeyCode::Char(chr) if chr.is_alphabetic() => Some(chr.to_string().to_lowercase()),
+            KeyCode::Char(chr) => Some(chr.to_string()),
+            _ => None,
+        }
+    }
+    fn unhandled_key(
+        &self,
+        opts: &RunOptions,
+        event: KeyEvent,
+        stdout_ins: &mut Term,
+        path: &[String],
+        state: &mut State,
+    ) -> Step {
+        let flow = match &opts.on_unhandled_key {
+            Some(handler) => handler.handle(event),
+            None => KeyFlow::Ignore,
+        };
+        match flow {
+            KeyFlow::Exit(result) => {
+                self.clear_menu(opts, stdout_ins, path.len() > 1, state);
+                stdout_ins.flush().unwrap();
+                Step::Done(Err(MenuError::Run(result).into()))
+            }
+            KeyFlow::Consume => {
+                self.redraw(opts, stdout_ins, path, state.clone(), state);
+                Step::Wait
+            }
+            KeyFlow::Ignore => Step::Wait,
+        }
+    }
+    fn match_selection(
+        &self,
+        opts: &RunOptions,
+        key: &Option<String>,
+        is_sub: bool,
+        stdout_ins: &mut Term,
+        path: &[String],
+        state: &mut State,
+    ) -> Step {
+        if key.is_none() {
+            return Step::Wait;
+        }
+        state.notice = None;
+        if self.match_path(opts, key, stdout_ins, path, state) {
+            return Step::Wait;
+        }
+        let previous = state.clone();
+        let key = &match (&mut state.filter, key.as_deref()) {
+            (None, Some("/")) if opts.search => {
+                state.filter = Some(String::new());
+                self.redraw(opts, stdout_ins, path, previous, state);
+                return Step::Wait;
+            }
+            (Some(filter), Some("Back")) if !filter.is_empty() => {
+                filter.pop();
+                self.hover_best(opts, state);
+                self.fix_hover(opts, state);
+                self.redraw(opts, stdout_ins, path, previous, state);
+                return Step::Wait;
+            }
+            (Some(_), Some("Back")) if !is_sub => {
+                state.filter = None;
+                self.redraw(opts, stdout_ins, path, previous, state);
+                return Step::Wait;
+            }
+            (Some(filter), Some(chr)) if chr.chars().count() == 1 => {
+                filter.push_str(chr);
+                self.hover_best(opts, state);
+                self.fix_hover(opts, state);
+                self.redraw(opts, stdout_ins, path, previous.clone(), state);
+                if !(opts.auto_accept_single && self.navigable(opts, state).len() == 1) {
+                    return Step::Wait;
+                }
+                Some("Enter".to_string())
+            }
+            _ => key.clone(),
+        };
+        let key = &match key.as_deref() {
+            Some("Undo") => match state.moves.pop() {
+                Some(hover) => {
+                    state.hover = hover;
+                    self.fix_hover(opts, state);
+                    self.redraw(opts, stdout_ins, path, previous, state);
+                    return Step::Wait;
+                }
+                None if is_sub => Some("Back".to_string()),
+                None => return Step::Wait,
+            },
+            _ => key.clone(),
+        };
+        let visible = self.visible(opts, state);
+        let navigable = self.navigable(opts, state);
+        if let (Some(Item::Counter { min, max, step, .. }), Some(value)) = (
+            self.items.get(state.hover),
+            self.counter(state, state.hover),
+        ) {
+            let change = match key.as_deref() {
+                Some("Left") | Some("-") => Some(-step),
+                Some("Right") | Some("+") => Some(*step),
+                _ => None,
+            };
+            if let Some(change) = change {
+                let value = value.saturating_add(change).clamp(*min, (*max).max(*min));
+                state.counters.insert(state.hover, value);
+                self.redraw(opts, stdout_ins, path, previous, state);
+                return Step::Wait;
+            }
+        }
+        if let Layout::Grid { .. } = opts.layout {
+            if *key == Some("Left".to_string()) || *key == Some("Right".to_string()) {
+                let (rows, _) = self.grid_shape(opts, state);
+                if let Some(position) = visible.iter().position(|f| *f == state.hover) {
+                    // moves a column over, or further past `Label`s.
+                    let mut cells = (1..).map(|f| {
+                        if *key == Some("Left".to_string()) {
+                            position.checked_sub(rows * f)
+                        } else {
+                            Some(position + rows * f).filter(|f| *f < visible.len())
+                        }
+                    });
+                    if let Some(Some(next)) =
+                        cells.find(|f| f.is_none_or(|f| navigable.contains(&visible[f])))
+                    {
+                        state.hover = visible[next];
+                    }
+                }
+                self.redraw(opts, stdout_ins, path, previous, state);
+                return Step::Wait;
+            }
+        }
+        if is_sub && (*key == Some("Back".to_string()) || *key == Some("Left".to_string())) {
+            let question = opts.locale.yes_no(&opts.locale.discard_entries);
+            if opts.confirm_discard && state.entered {
+                outln!(opts, "{}", question.as_str().with(opts.theme.error));
+                return Step::Ask(Prompt::Confirm(question, Err(Unwind::Back)));
+            }
+            self.clear_menu(opts, stdout_ins, is_sub, state);
+            return Step::Done(Err(Unwind::Back));
+        } else if *key == Some("Exit".to_string()) {
+            if self.esc {
+                let guarded = opts
+                    .confirm_exit
+                    .as_ref()
+                    .is_some_and(|f| f.should_confirm());
+                let question = opts.locale.yes_no(&opts.locale.confirm_exit);
+                if guarded {
+                    outln!(opts, "{}", question.as_str().with(opts.theme.error));
+                    let exited = Err(MenuError::Exited.into());
+                    return Step::Ask(Prompt::Confirm(question, exited));
+                }
+                self.clear_menu(opts, stdout_ins, is_sub, state);
+                stdout_ins.flush().unwrap();
+                return Step::Done(Err(MenuError::Exited.into()));
+            }
+        } else if *key == Some("Up".to_string()) {
+            if state.hover == UNFOCUSED {
+                state.hover = navigable.last().copied().unwrap_or(0);
+            } else if let Some(position) = navigable.iter().position(|f| *f == state.hover) {
+                if position > 0 {
+                    state.hover = navigable[position - 1];
+                } else if opts.wrap_navigation {
+                    state.hover = navigable[navigable.len() - 1];
+                }
+            }
+            self.redraw(opts, stdout_ins, path, previous, state);
+            return Step::Wait;
+        } else if *key == Some(" ".to_string()) && self.check_mark(state, state.hover).is_some() {
+            if let Some(checked) = &state.checked {
+                let checked = &mut checked.borrow_mut().indexes;
+                match checked.iter().position(|f| *f == state.hover) {
+                    Some(position) => {
+                        checked.remove(position);
+                    }
+                    None if opts.max_select.is_some_and(|f| checked.len() >= f) => {
+                        state.notice = opts
+                            .max_select
+                            .map(|f| format!("{}{}", opts.locale.select_at_most, f));
+                    }
+                    None => checked.push(state.hover),
+                }
+            }
+            self.redraw(opts, stdout_ins, path, previous, state);
+            return Step::Wait;
+        } else if *key == Some("ToggleExp".to_string()) {
+            state.hide_exp.set(!state.hide_exp.get());
+            self.redraw(opts, stdout_ins, path, previous, state);
+            return Step::Wait;
+        } else if *key == Some("Reveal".to_string()) {
+            state.revealed.set(!state.revealed.get());
+            self.redraw(opts, stdout_ins, path, previous, state);
+            return Step::Wait;
+        } else if *key == Some("Down".to_string()) {
+            if state.hover == UNFOCUSED {
+                state.hover = navigable.first().copied().unwrap_or(0);
+            } else if let Some(position) = navigable.iter().position(|f| *f == state.hover) {
+                if (position + 1) < navigable.len() {
+                    state.hover = navigable[position + 1];
+                } else if opts.wrap_navigation {
+                    state.hover = navigable[0];
+                }
+            }
+            self.redraw(opts, stdout_ins, path, previous, state);
+            return Step::Wait;
+        }
+        let matches: Vec<usize> = visible
+            .iter()
+            .copied()
+            .filter(|f| self.is_hotkey(opts, key, state, *f))
+            .collect();
+        if matches.len() > 1 {
+            match opts.hotkey_policy {
+                HotkeyPolicy::FirstMatch => {}
+                HotkeyPolicy::Error => {
+                    self.clear_menu(opts, stdout_ins, is_sub, state);
+                    stdout_ins.flush().unwrap();
+                    let hotkey = self.hotkey_at(opts, state, matches[0]);
+                    let hotkey = hotkey.expect("matched hotkey");
+                    return Step::Done(Err(MenuError::AmbiguousHotkey(hotkey).into()));
+                }
+                HotkeyPolicy::Cycle => {
+                    state.hover = match matches.iter().position(|f| *f == state.hover) {
+                        Some(position) => matches[(position + 1) % matches.len()],
+                        None => matches[0],
+                    };
+                    self.redraw(opts, stdout_ins, path, previous, state);
+                    return Step::Wait;
+                }
+            }
+        }
+        for (i, item) in self.items.iter().enumerate() {
+            if !visible.contains(&i) {
+                continue;
+            }
+            match item {
+                Item::Action {
+                    name,
+                    confirm_phrase,
+                    ..
+                } => {
+                    if self.is_hotkey(opts, key, state, i)
+                        || self.is_index_key(opts, key, i)
+                        || (*key == Some("Accept".to_string()) && is_default_action(opts, i))
+                        || (*key == Some("Enter".to_string()) && i == state.hover)
                     {
-                        self.clear_menu(stdout_ins);
-                        path.push(name.to_string());
-                        let sub_menu = Menu {
-                            name: name.to_string(),
-                            items: items.clone(),
-                            exp: exp.as_ref().map(|f| String::from(f)),
-                            esc: self.esc,
-                        };
-                        let sub_result = sub_menu.run_sub(path);
-                        match sub_result {
-                            Ok(ok) => return Ok(ok),
-                            Err(err) if &err == "Back" => {
-                                path.pop();
-                                if path.len() == 1 {
-                                    return self.rerun(*hover);
-                                } else {
-                                    return self.rerun_sub(path, *hover);
+                        let checklist = state.checked.clone().filter(|_| {
+                            matches!(
+                                item,
+                                Item::Action {
+                                    checkable: true,
+                                    ..
                                 }
+                            )
+                        });
+                        if let (Some(checked), Some(min)) = (&checklist, opts.min_select) {
+                            if checked.borrow().indexes.len() < min {
+                                let previous = state.clone();
+                                state.notice =
+                                    Some(format!("{}{}", opts.locale.select_at_least, min));
+                                self.redraw(opts, stdout_ins, path, previous, state);
+                                return Step::Wait;
                             }
-                            Err(err) => return Err(err),
                         }
+                        self.clear_menu(opts, stdout_ins, is_sub, state);
+                        stdout_ins.flush().unwrap();
+                        let confirms = checklist.is_some_and(|f| {
+                            let mut checklist = f.borrow_mut();
+                            checklist.confirmed = !checklist.indexes.is_empty();
+                            checklist.confirmed
+                        });
+                        let selection = Selection {
+                            name: name.to_string(),
+                            path: [path, &[name.to_string()]].concat(),
+                            indexes: vec![i],
+                            value: None,
+                            len: None,
+                            attempt: None,
+                            skipped: false,
+                            item_path: vec![],
+                        };
+                        if let Some(phrase) = confirm_phrase.as_ref().filter(|_| !confirms) {
+                            self.print_phrase_prompt(opts, phrase);
+                            return Step::Ask(Prompt::Phrase(phrase.to_string(), selection));
+                        }
+                        return Step::Done(Ok(selection));
                     } else {
                         continue;
                     }
                 }
-                Item::Bool { name, hotkey, exp } => {
-                    if (*key == hotkey.map(|f| f.to_string()))
-                        || (*key == Some(i.to_string()))
+                Item::SubMenu { name, .. } | Item::LazySubMenu { name, .. } => {
+                    if opts.submenu_selectable
+                        && (self.is_hotkey(opts, key, state, i)
+                            || self.is_index_key(opts, key, i)
+                            || (*key == Some("Enter".to_string()) && i == state.hover))
+                    {
+                        self.clear_menu(opts, stdout_ins, is_sub, state);
+                        stdout_ins.flush().unwrap();
+                        return Step::Done(Ok(Selection {
+                            name: name.to_string(),
+                            path: [path, &[name.to_string()]].concat(),
+                            indexes: vec![i],
+                            value: None,
+                            len: None,
+                            attempt: None,
+                            skipped: false,
+                            item_path: vec![],
+                        }));
+                    }
+                    if self.is_hotkey(opts, key, state, i)
+                        || self.is_index_key(opts, key, i)
+                        || (*key == Some("Accept".to_string()) && is_default_action(opts, i))
                         || ((*key == Some("Enter".to_string())
                             || *key == Some("Right".to_string()))
-                            && i == *hover)
+                            && i == state.hover)
                     {
-                        self.clear_menu(stdout_ins);
-                        path.push(name.to_string());
-                        let sub_menu = Menu {
-                            name: name.to_string(),
-                            items: vec![
-                                Item::Action {
-                                    name: "true".to_string(),
-                                    exp: None,
-                                    hotkey: Some('t'),
-                                },
-                                Item::Action {
-                                    name: "false".to_string(),
-                                    exp: None,
-                                    hotkey: Some('f'),
-                                },
-                            ],
-                            exp: exp.as_ref().map(|f| String::from(f)),
-                            esc: self.esc,
-                        };
-                        let sub_result = sub_menu.run_sub(path);
-                        match sub_result {
-                            Ok(mut ok) => {
-                                let last = ok.path.pop().expect("item bool path pop");
-                                ok.value =
-                                    Some(Value::Bool(last.parse().expect("item bool value parse")));
-                                return Ok(ok);
-                            }
-                            Err(err) if &err == "Back" => {
-                                path.pop();
-                                if path.len() == 1 {
-                                    return self.rerun(*hover);
-                                } else {
-                                    return self.rerun_sub(path, *hover);
-                                }
-                            }
-                            Err(err) => return Err(err),
-                        }
+                        self.clear_menu(opts, stdout_ins, is_sub, state);
+                        let items = item.sub_items().expect("item sub menu items");
+                        let level = self.sub_level(sub_opts(opts), path, state, i, items);
+                        return Step::Descend(level);
                     } else {
                         continue;
                     }
                 }
-                Item::Char { name, hotkey, exp }
-                | Item::String { name, hotkey, exp }
-                | Item::F32 { name, hotkey, exp }
-                | Item::I32 { name, hotkey, exp }
-                | Item::U32 { name, hotkey, exp } => {
-                    if (*key == hotkey.map(|f| f.to_string()))
-                        || (*key == Some(i.to_string()))
-                        || (*key == Some("Enter".to_string()) && i == *hover)
+                Item::Bool { .. } => {
+                    if self.is_hotkey(opts, key, state, i)
+                        || self.is_index_key(opts, key, i)
+                        || (*key == Some("Accept".to_string()) && is_default_action(opts, i))
+                        || ((*key == Some("Enter".to_string())
+                            || (*key == Some("Right".to_string()) && !opts.right_descends_only))
+                            && i == state.hover)
                     {
-                        // (done): flush
-                        self.clear_menu(stdout_ins);
-                        path.push(name.to_string());
-                        // (done): print
-                        self.print_top(path);
-                        self.print_name(item, name, exp);
-                        // (done): selection
-                        let mut attempt = 1;
-                        let input = self.read_line_string();
-                        let selection = match item {
-                            Item::Char { .. } => {
-                                let value: char = self.match_input(item, input, &mut attempt);
-                                Selection {
-                                    name: name.to_string(),
-                                    path: path.to_vec(),
-                                    value: Some(Value::Char(value)),
-                                    len: Some(1),
-                                    attempt: Some(attempt),
-                                }
-                            }
-                            Item::F32 { .. } => {
-                                let value: f32 = self.match_input(item, input, &mut attempt);
-                                Selection {
-                                    name: name.to_string(),
-                                    path: path.to_vec(),
-                                    value: Some(Value::F32(value)),
-                                    len: Some(value.to_string().len()),
-                                    attempt: Some(attempt),
-                                }
-                            }
-                            Item::I32 { .. } => {
-                                let value: i32 = self.match_input(item, input, &mut attempt);
-                                Selection {
-                                    name: name.to_string(),
-                                    path: path.to_vec(),
-                                    value: Some(Value::I32(value)),
-                                    len: Some(value.to_string().len()),
-                                    attempt: Some(attempt),
-                                }
-                            }
-                            Item::U32 { .. } => {
-                                let value: u32 = self.match_input(item, input, &mut attempt);
-                                Selection {
-                                    name: name.to_string(),
-                                    path: path.to_vec(),
-                                    value: Some(Value::U32(value)),
-                                    len: Some(value.to_string().len()),
-                                    attempt: Some(attempt),
-                                }
-                            }
-                            _ => Selection {
-                                name: name.to_string(),
-                                path: path.to_vec(),
-                                value: Some(Value::String(String::from(&input))),
-                                len: Some(input.len()),
-                                attempt: Some(attempt),
+                        self.clear_menu(opts, stdout_ins, is_sub, state);
+                        let choices = vec![
+                            Item::Action {
+                                name: "true".to_string(),
+                                exp: None,
+                                badges: Vec::new(),
+                                hotkey: Some('t'),
+                                confirm_phrase: None,
+                                global_hotkey: None,
+                                url: None,
+                                checkable: false,
+                            },
+                            Item::Action {
+                                name: "false".to_string(),
+                                exp: None,
+                                badges: Vec::new(),
+                                hotkey: Some('f'),
+                                confirm_phrase: None,
+                                global_hotkey: None,
+                                url: None,
+                                checkable: false,
                             },
+                        ];
+                        // choosing true or false isn't a selection of its own to stay after.
+                        let choice_opts = RunOptions {
+                            stay: None,
+                            ..sub_opts(opts)
                         };
-                        self.clear_lines(stdout_ins, (2 + (attempt * 2)) as u16);
+                        return Step::Descend(self.sub_level(choice_opts, path, state, i, choices));
+                    } else {
+                        continue;
+                    }
+                }
+                Item::Group { name, .. } => {
+                    if self.is_hotkey(opts, key, state, i)
+                        || self.is_index_key(opts, key, i)
+                        || ((*key == Some("Enter".to_string()) || *key == Some(" ".to_string()))
+                            && i == state.hover)
+                    {
+                        let collapsed = !self.is_collapsed(state, i);
+                        state.collapsed.insert(i, collapsed);
+                        self.save_group(opts, path, state, name, collapsed);
+                        state.hover = i;
+                        self.redraw(opts, stdout_ins, path, previous, state);
+                        return Step::Wait;
+                    } else {
+                        continue;
+                    }
+                }
+                Item::Counter { name, .. } => {
+                    if self.is_hotkey(opts, key, state, i)
+                        || self.is_index_key(opts, key, i)
+                        || (*key == Some("Accept".to_string()) && is_default_action(opts, i))
+                        || (*key == Some("Enter".to_string()) && i == state.hover)
+                    {
+                        self.clear_menu(opts, stdout_ins, is_sub, state);
                         stdout_ins.flush().unwrap();
-                        return Ok(selection);
+                        let value = self.counter(state, i).expect("counter value");
+                        return Step::Done(Ok(Selection {
+                            name: name.to_string(),
+                            path: [path, &[name.to_string()]].concat(),
+                            indexes: vec![i],
+                            value: Some(Value::I64(value)),
+                            len: Some(value.to_string().len()),
+                            attempt: None,
+                            skipped: false,
+                            item_path: vec![],
+                        }));
                     } else {
                         continue;
                     }
                 }
+                Item::Char { .. }
+                | Item::String { .. }
+                | Item::Secret { .. }
+                | Item::F32 { .. }
+                | Item::F64 { .. }
+                | Item::I32 { .. }
+                | Item::U32 { .. }
+                | Item::NumberList { .. }
+                | Item::ByteSize { .. }
+                | Item::Color { .. }
+                | Item::Json { .. } => {
+                    if self.is_hotkey(opts, key, state, i)
+                        || self.is_index_key(opts, key, i)
+                        || (*key == Some("Accept".to_string()) && is_default_action(opts, i))
+                        || (*key == Some("Enter".to_string()) && i == state.hover)
+                    {
+                        return self.select_input(opts, stdout_ins, is_sub, path, state, i);
+                    } else {
+                        continue;
+                    }
+                }
+                Item::Label { .. } => continue,
             };
         }
-        Err("No Selection".to_string())
+        let global = key.as_deref().and_then(|f| {
+            let mut chars = f.chars();
+            chars.next().filter(|_| chars.next().is_none())
+        });
+        if let Some(path) = global.and_then(|f| state.globals.get(&f)) {
+            let path = path.to_vec();
+            self.clear_menu(opts, stdout_ins, is_sub, state);
+            stdout_ins.flush().unwrap();
+            return Step::Done(Err(Unwind::Global(path)));
+        }
+        Step::Wait
+    }
+    /// Prompts for the value of the input `Item` at the index, in place of the `Menu`.
+    fn select_input(
+        &self,
+        opts: &RunOptions,
+        stdout_ins: &mut Term,
+        is_sub: bool,
+        path: &[String],
+        state: &State,
+        i: usize,
+    ) -> Step {
+        self.clear_menu(opts, stdout_ins, is_sub, state);
+        self.print_input_prompt(opts, path, state, i);
+        Step::Ask(Prompt::Input(InputPrompt {
+            index: i,
+            attempt: 1,
+            height: 1,
+        }))
+    }
+    /// Prints the top line and the name of the input `Item` at the index of the level at the path, and the prompt for its value.
+    fn print_input_prompt(&self, opts: &RunOptions, path: &[String], state: &State, i: usize) {
+        let item = &self.items[i];
+        let name = item.name();
+        let exp = item.exp().map(|f| self.shown_exp(opts, state, i, f));
+        self.print_top(opts, &[path, &[name.to_string()]].concat());
+        self.print_name(opts, item, name, exp.as_deref());
+    }
+    /// Creates a sub menu taking over the settings of this `Menu`.
+    fn sub_menu(&self, name: &str, exp: Option<&str>, items: Vec<Item>) -> Menu {
+        Menu {
+            name: name.to_string(),
+            items,
+            exp: exp.map(str::to_string),
+            esc: self.esc,
+        }
+    }
+    /// Clears the frame rendered with `previous` hover and renders the current one.
+    fn redraw(
+        &self,
+        opts: &RunOptions,
+        stdout_ins: &mut Term,
+        path: &[String],
+        previous: State,
+        state: &mut State,
+    ) {
+        self.clear_menu(opts, stdout_ins, path.len() > 1, &previous);
+        self.fix_hover(opts, state);
+        self.print_header(opts, path, state);
+        self.print_items(opts, path, state);
+        if path.len() == 1 {
+            self.print_bottom(opts, false, state);
+        } else {
+            self.print_bottom(opts, true, state);
+        }
     }
-    fn clear_lines(&self, stdout_ins: &mut Stdout, lines: u16) {
+    fn clear_lines(&self, stdout_ins: &mut Term, lines: u16) {
         stdout_ins
             .queue(cursor::MoveUp(lines))
             .expect("cursor move up");
@@ -666,86 +7144,967 @@ impl Menu {
             .queue(terminal::Clear(ClearType::FromCursorDown))
             .expect("terminal clear");
     }
-    fn clear_menu(&self, stdout_ins: &mut Stdout) {
-        self.clear_lines(stdout_ins, (self.items.len() + 3) as u16);
+    /// Clears the prompt and the rejected input, so the next attempt reuses the same two lines instead of stacking under them.
+    fn clear_prompt(&self, opts: &RunOptions) {
+        let mut term = opts.output.term();
+        self.clear_lines(&mut term, 2);
+        term.flush().expect("flush");
+    }
+    fn clear_menu(&self, opts: &RunOptions, stdout_ins: &mut Term, is_sub: bool, state: &State) {
+        let lines = self.height(opts, is_sub, state);
+        self.clear_lines(stdout_ins, lines as u16);
+    }
+    /// Returns the number of lines the frame occupies: the header, the `Item`s, the bottom border and `print_bottom`.
+    fn height(&self, opts: &RunOptions, is_sub: bool, state: &State) -> usize {
+        let (rows, _) = self.grid_shape(opts, state);
+        let border = if opts.border { 1 } else { 0 };
+        let exp = self.exp_lines(opts).len();
+        let labels = self.label_rows(opts, state);
+        1 + exp + rows + labels + border + self.bottom_height(opts, is_sub, state)
+    }
+    fn print_marker(&self, opts: &RunOptions, index: &usize, hover: &usize) {
+        if let Some(marker) = &opts.hover_marker {
+            if index == hover {
+                out!(opts, "{}", marker.as_str().with(opts.theme.hover).bold());
+            } else {
+                out!(opts, "{}", " ".repeat(marker.chars().count()));
+            }
+        }
+    }
+    /// Prints the index, and the hotkey with `HotkeyPosition::Before`.
+    fn print_hotkey(&self, opts: &RunOptions, index: &usize, hotkey: Option<char>) {
+        if !opts.hide_indexes {
+            match self.display_index(opts, *index) {
+                Some(number) => out!(
+                    opts,
+                    "{}{}",
+                    number.to_string().with(opts.theme.hotkey),
+                    ".".with(opts.theme.muted)
+                ),
+                None => out!(opts, "{}", self.index_text(opts, *index)),
+            }
+        }
+        if opts.hotkey_position == HotkeyPosition::Before {
+            self.print_hotkey_mark(opts, hotkey);
+        }
+    }
+    /// Prints the hotkey aligned after the name part of the label with `HotkeyPosition::After`.
+    fn print_hotkey_after(
+        &self,
+        opts: &RunOptions,
+        state: &State,
+        index: usize,
+        offset: bool,
+        name: &str,
+    ) {
+        if opts.hotkey_position == HotkeyPosition::After {
+            let used = name.chars().count() + if offset { 1 } else { 2 };
+            let pad = self.name_column(opts, state).saturating_sub(used);
+            out!(opts, "{} ", " ".repeat(pad));
+            self.print_hotkey_mark(opts, self.hotkey_at(opts, state, index));
+        }
     }
-    fn print_hotkey(&self, index: &usize, hotkey: &Option<char>) {
-        print!("{}{}", index.to_string().yellow(), ".".dark_grey());
+    fn print_hotkey_mark(&self, opts: &RunOptions, hotkey: Option<char>) {
         match hotkey {
-            Some(chr) => print!(
+            Some(chr) => out!(
+                opts,
                 "{}{}{}",
-                "(".dark_grey(),
-                chr.to_string().to_uppercase().yellow(),
-                ")".dark_grey()
+                "(".with(opts.theme.muted),
+                hotkey_text(chr).with(opts.theme.hotkey),
+                ")".with(opts.theme.muted)
             ),
-            None => print!("   "),
+            None => out!(opts, "   "),
         }
     }
-    fn print_name(&self, item: &Item, name: &String, item_exp: &Option<String>) {
+    fn print_name(&self, opts: &RunOptions, item: &Item, name: &str, item_exp: Option<&str>) {
         if let Some(item_exp) = item_exp {
-            println!(
+            outln!(
+                opts,
                 "       {} {}",
-                String::from(name.to_owned() + "=").cyan().bold(),
-                String::from(item_exp).dark_grey()
+                (name.to_owned() + "=").with(opts.theme.hover).bold(),
+                String::from(item_exp).with(opts.theme.muted)
             );
         } else {
-            println!(
+            outln!(
+                opts,
                 "       {} ",
-                String::from(name.to_owned() + "=").cyan().bold()
+                (name.to_owned() + "=").with(opts.theme.hover).bold()
             );
         }
-        self.print_input_bottom(item);
+        self.print_input_bottom(opts, item);
     }
-    fn print_input_bottom(&self, item: &Item) {
-        // (done): slice
-        let string = item.to_string();
-        let first = string
-            .find("{")
-            .expect("struct name find first parenthesis");
-        let slice = string[0..first - 1].to_string();
-        // (done): print
-        print!("{}{}", "Enter a value. Type: ".dark_grey(), slice.blue());
-        println!();
+    fn print_input_bottom(&self, opts: &RunOptions, item: &Item) {
+        let label = opts.locale.type_labels.get(item.type_name());
+        let type_name = match (opts.type_hint, item, label) {
+            (TypeHint::Friendly, _, Some(label)) => label.to_string(),
+            (_, Item::NumberList { kind, .. }, _) => format!("{}<{:?}>", item.type_name(), kind),
+            _ => item.type_name().to_string(),
+        };
+        if opts.type_hint == TypeHint::Hidden {
+            let prompt = &opts.locale.enter_value_untyped;
+            out!(opts, "{}", prompt.as_str().with(opts.theme.muted));
+        } else {
+            out!(
+                opts,
+                "{}{}",
+                opts.locale.enter_value.as_str().with(opts.theme.muted),
+                type_name.with(opts.theme.kind)
+            );
+        }
+        if let Item::Char {
+            allowed: Some(allowed),
+            ..
+        } = item
+        {
+            let allowed: Vec<String> = allowed.iter().map(char::to_string).collect();
+            let allowed = format!(" ({})", allowed.join("/"));
+            out!(opts, "{}", allowed.with(opts.theme.muted));
+        }
+        if matches!(item, Item::I32 { .. } | Item::F32 { .. } | Item::F64 { .. }) {
+            out!(
+                opts,
+                "{}",
+                opts.locale.signed_hint.as_str().with(opts.theme.muted)
+            );
+        }
+        let step = match item {
+            Item::F32 { step, .. } => step.map(|f| f.to_string()),
+            Item::F64 { step, .. } => step.map(|f| f.to_string()),
+            Item::I32 { step, .. } => step.map(|f| f.to_string()),
+            Item::U32 { step, .. } => step.map(|f| f.to_string()),
+            _ => None,
+        };
+        if let Some(step) = step {
+            let hint = format!(
+                " ({} {}{})",
+                opts.glyph("↑/↓", "Up/Down"),
+                opts.glyph("±", "+-"),
+                step
+            );
+            out!(opts, "{}", hint.with(opts.theme.muted));
+        }
+        outln!(opts);
     }
     fn print_name_exp(
         &self,
+        opts: &RunOptions,
         index: &usize,
-        hover: &mut usize,
+        state: &State,
         offset: bool,
-        name: &String,
-        exp: &Option<String>,
+        name: &str,
+        exp: Option<&str>,
     ) {
-        let space;
-        if offset {
-            space = " ";
-        } else {
-            space = "  ";
+        let space = if offset { " " } else { "  " };
+        out!(opts, "{}", space);
+        self.print_label_name(opts, index, state, name);
+        self.print_hotkey_after(opts, state, *index, offset, name);
+        if let Some(exp_str) = exp {
+            out!(opts, " {}", String::from(exp_str).with(opts.theme.muted));
         }
-        if index == hover {
-            print!("{}{}", space, String::from(name).cyan().bold());
-        } else {
-            print!("{}{}", space, name);
+    }
+    /// Prints the name part of a label, highlighted if hovered, followed by the current value of the `Item` in green and its badges. Parts of the name matching the search text are underlined if `RunOptions::highlight_search` is enabled, and so is the hotkey letter with `RunOptions::auto_hotkeys`.
+    fn print_label_name(&self, opts: &RunOptions, index: &usize, state: &State, name: &str) {
+        let badges = self.items.get(*index).map_or("".to_string(), badge_text);
+        let name = &name[..name.len() - badges.len()];
+        let current = self
+            .items
+            .get(*index)
+            .and_then(|f| f.current())
+            .unwrap_or("");
+        let (name, current) = name.split_at(name.len() - current.len());
+        let mut matches = match (&state.filter, self.items.get(*index)) {
+            (Some(filter), Some(item)) if opts.highlight_search => {
+                search_matches(name, item.name(), filter, opts.search_mode)
+            }
+            _ => vec![false; name.chars().count()],
+        };
+        // underlines the letter of `RunOptions::auto_hotkeys` in the name, after any prefix like `+`.
+        let mnemonic = self
+            .hotkey_at(opts, state, *index)
+            .filter(|_| opts.auto_hotkeys);
+        if let (Some(hotkey), Some(item)) = (mnemonic, self.items.get(*index)) {
+            let start = name
+                .find(item.name())
+                .map_or(0, |f| name[..f].chars().count());
+            let same = |f: char| f.to_lowercase().eq(hotkey.to_lowercase());
+            if let Some(at) = name.chars().skip(start).position(same) {
+                matches[start + at] = true;
+            }
         }
-        if let Some(exp_str) = exp {
-            print!(" {}", String::from(exp_str).dark_grey());
+        let url = match self.items.get(*index) {
+            Some(Item::Action { url: Some(url), .. }) if opts.capabilities().hyperlinks => {
+                Some(url)
+            }
+            _ => None,
+        };
+        if let Some(url) = url {
+            out!(opts, "\x1b]8;;{}\x1b\\", url);
+        }
+        let mut chars = name.chars().zip(matches).peekable();
+        while let Some((chr, matched)) = chars.next() {
+            let mut part = chr.to_string();
+            while let Some((chr, _)) = chars.next_if(|f| f.1 == matched) {
+                part.push(chr);
+            }
+            match (*index == state.hover, matched) {
+                (true, true) => out!(opts, "{}", part.with(opts.theme.hover).bold().underlined()),
+                (true, false) => out!(opts, "{}", part.with(opts.theme.hover).bold()),
+                (false, true) => out!(opts, "{}", part.bold().underlined()),
+                (false, false) => out!(opts, "{}", part),
+            }
+        }
+        if url.is_some() {
+            out!(opts, "\x1b]8;;\x1b\\");
+        }
+        out!(opts, "{}", current.with(opts.theme.value));
+        for (text, color) in self.items.get(*index).map_or(&[][..], |f| f.badges()) {
+            let color = match color {
+                Color::Reset => opts.theme.badge,
+                color => *color,
+            };
+            out!(opts, " {}", format!("[{}]", text).with(color));
         }
-        println!();
     }
-    fn read_line_string(&self) -> String {
-        let mut input = String::new();
-        stdin().read_line(&mut input).expect("read line");
-        input.trim().to_string()
+    fn read_line_string(&self, opts: &RunOptions) -> String {
+        self.read_line_trimmed(opts, TrimPolicy::Both)
     }
-    fn match_input<T: FromStr>(&self, item: &Item, input: String, attempt: &mut i32) -> T {
+    /// Reads a line, removing whitespace from its ends by the policy.
+    fn read_line_trimmed(&self, opts: &RunOptions, trim: TrimPolicy) -> String {
+        if let Some(script) = &opts.script {
+            return trim.apply(&script.next().unwrap_or_default()).to_string();
+        }
+        if let Input::Reader(reader) = &opts.input {
+            return trim.apply(&reader.line(opts)).to_string();
+        }
+        // bracketed paste marks pasted text, so its newlines don't submit the input. The marks are read in raw mode, where the terminal doesn't echo them.
+        terminal::enable_raw_mode().expect("enable raw mode");
+        out!(opts, "{}", BRACKETED_PASTE_ON);
+        opts.output.term().flush().expect("flush");
+        let reader = Reader::new(stdin());
+        let input = reader.read_line(opts);
+        out!(opts, "{}", BRACKETED_PASTE_OFF);
+        opts.output.term().flush().expect("flush");
+        terminal::disable_raw_mode().expect("disable raw mode");
+        stdin_read(&reader);
+        outln!(opts);
+        trim.apply(&input).to_string()
+    }
+    /// Reads the input of the `Item`, recalling its history if it's a `String` with any, and masking it if it's a `Secret`. A `Json` is read up to a blank line.
+    fn read_input(&self, opts: &RunOptions, item: &Item) -> String {
+        match item {
+            Item::String { history, .. } if !history.is_empty() => {
+                self.read_line_edit(opts, item, history, false)
+            }
+            Item::Secret { .. } => self.read_line_edit(opts, item, &[], true),
+            Item::F32 { step: Some(_), .. }
+            | Item::F64 { step: Some(_), .. }
+            | Item::I32 { step: Some(_), .. }
+            | Item::U32 { step: Some(_), .. } => self.read_line_edit(opts, item, &[], false),
+            Item::String { .. } => self.read_line_trimmed(opts, opts.trim),
+            Item::Json { .. } => self.read_json(opts),
+            _ => self.read_line_string(opts),
+        }
+    }
+    /// Reads lines up to a blank one, joined by line breaks. The terminal is read in raw mode with bracketed paste, so pasted text keeps its line breaks without ending the input at a blank one.
+    fn read_json(&self, opts: &RunOptions) -> String {
+        if let Some(script) = &opts.script {
+            let lines = iter::from_fn(|| script.next().filter(|f| !f.trim().is_empty()));
+            return lines.collect::<Vec<_>>().join("\n");
+        }
+        if let Input::Reader(reader) = &opts.input {
+            return reader.block(opts);
+        }
+        terminal::enable_raw_mode().expect("enable raw mode");
+        out!(opts, "{}", BRACKETED_PASTE_ON);
+        opts.output.term().flush().expect("flush");
+        let reader = Reader::new(stdin());
+        let input = reader.read_block(opts);
+        out!(opts, "{}", BRACKETED_PASTE_OFF);
+        opts.output.term().flush().expect("flush");
+        terminal::disable_raw_mode().expect("disable raw mode");
+        stdin_read(&reader);
+        outln!(opts);
+        input
+    }
+    /// Reads a line in raw mode, edited by `LineEdit`. Each character is echoed as `*` if `mask` is set. Pasted text is typed as literal text, like by `Reader::read_line()`.
+    /// Reads a line in raw mode, where `Up` and `Down` recall the `history`, or adjust the number by the `step` of the `Item`.
+    fn read_line_edit(
+        &self,
+        opts: &RunOptions,
+        item: &Item,
+        history: &[String],
+        mask: bool,
+    ) -> String {
+        let mut edit = LineEdit::default();
+        let trim = input_trim(opts, item);
+        if let Some(script) = &opts.script {
+            while let Some(action) = script.next() {
+                match Script::key_code(&action) {
+                    Some(KeyCode::Enter) => return edit.text(trim),
+                    Some(code) => {
+                        edit.edit_key(item, code, history);
+                    }
+                    None => action.chars().for_each(|f| {
+                        edit.key(KeyCode::Char(f), history);
+                    }),
+                }
+            }
+            // the script ended, which the `Menu` stops on.
+            return String::new();
+        }
+        let mut term = opts.output.term();
+        // the terminal is read in raw mode with bracketed paste, like by `read_line_trimmed()`.
+        let reader = match &opts.input {
+            Input::Reader(reader) => reader.clone(),
+            Input::Terminal => {
+                terminal::enable_raw_mode().expect("enable raw mode");
+                out!(opts, "{}", BRACKETED_PASTE_ON);
+                term.flush().expect("flush");
+                Reader::new(stdin())
+            }
+        };
+        loop {
+            // pasted text is typed as literal text, so its line breaks don't submit the line.
+            if let Some(pasted) = reader.paste() {
+                literal_paste(&pasted).chars().for_each(|f| {
+                    edit.key(KeyCode::Char(f), history);
+                });
+            } else {
+                let event = reader.key();
+                if event.code == KeyCode::Enter || reader.error().is_some() {
+                    break;
+                }
+                if event.modifiers.contains(KeyModifiers::CONTROL)
+                    || !edit.edit_key(item, event.code, history)
+                {
+                    continue;
+                }
+            }
+            out!(opts, "\r");
+            term.queue(terminal::Clear(ClearType::CurrentLine))
+                .expect("terminal clear");
+            let line = match mask {
+                true => "*".repeat(edit.chars.len()),
+                false => edit.chars.iter().collect::<String>(),
+            };
+            out!(opts, "{}{}", " ".repeat(opts.margin()), line);
+            let back = edit.chars.len() - edit.cursor;
+            if back > 0 {
+                term.queue(cursor::MoveLeft(back as u16))
+                    .expect("cursor move left");
+            }
+            term.flush().expect("flush");
+        }
+        if opts.input == Input::Terminal {
+            out!(opts, "{}", BRACKETED_PASTE_OFF);
+            term.flush().expect("flush");
+            terminal::disable_raw_mode().expect("disable raw mode");
+            stdin_read(&reader);
+        }
+        outln!(opts);
+        edit.text(trim)
+    }
+    /// Asks to confirm the parsed value of an input `Item` with `confirm`, for the answer the `Menu` waits for.
+    fn ask_value(&self, opts: &RunOptions, value: &Value) {
+        let question = format!(
+            "{}{}{}",
+            opts.locale.you_entered,
+            value.to_plain_string(),
+            opts.locale.confirm_entry
+        );
+        pose(&question, false, opts);
+    }
+    /// Parses the input, printing the prompt again with the reason if it's invalid. Gives `Parsed::Skipped` if the input is `skip`.
+    fn match_input<T: FromStr>(
+        &self,
+        opts: &RunOptions,
+        item: &Item,
+        input: String,
+        skip: Option<&str>,
+    ) -> Parsed<T> {
+        if skip == Some(input.as_str()) || opts.input_ended() {
+            return Parsed::Skipped;
+        }
         match input.parse() {
-            Ok(ok) => ok,
+            Ok(ok) => Parsed::Valid(ok),
             Err(_) => {
-                *attempt += 1;
-                print!("{}", "Invalid entry: ".dark_red(),);
-                self.print_input_bottom(item);
-                let input = self.read_line_string();
-                self.match_input(item, input, attempt)
+                self.clear_prompt(opts);
+                out!(
+                    opts,
+                    "{}",
+                    opts.locale.invalid_entry.as_str().with(opts.theme.error)
+                );
+                self.print_input_bottom(opts, item);
+                Parsed::Invalid
+            }
+        }
+    }
+    fn match_string(
+        &self,
+        opts: &RunOptions,
+        item: &Item,
+        input: String,
+        skip: Option<&str>,
+    ) -> Parsed<String> {
+        if skip == Some(input.as_str()) || opts.input_ended() {
+            return Parsed::Skipped;
+        }
+        if !input.is_empty()
+            || !matches!(
+                item,
+                Item::String { required: true, .. } | Item::Secret { required: true, .. }
+            )
+        {
+            return Parsed::Valid(input);
+        }
+        self.clear_prompt(opts);
+        out!(
+            opts,
+            "{}",
+            opts.locale.required.as_str().with(opts.theme.error)
+        );
+        self.print_input_bottom(opts, item);
+        Parsed::Invalid
+    }
+    /// Parses the input as JSON by `compact_json()`, printing the prompt again with the parse error if it's invalid. Gives the JSON without whitespace, or `Parsed::Skipped` if the input is `skip`. `height` is the number of lines the last input takes.
+    fn match_json(
+        &self,
+        opts: &RunOptions,
+        item: &Item,
+        input: String,
+        skip: Option<&str>,
+        height: u16,
+    ) -> Parsed<String> {
+        if skip == Some(input.as_str()) || opts.input_ended() {
+            return Parsed::Skipped;
+        }
+        let error = match compact_json(&input) {
+            Ok(json) => return Parsed::Valid(json),
+            Err(error) => error,
+        };
+        let mut term = opts.output.term();
+        // prompt and the rejected input.
+        self.clear_lines(&mut term, 1 + height);
+        term.flush().expect("flush");
+        out!(
+            opts,
+            "{}{} ",
+            opts.locale.invalid_entry.as_str().with(opts.theme.error),
+            error.with(opts.theme.error)
+        );
+        self.print_input_bottom(opts, item);
+        Parsed::Invalid
+    }
+    /// Parses the input as a `char`, which is invalid unless it's one of the `allowed` characters of the `Item`, if they are set.
+    fn match_char(
+        &self,
+        opts: &RunOptions,
+        item: &Item,
+        input: String,
+        skip: Option<&str>,
+    ) -> Parsed<char> {
+        if skip == Some(input.as_str()) || opts.input_ended() {
+            return Parsed::Skipped;
+        }
+        let (allowed, ignore_case) = match item {
+            Item::Char {
+                allowed,
+                ignore_case,
+                ..
+            } => (allowed.as_deref(), *ignore_case),
+            _ => (None, false),
+        };
+        if let Some(value) = input
+            .parse()
+            .ok()
+            .and_then(|f| allowed_char(allowed, ignore_case, f))
+        {
+            return Parsed::Valid(value);
+        }
+        self.clear_prompt(opts);
+        out!(
+            opts,
+            "{}",
+            opts.locale.invalid_entry.as_str().with(opts.theme.error)
+        );
+        self.print_input_bottom(opts, item);
+        Parsed::Invalid
+    }
+    fn match_decimal(
+        &self,
+        opts: &RunOptions,
+        item: &Item,
+        input: String,
+        skip: Option<&str>,
+    ) -> Parsed<f64> {
+        if skip == Some(input.as_str()) || opts.input_ended() {
+            return Parsed::Skipped;
+        }
+        let (decimals, round) = match item {
+            Item::F64 {
+                decimals, round, ..
+            } => (*decimals, *round),
+            _ => (None, false),
+        };
+        // `F32` is parsed as such, so values out of its range are infinite.
+        let parsed = match item {
+            Item::F32 { .. } => input.parse::<f32>().map(f64::from).ok(),
+            _ => input.parse::<f64>().ok(),
+        };
+        let message = match parsed {
+            Some(value) if !value.is_finite() && !opts.allow_non_finite => {
+                &opts.locale.finite_number
+            }
+            Some(value) => match fit_decimals(value, decimals, round) {
+                Some(value) if opts.positive_zero && value == 0.0 => return Parsed::Valid(0.0),
+                Some(value) => return Parsed::Valid(value),
+                None => &opts.locale.too_many_decimals,
+            },
+            None => &opts.locale.invalid_entry,
+        };
+        self.clear_prompt(opts);
+        out!(opts, "{}", message.as_str().with(opts.theme.error));
+        self.print_input_bottom(opts, item);
+        Parsed::Invalid
+    }
+    fn match_list(
+        &self,
+        opts: &RunOptions,
+        item: &Item,
+        input: String,
+        skip: Option<&str>,
+        kind: NumberKind,
+    ) -> Parsed<Vec<Value>> {
+        if skip == Some(input.as_str()) || opts.input_ended() {
+            return Parsed::Skipped;
+        }
+        match kind.parse_list(&input) {
+            Ok(values) => Parsed::Valid(values),
+            Err(element) => {
+                self.clear_prompt(opts);
+                out!(
+                    opts,
+                    "{}{}{}",
+                    opts.locale.invalid_element.as_str().with(opts.theme.error),
+                    element.as_str().with(opts.theme.error).bold(),
+                    " ".with(opts.theme.error)
+                );
+                self.print_input_bottom(opts, item);
+                Parsed::Invalid
+            }
+        }
+    }
+}
+/// Asks the yes or no `question` on its own, without building a `Menu`, and reads a single key: the yes or no key of `Locale`, in either case, or `Enter` for `default`, which the hint after the question shows in capital. Returns `MenuError::Io` if writing the question fails.
+pub fn confirm(question: &str, default: bool) -> Result<bool, MenuError> {
+    confirm_with(question, default, &RunOptions::default())
+}
+/// Same as `confirm()`, but presents and behaves as set by `opts`, like its `locale` and `script`.
+/// ```
+/// use rushterm::{confirm_with, RunOptions, Script};
+///
+/// let opts = RunOptions {
+///     script: Some(Script::new(["Enter", "Enter", "Y", "x", "n"])),
+///     ..RunOptions::default()
+/// };
+/// assert_eq!(confirm_with("Overwrite?", true, &opts), Ok(true));
+/// assert_eq!(confirm_with("Overwrite?", false, &opts), Ok(false));
+/// assert_eq!(confirm_with("Overwrite?", false, &opts), Ok(true));
+/// // Other keys are ignored.
+/// assert_eq!(confirm_with("Overwrite?", true, &opts), Ok(false));
+/// ```
+pub fn confirm_with(question: &str, default: bool, opts: &RunOptions) -> Result<bool, MenuError> {
+    ask(question, default, &opts.for_run())
+}
+/// Asks the question of `confirm_with()` as a part of the run of `opts`, whose `Menu` then sees a failed write too.
+fn ask(question: &str, default: bool, opts: &RunOptions) -> Result<bool, MenuError> {
+    pose(question, default, opts);
+    let answer = loop {
+        if let Some(err) = opts.error() {
+            return Err(err);
+        }
+        let code = match &opts.script {
+            Some(script) => script.next_key().code,
+            None => match opts.read_key() {
+                Some(event) => event.code,
+                None => continue,
+            },
+        };
+        if let Some(answer) = answer(code, default, opts) {
+            break answer;
+        }
+    };
+    outln!(opts);
+    match opts.error() {
+        Some(err) => Err(err),
+        None => Ok(answer),
+    }
+}
+/// Prints the yes or no question of `ask()` with its hint.
+fn pose(question: &str, default: bool, opts: &RunOptions) {
+    let (yes, no) = (opts.locale.yes, opts.locale.no);
+    let hint = match default {
+        true => format!("({}/{})", hotkey_text(yes), no),
+        false => format!("({}/{})", yes, hotkey_text(no)),
+    };
+    opts.start_line();
+    out!(opts, "{} {} ", question, hint.with(opts.theme.muted));
+    opts.output.term().flush().expect("flush");
+}
+/// Returns the answer the key gives to a yes or no question, or `None` if it gives none.
+fn answer(code: KeyCode, default: bool, opts: &RunOptions) -> Option<bool> {
+    match code {
+        KeyCode::Enter => Some(default),
+        KeyCode::Char(chr) if same_letter(chr, opts.locale.yes) => Some(true),
+        KeyCode::Char(chr) if same_letter(chr, opts.locale.no) => Some(false),
+        _ => None,
+    }
+}
+thread_local! {
+    /// Width `terminal_width()` gives instead of the terminal's while `with_width()` runs.
+    static RENDER_WIDTH: Cell<Option<usize>> = const { Cell::new(None) };
+    /// Error reading the terminal of the process ended or failed with, given by `Input::error()`.
+    static TERMINAL_ERROR: Cell<Option<io::ErrorKind>> = const { Cell::new(None) };
+}
+/// Runs `render` as if the terminal was `width` columns wide.
+fn with_width<T>(width: u16, render: impl FnOnce() -> T) -> T {
+    let previous = RENDER_WIDTH.with(|f| f.replace(Some(width as usize)));
+    let result = render();
+    RENDER_WIDTH.with(|f| f.set(previous));
+    result
+}
+/// Returns the width of the terminal, or `RunOptions::fallback_width` if it can't be determined or the output isn't the terminal. `RunOptions::assumed_terminal` takes precedence.
+fn terminal_width(opts: &RunOptions) -> usize {
+    if let Some(terminal) = opts.assumed_terminal {
+        return (terminal.width as usize).max(1);
+    }
+    if let Some(width) = RENDER_WIDTH.with(Cell::get) {
+        return width.max(1);
+    }
+    let detected = match opts.output.is_terminal() {
+        true => terminal::size().ok().map(|(w, _)| w as usize),
+        false => None,
+    };
+    detected.unwrap_or(opts.fallback_width).max(1)
+}
+/// Whether the characters are the same letter, in either case.
+fn same_letter(a: char, b: char) -> bool {
+    a.to_lowercase().eq(b.to_lowercase())
+}
+/// Moves the recalled position in a history of `len` entries, oldest first: `up` goes to older entries, stopping at the oldest, and down goes to newer ones, past the newest back to no entry.
+fn recall(len: usize, position: Option<usize>, up: bool) -> Option<usize> {
+    match (position, up) {
+        (None, true) => len.checked_sub(1),
+        (Some(i), true) => Some(i.saturating_sub(1)),
+        (Some(i), false) if i + 1 < len => Some(i + 1),
+        (_, false) => None,
+    }
+}
+/// Marks the chars of the label matching the search text, case-insensitively, within the `Item` name part of it. Overlapping matches mark the chars of both.
+fn search_matches(label: &str, name: &str, filter: &str, mode: SearchMode) -> Vec<bool> {
+    let chars: Vec<char> = label.chars().collect();
+    let mut matches = vec![false; chars.len()];
+    let width = filter.chars().count();
+    let start = match label.find(name) {
+        Some(start) if width > 0 => label[..start].chars().count(),
+        _ => return matches,
+    };
+    if mode == SearchMode::Fuzzy {
+        for position in fuzzy_match(name, filter).map_or(vec![], |f| f.1) {
+            matches[start + position] = true;
+        }
+        return matches;
+    }
+    let end = start + name.chars().count();
+    let filter = filter.to_lowercase();
+    for i in start..end.saturating_sub(width - 1) {
+        let window: String = chars[i..i + width].iter().collect();
+        if window.to_lowercase() == filter {
+            matches[i..i + width].iter_mut().for_each(|f| *f = true);
+        }
+    }
+    matches
+}
+/// Returns the `Selection` of the skipped input `Item` at the end of the path.
+fn skipped_selection(names: Vec<String>, path: &[usize]) -> Selection {
+    Selection {
+        name: names.last().cloned().unwrap_or_default(),
+        path: names,
+        indexes: path.to_vec(),
+        value: None,
+        len: None,
+        attempt: Some(1),
+        skipped: true,
+        item_path: vec![],
+    }
+}
+/// Matches the chars of the query in order within the name, ignoring case, for `SearchMode::Fuzzy`. Returns the score and the positions of the matched chars in the name, or `None` if they aren't all in it. Each occurrence of the first char is tried as the start, keeping the best score, the leftmost one on ties.
+fn fuzzy_match(name: &str, query: &str) -> Option<(i64, Vec<usize>)> {
+    let name: Vec<char> = name.chars().collect();
+    let query: Vec<char> = query.chars().collect();
+    let same = |a: char, b: char| a.to_lowercase().eq(b.to_lowercase());
+    let first = match query.first() {
+        Some(first) => *first,
+        None => return Some((0, vec![])),
+    };
+    let mut best: Option<(i64, Vec<usize>)> = None;
+    for start in (0..name.len()).filter(|f| same(name[*f], first)) {
+        let mut positions = vec![start];
+        for chr in &query[1..] {
+            let next = positions[positions.len() - 1] + 1;
+            match (next..name.len()).find(|f| same(name[*f], *chr)) {
+                Some(position) => positions.push(position),
+                None => break,
+            }
+        }
+        // later starts leave even fewer chars to match.
+        if positions.len() < query.len() {
+            break;
+        }
+        let score = fuzzy_score(&name, &positions);
+        if best.as_ref().is_none_or(|f| score > f.0) {
+            best = Some((score, positions));
+        }
+    }
+    best
+}
+/// Scores the matched chars of the name as `SearchMode::Fuzzy` documents.
+fn fuzzy_score(name: &[char], positions: &[usize]) -> i64 {
+    let mut score = 0;
+    for (n, &position) in positions.iter().enumerate() {
+        score += 16;
+        let word_start = position == 0
+            || !name[position - 1].is_alphanumeric()
+            || (name[position - 1].is_lowercase() && name[position].is_uppercase());
+        let previous = n.checked_sub(1).map(|f| positions[f]);
+        if word_start || previous == Some(position.wrapping_sub(1)) {
+            score += 8;
+        }
+        let skipped = position - previous.map_or(0, |f| f + 1);
+        score -= skipped as i64;
+    }
+    score
+}
+/// Wraps the text into lines of `width` chars at most, breaking between words, or inside words longer than a line.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    for word in text.split_whitespace() {
+        let last = lines.last_mut().expect("wrap last line");
+        let used = last.chars().count();
+        if used > 0 && used + 1 + word.chars().count() > width {
+            lines.push(String::new());
+        } else if used > 0 {
+            last.push(' ');
+        }
+        for chr in word.chars() {
+            if lines.last().expect("wrap last line").chars().count() >= width {
+                lines.push(String::new());
+            }
+            lines.last_mut().expect("wrap last line").push(chr);
+        }
+    }
+    lines
+}
+/// Replaces the middle half of the text by `*`, keeping a quarter of the characters at each end.
+fn mask_middle(text: &str) -> String {
+    let len = text.chars().count();
+    let keep = len / 4;
+    text.chars()
+        .enumerate()
+        .map(|(i, f)| if i < keep || i >= len - keep { f } else { '*' })
+        .collect()
+}
+/// Adds `step` to the decimal `text`, or subtracts it if not `up`, keeping as many decimal places as the text or the step has, so float errors don't show.
+fn step_decimal(text: &str, step: f64, step_text: &str, up: bool) -> Option<String> {
+    let value: f64 = text.parse().ok()?;
+    let value = if up { value + step } else { value - step };
+    let places = |f: &str| f.split_once('.').map_or(0, |f| f.1.len());
+    Some(format!("{:.*}", places(text).max(places(step_text)), value))
+}
+/// Returns the character as listed in `allowed`, matched in either case with `ignore_case`, or the character itself if nothing is listed. `None` if it's not allowed.
+fn allowed_char(allowed: Option<&[char]>, ignore_case: bool, chr: char) -> Option<char> {
+    let allowed = match allowed {
+        Some(allowed) => allowed,
+        None => return Some(chr),
+    };
+    allowed
+        .iter()
+        .copied()
+        .find(|f| *f == chr || (ignore_case && same_letter(*f, chr)))
+}
+/// Returns the rows of a `Label` text: its lines in `Layout::List`, or the lines joined by spaces in a single cell of `Layout::Grid`.
+fn label_lines(opts: &RunOptions, text: &str) -> Vec<String> {
+    match opts.layout {
+        Layout::List | Layout::Stacked => text.split('\n').map(str::to_string).collect(),
+        Layout::Grid { .. } => vec![text.split('\n').collect::<Vec<_>>().join(" ")],
+    }
+}
+/// Returns the number of lines a `Json` input takes on the terminal: the rows of each of its lines, wrapped at the width of the terminal, and the blank one ending it.
+fn json_height(opts: &RunOptions, input: &str) -> u16 {
+    let width = terminal_width(opts);
+    let rows: usize = input
+        .lines()
+        .map(|f| f.chars().count().div_ceil(width).max(1))
+        .sum();
+    (rows + 1).try_into().unwrap_or(u16::MAX)
+}
+/// Parses the input as JSON, giving it without whitespace, or the parse error. Object keys are sorted, as `serde_json` keeps them. Without the `serde` feature, the input is given as typed.
+#[cfg(feature = "serde")]
+fn compact_json(input: &str) -> Result<String, String> {
+    serde_json::from_str::<serde_json::Value>(input)
+        .map(|f| f.to_string())
+        .map_err(|f| f.to_string())
+}
+/// Parses the input as JSON, giving it without whitespace, or the parse error. Object keys are sorted, as `serde_json` keeps them. Without the `serde` feature, the input is given as typed.
+#[cfg(not(feature = "serde"))]
+fn compact_json(input: &str) -> Result<String, String> {
+    Ok(input.to_string())
+}
+/// Limits the value to `decimals` places, at most `MAX_DECIMALS`, by rounding it half away from zero if `round`, or gives `None` if it has more places. Places are counted and rounded on the shortest decimal text of the value, which is the typed one, like `2.675`, since the binary value is a bit less than that and would round down.
+fn fit_decimals(value: f64, decimals: Option<u8>, round: bool) -> Option<f64> {
+    let decimals = match decimals {
+        Some(decimals) => usize::from(decimals.min(MAX_DECIMALS)),
+        None => return Some(value),
+    };
+    if !value.is_finite() {
+        return Some(value);
+    }
+    let text = value.abs().to_string();
+    let (whole, fraction) = text.split_once('.').unwrap_or((&text, ""));
+    if fraction.len() <= decimals {
+        return Some(value);
+    }
+    if !round {
+        return None;
+    }
+    let mut digits: Vec<u8> = whole
+        .bytes()
+        .chain(fraction.bytes().take(decimals))
+        .map(|f| f - b'0')
+        .collect();
+    if fraction.as_bytes()[decimals] >= b'5' {
+        // carries the rounding up through the nines.
+        match digits.iter().rposition(|f| *f != 9) {
+            Some(at) => {
+                digits[at] += 1;
+                digits[at + 1..].iter_mut().for_each(|f| *f = 0);
+            }
+            None => {
+                digits.iter_mut().for_each(|f| *f = 0);
+                digits.insert(0, 1);
             }
         }
     }
+    let digits: String = digits.iter().map(|f| char::from(b'0' + f)).collect();
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    let rounded: f64 = format!("{}.{}", whole, fraction)
+        .parse()
+        .expect("rounded decimal parse");
+    Some(rounded.copysign(value))
+}
+/// Returns the badges of the `Item` as displayed after its name, like ` [NEW] [beta]`.
+fn badge_text(item: &Item) -> String {
+    item.badges()
+        .iter()
+        .map(|(text, _)| format!(" [{}]", text))
+        .collect()
+}
+/// Returns the key of the `Group` in `UiState`: the names of the `Menu` and `SubMenu`s leading to it and its own, joined by `/`, with `\` and `/` in them escaped by a `\`.
+fn group_key(path: &[String], name: &str) -> String {
+    let escape = |name: &str| name.replace('\\', "\\\\").replace('/', "\\/");
+    let names: Vec<String> = path
+        .iter()
+        .map(|f| escape(f))
+        .chain([escape(name)])
+        .collect();
+    names.join("/")
+}
+/// Prints the path of a selection whose callbacks `RunOptions::dry_run` skipped, if it reports them.
+fn report_dry_run(opts: &RunOptions, selection: &Selection) {
+    if opts.dry_run == DryRun::Report {
+        outln!(
+            opts,
+            "{}{}",
+            opts.locale.would_run,
+            selection.path.join("/")
+        );
+    }
+}
+/// Whether the `Item` at the index is `RunOptions::default_action`.
+fn is_default_action(opts: &RunOptions, index: usize) -> bool {
+    opts.default_action.map(|f| f.index) == Some(index)
+}
+/// Returns the policy the input of the `Item` is trimmed by: `RunOptions::trim` for text, both ends for others.
+fn input_trim(opts: &RunOptions, item: &Item) -> TrimPolicy {
+    match item {
+        Item::String { .. } | Item::Secret { .. } => opts.trim,
+        _ => TrimPolicy::Both,
+    }
+}
+/// Returns the options `SubMenu`s run with: those of the `Menu`, without `RunOptions::default_action`, which points to a top-level `Item`.
+fn sub_opts(opts: &RunOptions) -> RunOptions {
+    RunOptions {
+        default_action: None,
+        ..opts.clone()
+    }
+}
+/// Most decimal places `Item::F64::decimals` limits input to. An `f64` keeps 15 significant digits exactly.
+const MAX_DECIMALS: u8 = 15;
+/// Hover of a `Menu` opened by `RunOptions::start_unfocused` until `Up` or `Down` is pressed.
+const UNFOCUSED: usize = usize::MAX;
+/// Most moves `RunOptions::undo_key` undoes in a `Menu`.
+const MAX_UNDO: usize = 32;
+/// Longest pause of `RunOptions::animation`.
+const MAX_ANIMATION: Duration = Duration::from_millis(250);
+const BRACKETED_PASTE_ON: &str = "\x1b[?2004h";
+const BRACKETED_PASTE_OFF: &str = "\x1b[?2004l";
+const PASTE_START: &str = "\x1b[200~";
+const PASTE_END: &str = "\x1b[201~";
+/// Keeps the error the terminal's stdin ended or failed with, if any, for the run to return instead of reading it again forever.
+fn stdin_read(reader: &Reader) {
+    if let Some(kind) = reader.error() {
+        TERMINAL_ERROR.with(|f| f.set(Some(kind)));
+    }
+}
+/// Keeps pasted text as literal text: line breaks and tabs become spaces, other control chars are dropped.
+fn literal_paste(pasted: &str) -> String {
+    pasted
+        .chars()
+        .filter_map(|chr| match chr {
+            '\n' | '\r' | '\t' => Some(' '),
+            chr if chr.is_control() => None,
+            chr => Some(chr),
+        })
+        .collect()
+}
+/// Keeps the lines of pasted text: line breaks become `\n`, tabs become spaces, other control chars are dropped.
+fn block_paste(pasted: &str) -> String {
+    pasted
+        .replace("\r\n", "\n")
+        .chars()
+        .filter_map(|chr| match chr {
+            '\n' | '\r' => Some('\n'),
+            '\t' => Some(' '),
+            chr if chr.is_control() => None,
+            chr => Some(chr),
+        })
+        .collect()
+}
+/// Gives the displayed text of a hotkey: letters in uppercase, and digits and symbols like `?` or `/` as they are.
+fn hotkey_text(chr: char) -> String {
+    if chr.is_alphabetic() {
+        chr.to_uppercase().to_string()
+    } else {
+        chr.to_string()
+    }
+}
+/// Gives the displayed name of a key.
+fn key_name(keycode: KeyCode) -> String {
+    match keycode {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(chr) => hotkey_text(chr),
+        KeyCode::F(number) => format!("F{}", number),
+        KeyCode::BackTab => "Shift+Tab".to_string(),
+        other => format!("{:?}", other),
+    }
 }
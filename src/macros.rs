@@ -0,0 +1,182 @@
+//! The `menu!` macro. Enabled by the `macros` feature.
+
+/// Builds a `Menu` from terse literals. Purely sugar over `Menu` and `Item`, with `esc` enabled.
+///
+/// The `Menu` name and optional explanation come first, followed by a `;` and the `Item`s. Each `Item` has its kind, name, optional hotkey in parentheses and optional explanation, ending with a `;`. `submenu` takes its `Item`s in braces instead. Kinds are `action`, `submenu`, `bool`, `char`, `string`, `f32`, `f64`, `i32`, `u32`, `bytesize` and `color`.
+/// ```
+/// use rushterm::{menu, Item, Menu};
+///
+/// let menu = menu! {
+///     "Main" "Main Menu Explanation.";
+///     action "Save" ('s') "Saves the file.";
+///     submenu "More" ('m') {
+///         bool "Verbose" ('v');
+///         u32 "Count";
+///     }
+/// };
+/// let written = Menu {
+///     name: "Main".to_string(),
+///     exp: Some("Main Menu Explanation.".to_string()),
+///     items: vec![
+///         Item::Action {
+///             name: "Save".to_string(),
+///             hotkey: Some('s'),
+///             exp: Some("Saves the file.".to_string()),
+///             badges: Vec::new(),
+///             confirm_phrase: None,
+///             global_hotkey: None,
+///             url: None,
+///             checkable: false,
+///         },
+///         Item::SubMenu {
+///             name: "More".to_string(),
+///             hotkey: Some('m'),
+///             exp: None,
+///             badges: Vec::new(),
+///             items: vec![
+///                 Item::Bool {
+///                     name: "Verbose".to_string(),
+///                     hotkey: Some('v'),
+///                     exp: None,
+///                     badges: Vec::new(),
+///                     current: None,
+///                 },
+///                 Item::U32 {
+///                     name: "Count".to_string(),
+///                     hotkey: None,
+///                     exp: None,
+///                     badges: Vec::new(),
+///                     skippable: false,
+///                     current: None,
+///                     confirm: false,
+///                     step: None,
+///                 },
+///             ],
+///         },
+///     ],
+///     esc: true,
+/// };
+/// assert_eq!(menu, written);
+/// ```
+#[macro_export]
+macro_rules! menu {
+    (@option $($value:expr)?) => {
+        None $(.or(Some($value)))?
+    };
+    (@items [$($done:expr,)*]) => {
+        vec![$($done,)*]
+    };
+    (@items [$($done:expr,)*] action $name:literal $(($hotkey:literal))? $($exp:literal)?; $($rest:tt)*) => {
+        $crate::menu!(@items [$($done,)* $crate::Item::Action {
+            name: $name.to_string(),
+            hotkey: $crate::menu!(@option $($hotkey)?),
+            exp: $crate::menu!(@option $($exp.to_string())?),
+            badges: Vec::new(),
+            confirm_phrase: None,
+            global_hotkey: None,
+            url: None,
+            checkable: false,
+        },] $($rest)*)
+    };
+    (@items [$($done:expr,)*] submenu $name:literal $(($hotkey:literal))? $($exp:literal)? { $($items:tt)* } $($rest:tt)*) => {
+        $crate::menu!(@items [$($done,)* $crate::Item::SubMenu {
+            name: $name.to_string(),
+            hotkey: $crate::menu!(@option $($hotkey)?),
+            exp: $crate::menu!(@option $($exp.to_string())?),
+            badges: Vec::new(),
+            items: $crate::menu!(@items [] $($items)*),
+        },] $($rest)*)
+    };
+    (@items [$($done:expr,)*] f64 $name:literal $(($hotkey:literal))? $($exp:literal)?; $($rest:tt)*) => {
+        $crate::menu!(@items [$($done,)* $crate::Item::F64 {
+            name: $name.to_string(),
+            hotkey: $crate::menu!(@option $($hotkey)?),
+            exp: $crate::menu!(@option $($exp.to_string())?),
+            badges: Vec::new(),
+            skippable: false,
+            decimals: None,
+            round: false,
+            current: None,
+            confirm: false,
+            step: None,
+        },] $($rest)*)
+    };
+    (@items [$($done:expr,)*] bool $name:literal $(($hotkey:literal))? $($exp:literal)?; $($rest:tt)*) => {
+        $crate::menu!(@items [$($done,)* $crate::Item::Bool {
+            name: $name.to_string(),
+            hotkey: $crate::menu!(@option $($hotkey)?),
+            exp: $crate::menu!(@option $($exp.to_string())?),
+            badges: Vec::new(),
+            current: None,
+        },] $($rest)*)
+    };
+    (@items [$($done:expr,)*] char $name:literal $(($hotkey:literal))? $($exp:literal)?; $($rest:tt)*) => {
+        $crate::menu!(@items [$($done,)* $crate::Item::Char {
+            name: $name.to_string(),
+            hotkey: $crate::menu!(@option $($hotkey)?),
+            exp: $crate::menu!(@option $($exp.to_string())?),
+            badges: Vec::new(),
+            skippable: false,
+            current: None,
+            allowed: None,
+            ignore_case: false,
+        },] $($rest)*)
+    };
+    (@items [$($done:expr,)*] string $name:literal $(($hotkey:literal))? $($exp:literal)?; $($rest:tt)*) => {
+        $crate::menu!(@items [$($done,)* $crate::Item::String {
+            name: $name.to_string(),
+            hotkey: $crate::menu!(@option $($hotkey)?),
+            exp: $crate::menu!(@option $($exp.to_string())?),
+            badges: Vec::new(),
+            skippable: false,
+            required: false,
+            history: vec![],
+            current: None,
+        },] $($rest)*)
+    };
+    (@items [$($done:expr,)*] f32 $($rest:tt)*) => {
+        $crate::menu!(@number [$($done,)*] F32 $($rest)*)
+    };
+    (@items [$($done:expr,)*] i32 $($rest:tt)*) => {
+        $crate::menu!(@number [$($done,)*] I32 $($rest)*)
+    };
+    (@items [$($done:expr,)*] u32 $($rest:tt)*) => {
+        $crate::menu!(@number [$($done,)*] U32 $($rest)*)
+    };
+    (@items [$($done:expr,)*] bytesize $($rest:tt)*) => {
+        $crate::menu!(@input [$($done,)*] ByteSize $($rest)*)
+    };
+    (@items [$($done:expr,)*] color $($rest:tt)*) => {
+        $crate::menu!(@input [$($done,)*] Color $($rest)*)
+    };
+    (@input [$($done:expr,)*] $variant:ident $name:literal $(($hotkey:literal))? $($exp:literal)?; $($rest:tt)*) => {
+        $crate::menu!(@items [$($done,)* $crate::Item::$variant {
+            name: $name.to_string(),
+            hotkey: $crate::menu!(@option $($hotkey)?),
+            exp: $crate::menu!(@option $($exp.to_string())?),
+            badges: Vec::new(),
+            skippable: false,
+            current: None,
+        },] $($rest)*)
+    };
+    (@number [$($done:expr,)*] $variant:ident $name:literal $(($hotkey:literal))? $($exp:literal)?; $($rest:tt)*) => {
+        $crate::menu!(@items [$($done,)* $crate::Item::$variant {
+            name: $name.to_string(),
+            hotkey: $crate::menu!(@option $($hotkey)?),
+            exp: $crate::menu!(@option $($exp.to_string())?),
+            badges: Vec::new(),
+            skippable: false,
+            current: None,
+            confirm: false,
+            step: None,
+        },] $($rest)*)
+    };
+    ($name:literal $($exp:literal)?; $($items:tt)*) => {
+        $crate::Menu {
+            name: $name.to_string(),
+            exp: $crate::menu!(@option $($exp.to_string())?),
+            items: $crate::menu!(@items [] $($items)*),
+            esc: true,
+        }
+    };
+}
@@ -0,0 +1,36 @@
+//! Commonly used types, to bring them into scope at once by `use rushterm::prelude::*;`. They stay available from the crate root too.
+//! ```
+//! use rushterm::prelude::*;
+//!
+//! let menu = Menu {
+//!     name: "Main".to_string(),
+//!     exp: None,
+//!     items: vec![Item::Action {
+//!         name: "Build".to_string(),
+//!         hotkey: Some('b'),
+//!         exp: None,
+//!         badges: Vec::new(),
+//!         confirm_phrase: None,
+//!         global_hotkey: None,
+//!         url: None,
+//!         checkable: false,
+//!     }],
+//!     esc: true,
+//! };
+//! let opts = RunOptions {
+//!     theme: Theme::dark(),
+//!     script: Some(Script::new(["b"])),
+//!     ..RunOptions::default()
+//! };
+//! let selection: Selection = menu.run_with(&opts).unwrap();
+//! assert_eq!(selection.name, "Build");
+//! let value: Option<Value> = selection.value;
+//! assert!(value.is_none());
+//! let err: MenuError = menu.select_by_path(&[1], None).unwrap_err();
+//! assert_eq!(err, MenuError::InvalidPath(vec![1]));
+//! ```
+
+pub use crate::{
+    confirm, confirm_with, Input, Item, KeyCode, KeyEvent, KeyModifiers, Locale, Menu, MenuError,
+    MenuOutcome, MenuSession, Output, Reader, RunOptions, Script, Selection, Theme, Value, Writer,
+};
@@ -0,0 +1,2858 @@
+//! Behavior tests of `Menu`, sharing the fixtures below.
+
+use super::*;
+use std::io::Cursor;
+
+/// Stream a run writes to, read back as text.
+#[derive(Clone, Default)]
+struct Buffer(Rc<RefCell<Vec<u8>>>);
+impl Buffer {
+    fn text(&self) -> String {
+        String::from_utf8(self.0.borrow().clone()).unwrap()
+    }
+}
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+fn action(name: &str) -> Item {
+    Item::Action {
+        name: name.to_string(),
+        hotkey: None,
+        exp: None,
+        badges: Vec::new(),
+        confirm_phrase: None,
+        global_hotkey: None,
+        url: None,
+        checkable: false,
+    }
+}
+/// `action()` with the hotkey.
+fn keyed(name: &str, hotkey: char) -> Item {
+    let mut item = action(name);
+    if let Item::Action { hotkey: key, .. } = &mut item {
+        *key = Some(hotkey);
+    }
+    item
+}
+/// `action()` with the explanation.
+fn explained(name: &str, exp: &str) -> Item {
+    let mut item = action(name);
+    if let Item::Action { exp: text, .. } = &mut item {
+        *text = Some(exp.to_string());
+    }
+    item
+}
+/// `action()` that is `checkable`.
+fn checkable(name: &str) -> Item {
+    let mut item = action(name);
+    if let Item::Action { checkable, .. } = &mut item {
+        *checkable = true;
+    }
+    item
+}
+fn sub_menu(name: &str, items: Vec<Item>) -> Item {
+    Item::SubMenu {
+        name: name.to_string(),
+        hotkey: None,
+        exp: None,
+        badges: Vec::new(),
+        items,
+    }
+}
+fn group(name: &str) -> Item {
+    Item::Group {
+        name: name.to_string(),
+        hotkey: None,
+        exp: None,
+        badges: Vec::new(),
+        collapsed: false,
+    }
+}
+
+#[test]
+fn item_action() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![keyed("Add", '+'), keyed("Help", '?'), keyed("Save", 's')],
+        esc: true,
+    };
+    let opts = RunOptions {
+        capabilities: Capabilities {
+            unicode: false,
+            color: false,
+            hyperlinks: false,
+        },
+        ..RunOptions::default()
+    };
+    let frame = menu.render_to_string(&opts, 80);
+    let items: Vec<&str> = frame.lines().skip(1).take(3).collect();
+    assert_eq!(items, ["0.(+)  Add", "1.(?)  Help", "2.(S)  Save"]);
+    let selected = |key: &str| menu.run_with_stdin_values(&opts, &[key]).unwrap().name;
+    assert_eq!(selected("?"), "Help");
+    assert_eq!(selected("+"), "Add");
+    assert_eq!(selected("S"), "Save");
+}
+
+#[test]
+fn item_badges() {
+    let action = |name: &str, hotkey: char, added: Vec<(String, Color)>| {
+        let mut item = keyed(name, hotkey);
+        if let Item::Action { badges, .. } = &mut item {
+            *badges = added;
+        }
+        item
+    };
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            action("Build", 'b', vec![]),
+            action("Deploy", 'd', vec![("NEW".to_string(), Color::Green)]),
+        ],
+        esc: true,
+    };
+    let run = |color: bool| {
+        let buffer = Buffer::default();
+        let opts = RunOptions {
+            hotkey_position: HotkeyPosition::After,
+            hide_indexes: true,
+            capabilities: Capabilities {
+                unicode: true,
+                color,
+                hyperlinks: false,
+            },
+            output: Output::Writer(Writer::new(buffer.clone())),
+            script: Some(Script::new(["Esc"])),
+            ..RunOptions::default()
+        };
+        menu.run_with(&opts).unwrap_err();
+        buffer.text()
+    };
+    // The badge is green.
+    assert!(run(true).contains("\x1b[38;5;10m[NEW]"));
+    // Hotkeys stay aligned after it.
+    let output = run(false);
+    let lines: Vec<&str> = output.lines().skip(1).take(2).collect();
+    assert_eq!(lines, ["  Build        (B)", "  Deploy [NEW] (D)"]);
+}
+
+#[test]
+fn item_group() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            group("File"),
+            action("Open"),
+            action("Save"),
+            group("Edit"),
+            action("Undo"),
+        ],
+        esc: true,
+    };
+    let opts = RunOptions::default();
+    // Collapsing `File` hides `Open` and `Save`, so `Down` reaches `Edit`.
+    let selection = menu.run_with_stdin_values(&opts, &["Space", "Down", "Down", "Enter"]);
+    assert_eq!(selection.unwrap().name, "Undo");
+    // Expanding it restores them.
+    let selection = menu.run_with_stdin_values(&opts, &["Enter", "Enter", "Down", "Enter"]);
+    assert_eq!(selection.unwrap().name, "Open");
+}
+
+#[test]
+fn group_collapsed() {
+    let file = Item::Group {
+        name: "File".to_string(),
+        hotkey: None,
+        exp: None,
+        badges: Vec::new(),
+        collapsed: true,
+    };
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![action("Quit"), file, keyed("Open", 'o'), action("Save")],
+        esc: true,
+    };
+    let opts = RunOptions::default();
+    // Neither the hotkey of `Open` nor the index number of `Save` selects them.
+    let selection = menu.run_with_stdin_values(&opts, &["o", "3", "Enter"]);
+    assert_eq!(selection.unwrap().name, "Quit");
+    // Expanding `File` enables them.
+    let selection = menu.run_with_stdin_values(&opts, &["Down", "Enter", "o"]);
+    assert_eq!(selection.unwrap().name, "Open");
+}
+
+#[test]
+fn item_label() {
+    let label = Item::Label {
+        text: "Disk: 42% used\nLast backup: today".to_string(),
+    };
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![label, action("Backup"), action("Restore")],
+        esc: true,
+    };
+    let opts = RunOptions {
+        capabilities: Capabilities {
+            unicode: true,
+            color: false,
+            hyperlinks: false,
+        },
+        ..RunOptions::default()
+    };
+    let text = menu.render_to_string(&opts, 80);
+    assert!(text.contains("\nDisk: 42% used\nLast backup: today\n0.     Backup\n1.     Restore\n"));
+    assert_eq!(
+        menu.rendered_height(&opts, 80) as usize,
+        text.lines().count()
+    );
+    // hover starts on `Backup`, and wraps back to it past the label.
+    let selection = menu
+        .run_with_stdin_values(&opts, &["Down", "Down", "Enter"])
+        .unwrap();
+    assert_eq!(selection.name, "Backup");
+    // index numbers skip the label.
+    let selection = menu.run_with_stdin_values(&opts, &["1"]).unwrap();
+    assert_eq!(
+        (selection.name.as_str(), selection.indexes),
+        ("Restore", vec![2])
+    );
+}
+
+#[test]
+fn menu_session() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            action("Build"),
+            Item::SubMenu {
+                name: "Deploy".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                items: vec![action("Staging"), action("Production")],
+            },
+            Item::U32 {
+                name: "Port".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                skippable: false,
+                current: None,
+                confirm: false,
+                step: None,
+            },
+        ],
+        esc: true,
+    };
+    let unhandled = Rc::new(Cell::new(0));
+    let counter = unhandled.clone();
+    let opts = RunOptions {
+        on_unhandled_key: Some(KeyHandler::new(move |_| {
+            counter.set(counter.get() + 1);
+            KeyFlow::Ignore
+        })),
+        ..RunOptions::default()
+    };
+    let mut session = menu.session(&opts, Writer::new(std::io::sink()));
+    let mut events = vec![
+        KeyCode::Down,
+        KeyCode::Enter,
+        KeyCode::Insert,
+        KeyCode::Down,
+        KeyCode::Enter,
+    ]
+    .into_iter();
+    let outcome = loop {
+        session.render().unwrap();
+        let event = KeyEvent::new(events.next().unwrap(), KeyModifiers::NONE);
+        if let Some(outcome) = session.handle_key(event) {
+            break outcome;
+        }
+        if let Some(state) = session.state() {
+            assert!(state.hover.is_some_and(|f| f < 3));
+        }
+    };
+    let selection = outcome.unwrap();
+    assert_eq!(selection.path, vec!["Main", "Deploy", "Production"]);
+    assert_eq!(selection.indexes, vec![1, 1]);
+    assert!(session.state().is_none());
+    // handlers run once for the key they get, however many keys follow.
+    assert_eq!(unhandled.get(), 1);
+
+    // An input `Item` takes the line typed at its prompt.
+    let mut session = menu.session(&opts, Writer::new(std::io::sink()));
+    let keys = [
+        KeyCode::Up,
+        KeyCode::Enter,
+        KeyCode::Char('8'),
+        KeyCode::Char('0'),
+        KeyCode::Char('8'),
+    ];
+    for code in keys
+        .into_iter()
+        .chain([KeyCode::Char('1'), KeyCode::Backspace, KeyCode::Char('0')])
+    {
+        assert!(session
+            .handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+            .is_none());
+        session.render().unwrap();
+    }
+    let outcome = session.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let selection = outcome.unwrap().unwrap();
+    assert_eq!(selection.path, vec!["Main", "Port"]);
+    assert_eq!(selection.value, Some(Value::U32(8080)));
+}
+
+#[test]
+fn menu_into_iter() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![action("Build"), action("Test")],
+        esc: true,
+    };
+    let names: Vec<&str> = (&menu).into_iter().map(Item::name).collect();
+    assert_eq!(names, ["Build", "Test"]);
+}
+
+#[test]
+fn item_path() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::SubMenu {
+            name: "File".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            items: vec![Item::Action {
+                name: "Save".to_string(),
+                hotkey: None,
+                exp: Some("Saves the file.".to_string()),
+                badges: Vec::new(),
+                confirm_phrase: None,
+                global_hotkey: None,
+                url: None,
+                checkable: false,
+            }],
+        }],
+        esc: true,
+    };
+    let opts = RunOptions {
+        item_path: true,
+        ..RunOptions::default()
+    };
+    let selection = menu
+        .run_with_stdin_values(&opts, &["Enter", "Enter"])
+        .unwrap();
+    let names: Vec<&str> = selection.item_path.iter().map(Item::name).collect();
+    assert_eq!(names, ["File", "Save"]);
+    assert_eq!(selection.item_path[1].exp(), Some("Saves the file."));
+}
+
+#[test]
+fn max_width() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::Action {
+            name: "Save".to_string(),
+            hotkey: None,
+            exp: Some("Saves the file to the disk, overwriting the previous version.".to_string()),
+            badges: Vec::new(),
+            confirm_phrase: None,
+            global_hotkey: None,
+            url: None,
+            checkable: false,
+        }],
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        max_width: Some(30),
+        border: true,
+        capabilities: Capabilities {
+            unicode: true,
+            color: true,
+            hyperlinks: false,
+        },
+        output: Output::Writer(Writer::new(buffer.clone())),
+        ..RunOptions::default()
+    };
+    menu.run_with_stdin_values(&opts, &["Enter"]).unwrap();
+    let output = buffer.text();
+    let bottom = output.lines().find(|f| f.contains('└')).unwrap();
+    // The border and the spaces inside it fit in 30 columns.
+    assert!(bottom.matches('─').count() + 2 <= 30);
+}
+
+#[test]
+fn fallback_width() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: Some("wrapped to the width assumed for the writer".to_string()),
+        items: vec![],
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        fallback_width: 20,
+        exp_below: true,
+        capabilities: Capabilities {
+            unicode: true,
+            color: false,
+            hyperlinks: false,
+        },
+        output: Output::Writer(Writer::new(buffer.clone())),
+        script: Some(Script::new(["Esc"])),
+        ..RunOptions::default()
+    };
+    menu.run_with(&opts).unwrap_err();
+    let output = buffer.text();
+    let lines: Vec<&str> = output.lines().skip(1).take(3).collect();
+    assert_eq!(lines, ["wrapped to the width", "assumed for the", "writer"]);
+}
+
+#[test]
+fn assumed_terminal() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: Some("an explanation long enough to be wrapped by the width".to_string()),
+        items: (0..8).map(|f| action(&format!("Item{}", f))).collect(),
+        esc: true,
+    };
+    // renders under the environment of a terminal `width` columns wide.
+    let render = |vars: [(&str, &str); 2], width| {
+        for (key, value) in vars {
+            std::env::set_var(key, value);
+        }
+        let opts = RunOptions {
+            exp_below: true,
+            border: true,
+            assumed_terminal: Some(AssumedTerminal::new(40, 5)),
+            ..RunOptions::default()
+        };
+        menu.render_to_string(&opts, width)
+    };
+    let dumb = render([("TERM", "dumb"), ("LANG", "C")], 30);
+    let capable = render([("TERM", "xterm-256color"), ("LANG", "en_US.UTF-8")], 120);
+    assert_eq!(dumb, capable);
+    assert!(dumb.contains("Item4") && !dumb.contains("Item5"));
+    // a Unicode border even under `LANG=C`.
+    assert!(dumb.starts_with("┌─ Main/"));
+}
+
+#[test]
+fn path_ellipsis() {
+    let mut items = vec![Item::Action {
+        name: "Save".to_string(),
+        hotkey: None,
+        exp: None,
+        badges: Vec::new(),
+        confirm_phrase: None,
+        global_hotkey: None,
+        url: None,
+        checkable: false,
+    }];
+    for name in ["Advanced", "Wireless", "Network", "Settings"] {
+        items = vec![Item::SubMenu {
+            name: name.to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            items,
+        }];
+    }
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items,
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        path_ellipsis: Some("…".to_string()),
+        max_width: Some(30),
+        output: Output::Writer(Writer::new(buffer.clone())),
+        script: Some(Script::new(["Esc"])),
+        ..RunOptions::default()
+    };
+    menu.run_at(&opts, &[0, 0, 0, 0]).unwrap_err();
+    let output = buffer.text();
+    assert!(output.contains("Main/…/Wireless/Advanced/"));
+    assert!(!output.contains("Settings"));
+}
+
+#[test]
+fn truncate_title() {
+    let menu = Menu {
+        name: "A very long menu name that overflows".to_string(),
+        exp: Some("And its explanation.".to_string()),
+        items: vec![Item::Action {
+            name: "Build".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            confirm_phrase: None,
+            global_hotkey: None,
+            url: None,
+            checkable: false,
+        }],
+        esc: true,
+    };
+    let opts = RunOptions {
+        truncate_title: true,
+        capabilities: Capabilities {
+            unicode: true,
+            color: false,
+            hyperlinks: false,
+        },
+        ..RunOptions::default()
+    };
+    let text = menu.render_to_string(&opts, 20);
+    assert_eq!(text.lines().next(), Some("A very long menu na…"));
+}
+
+#[test]
+fn position() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![action("Build"), action("Test"), action("Deploy")],
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        position: true,
+        output: Output::Writer(Writer::new(buffer.clone())),
+        ..RunOptions::default()
+    };
+    menu.run_with_stdin_values(&opts, &["Down", "Enter"])
+        .unwrap();
+    let output = buffer.text();
+    let first = output.find("1/3").unwrap();
+    assert!(output[first..].contains("2/3"));
+}
+
+#[test]
+fn sub_preview() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::SubMenu {
+            name: "Network".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            items: vec![action("Wi-Fi"), action("Ethernet"), action("Proxy")],
+        }],
+        esc: true,
+    };
+    let opts = RunOptions {
+        sub_preview: Some(2),
+        ..RunOptions::default()
+    };
+    let text = menu.render_to_string(&opts, 80);
+    assert!(text.contains("Wi-Fi, Ethernet, "));
+    assert!(!text.contains("Proxy"));
+    assert_eq!(
+        menu.rendered_height(&opts, 80) as usize,
+        text.lines().count()
+    );
+}
+
+#[test]
+fn right_descends_only() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            Item::Bool {
+                name: "Verbose".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                current: None,
+            },
+            action("Quit"),
+            Item::SubMenu {
+                name: "More".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                items: vec![action("About")],
+            },
+        ],
+        esc: true,
+    };
+    let mut opts = RunOptions {
+        right_descends_only: true,
+        ..RunOptions::default()
+    };
+    // `Right` on `Verbose` and `Quit` does nothing, then opens `More`.
+    let keys = ["Right", "Down", "Right", "Down", "Right", "Enter"];
+    assert_eq!(
+        menu.run_with_stdin_values(&opts, &keys).unwrap().name,
+        "About"
+    );
+    // `Enter` still opens `Verbose`.
+    let keys = ["Enter", "Enter"];
+    assert_eq!(
+        menu.run_with_stdin_values(&opts, &keys).unwrap().value,
+        Some(Value::Bool(true))
+    );
+
+    opts.right_descends_only = false;
+    let keys = ["Right", "Enter"];
+    assert_eq!(
+        menu.run_with_stdin_values(&opts, &keys).unwrap().value,
+        Some(Value::Bool(true))
+    );
+}
+
+#[test]
+fn default_action() {
+    let menu = Menu {
+        name: "Setup".to_string(),
+        exp: None,
+        items: vec![action("Back"), action("Skip"), action("Next")],
+        esc: true,
+    };
+    let opts = RunOptions {
+        default_action: Some(DefaultAction {
+            index: 2,
+            key: KeyCode::F(2),
+        }),
+        ..RunOptions::default()
+    };
+    assert!(menu.render_to_string(&opts, 80).contains("(F2) Next"));
+    // `Skip` is hovered when the key is pressed.
+    let selection = menu.run_with_stdin_values(&opts, &["Down", "F2"]).unwrap();
+    assert_eq!(selection.name, "Next");
+}
+
+#[test]
+fn submenu_selectable() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::SubMenu {
+            name: "Fruits".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            items: vec![Item::Action {
+                name: "Apple".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                confirm_phrase: None,
+                global_hotkey: None,
+                url: None,
+                checkable: false,
+            }],
+        }],
+        esc: true,
+    };
+    let opts = RunOptions::default();
+    let selection = menu
+        .run_with_stdin_values(&opts, &["Enter", "Enter"])
+        .unwrap();
+    assert_eq!(selection.path, ["Main", "Fruits", "Apple"]);
+
+    let opts = RunOptions {
+        submenu_selectable: true,
+        ..RunOptions::default()
+    };
+    let selection = menu.run_with_stdin_values(&opts, &["Enter"]).unwrap();
+    assert_eq!(
+        (selection.path, selection.indexes),
+        (vec!["Main".to_string(), "Fruits".to_string()], vec![0])
+    );
+    let selection = menu
+        .run_with_stdin_values(&opts, &["Right", "Enter"])
+        .unwrap();
+    assert_eq!(selection.name, "Apple");
+}
+
+#[test]
+fn min_select() {
+    let menu = Menu {
+        name: "Toppings".to_string(),
+        exp: None,
+        items: vec![
+            checkable("Olives"),
+            checkable("Peppers"),
+            checkable("Onions"),
+        ],
+        esc: true,
+    };
+    let opts = RunOptions {
+        min_select: Some(2),
+        max_select: Some(2),
+        ..RunOptions::default()
+    };
+    // confirming a single topping is blocked, and a third one can't be checked.
+    let script = ["Space", "Enter", "Down", "Space", "Down", "Space", "Enter"];
+    let selections = menu.run_checklist(&RunOptions {
+        script: Some(crate::Script::new(script)),
+        ..opts
+    });
+    let names: Vec<String> = selections.unwrap().into_iter().map(|f| f.name).collect();
+    assert_eq!(names, vec!["Olives", "Peppers"]);
+}
+
+#[test]
+fn locale() {
+    let menu = Menu {
+        name: "Ana".to_string(),
+        exp: None,
+        items: vec![Item::Action {
+            name: "Kaydet".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            confirm_phrase: None,
+            global_hotkey: None,
+            url: None,
+            checkable: false,
+        }],
+        esc: true,
+    };
+    let opts = RunOptions {
+        locale: Locale {
+            select: "Seç".to_string(),
+            exit: "Çık".to_string(),
+            ..Locale::default()
+        },
+        ..RunOptions::default()
+    };
+    let text = menu.render_to_string(&opts, 80);
+    assert!(text.contains("(Enter) Seç, (Esc) Çık"));
+    assert!(!text.contains("Select"));
+}
+
+#[test]
+fn sub_counts() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            Item::SubMenu {
+                name: "Settings".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                items: vec![action("Theme"), action("Font"), action("Language")],
+            },
+            Item::LazySubMenu {
+                name: "Files".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                provider: ItemsProvider::new(Vec::new),
+            },
+        ],
+        esc: true,
+    };
+    let opts = RunOptions {
+        sub_counts: true,
+        capabilities: Capabilities {
+            unicode: true,
+            color: false,
+            hyperlinks: false,
+        },
+        ..RunOptions::default()
+    };
+    let text = menu.render_to_string(&opts, 80);
+    let rows: Vec<&str> = text.lines().skip(1).take(2).collect();
+    assert_eq!(rows, ["0.    +Settings (3)", "1.    +Files (…)"]);
+}
+
+#[test]
+fn search() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            Item::SubMenu {
+                name: "More".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                items: vec![action("Bar"), action("Baz")],
+            },
+            action("Quit"),
+        ],
+        esc: true,
+    };
+    let opts = RunOptions {
+        search: true,
+        ..RunOptions::default()
+    };
+    // `zq` matches nothing, `z` matches `Baz`.
+    let keys = ["Enter", "/", "z", "q", "Backspace", "Enter"];
+    let selection = menu.run_with_stdin_values(&opts, &keys).unwrap();
+    assert_eq!(selection.path, ["Main", "More", "Baz"]);
+    // erasing `z`, closing the search, then going back.
+    let keys = [
+        "Enter",
+        "/",
+        "z",
+        "Backspace",
+        "Backspace",
+        "Backspace",
+        "Down",
+        "Enter",
+    ];
+    let selection = menu.run_with_stdin_values(&opts, &keys).unwrap();
+    assert_eq!(selection.path, ["Main", "Quit"]);
+}
+
+#[test]
+fn highlight_search() {
+    let menu = Menu {
+        name: "Fruits".to_string(),
+        exp: None,
+        items: vec![action("Cherry"), action("Banana")],
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        search: true,
+        capabilities: Capabilities {
+            unicode: false,
+            color: true,
+            hyperlinks: false,
+        },
+        output: Output::Writer(Writer::new(buffer.clone())),
+        script: Some(Script::new(["/", "A", "n", "a", "Enter"])),
+        ..RunOptions::default()
+    };
+    assert_eq!(menu.run_with(&opts).unwrap().name, "Banana");
+    let output = buffer.text();
+    let frames: Vec<&str> = output.split("\x1b[J").collect();
+    let row = |frame: &str| {
+        frame
+            .lines()
+            .nth(1)
+            .unwrap()
+            .split("     ")
+            .nth(1)
+            .unwrap()
+            .to_string()
+    };
+    let plain = "\x1b[38;5;14m\x1b[1m";
+    let underlined = "\x1b[38;5;14m\x1b[1m\x1b[4m";
+    // each `a` matches `A`.
+    let expected = format!("{plain}B\x1b[0m{underlined}a\x1b[0m{plain}n\x1b[0m{underlined}a\x1b[0m{plain}n\x1b[0m{underlined}a\x1b[0m");
+    assert!(row(frames[2]).starts_with(&expected));
+    // `an` matches twice, side by side.
+    assert!(row(frames[3]).starts_with(&format!(
+        "{plain}B\x1b[0m{underlined}anan\x1b[0m{plain}a\x1b[0m"
+    )));
+    // the two matches of `ana` overlap at the middle `a`.
+    assert!(row(frames[4]).starts_with(&format!("{plain}B\x1b[0m{underlined}anana\x1b[0m")));
+}
+
+#[test]
+fn search_mode() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            action("Restore Backup"),
+            action("Reset"),
+            action("Run Tests"),
+            action("Quit"),
+        ],
+        esc: true,
+    };
+    let opts = RunOptions {
+        search: true,
+        search_mode: SearchMode::Fuzzy,
+        ..RunOptions::default()
+    };
+    // "rt" is in none of the names, but "Run Tests" has it at the starts of its words.
+    let selection = menu
+        .run_with_stdin_values(&opts, &["/", "r", "t", "Enter"])
+        .unwrap();
+    assert_eq!(selection.name, "Run Tests");
+    // the next best match follows it.
+    let selection = menu
+        .run_with_stdin_values(&opts, &["/", "r", "t", "Down", "Enter"])
+        .unwrap();
+    assert_eq!(selection.name, "Restore Backup");
+}
+
+#[test]
+fn auto_accept_single() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![action("Debug"), action("Deploy"), action("Test")],
+        esc: true,
+    };
+    let opts = RunOptions {
+        search: true,
+        auto_accept_single: true,
+        ..RunOptions::default()
+    };
+    // `de` still matches two `Item`s, `dep` only `Deploy`, which is selected without `Enter`.
+    let selection = menu
+        .run_with_stdin_values(&opts, &["/", "d", "e", "p"])
+        .unwrap();
+    assert_eq!(selection.name, "Deploy");
+
+    // Otherwise `Deploy` is only hovered, so `Esc` closes the search and exits.
+    let opts = RunOptions {
+        auto_accept_single: false,
+        ..opts
+    };
+    let keys = ["/", "d", "e", "p", "Esc", "Esc"];
+    assert_eq!(
+        menu.run_with_stdin_values(&opts, &keys),
+        Err(MenuError::Exited)
+    );
+    let keys = ["/", "d", "e", "p", "Enter"];
+    assert_eq!(
+        menu.run_with_stdin_values(&opts, &keys).unwrap().name,
+        "Deploy"
+    );
+}
+
+#[test]
+fn path_jump() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            action("Open"),
+            action("Save"),
+            sub_menu(
+                "Settings",
+                vec![
+                    sub_menu("Display", vec![action("Light"), action("Dark")]),
+                    action("Reset"),
+                ],
+            ),
+        ],
+        esc: true,
+    };
+    let opts = RunOptions {
+        path_jump: true,
+        ..RunOptions::default()
+    };
+    let keys = ["2", ".", "0", ".", "1", "Enter"];
+    let selection = menu.run_with_stdin_values(&opts, &keys).unwrap();
+    assert_eq!(selection.path, ["Main", "Settings", "Display", "Dark"]);
+    assert_eq!(selection.indexes, [2, 0, 1]);
+
+    // there is no `Item` at 2.5, so the path is dropped and "Save" is jumped to instead.
+    let keys = ["2", ".", "5", "Enter", "1", "Enter"];
+    let selection = menu.run_with_stdin_values(&opts, &keys).unwrap();
+    assert_eq!(selection.name, "Save");
+
+    // going back from the jumped `SubMenu` returns to its parent.
+    let keys = ["2", ".", "0", "Enter", "Backspace", "1", "Enter"];
+    let selection = menu.run_with_stdin_values(&opts, &keys).unwrap();
+    assert_eq!(selection.name, "Reset");
+}
+
+#[test]
+fn hide_indexes() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![keyed("Build", 'b'), keyed("Quit", 'q')],
+        esc: true,
+    };
+    let mut opts = RunOptions {
+        hide_indexes: true,
+        ..RunOptions::default()
+    };
+    let text = menu.render_to_string(&opts, 80);
+    let rows: Vec<&str> = text.lines().skip(1).take(2).collect();
+    assert_eq!(rows, ["(B)  Build", "(Q)  Quit"]);
+    // `1` is ignored, and `Enter` selects the hovered `Build`.
+    assert_eq!(
+        menu.run_with_stdin_values(&opts, &["1", "Enter"])
+            .unwrap()
+            .name,
+        "Build"
+    );
+
+    opts.hide_indexes = false;
+    assert_eq!(
+        menu.run_with_stdin_values(&opts, &["1"]).unwrap().name,
+        "Quit"
+    );
+}
+
+#[test]
+fn number_actionable() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            group("File"),
+            action("Open"),
+            action("Save"),
+            group("Edit"),
+            action("Undo"),
+        ],
+        esc: true,
+    };
+    let opts = RunOptions {
+        number_actionable: true,
+        ..RunOptions::default()
+    };
+    let text = menu.render_to_string(&opts, 80);
+    let line = |name: &str| text.lines().find(|f| f.contains(name)).unwrap().to_string();
+    assert!(line("Edit").starts_with(' '));
+    assert!(line("Undo").starts_with("2."));
+    let selection = menu.run_with_stdin_values(&opts, &["2"]);
+    assert_eq!(selection.unwrap().name, "Undo");
+}
+
+#[test]
+fn scroll_margin() {
+    let numbered = |number: usize| action(&format!("Item{}", number));
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: (0..8).map(numbered).collect(),
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let mut keys = vec!["Down"; 7];
+    keys.push("Enter");
+    let opts = RunOptions {
+        max_rows: Some(4),
+        scroll_margin: 1,
+        capabilities: Capabilities {
+            unicode: false,
+            color: false,
+            hyperlinks: false,
+        },
+        output: Output::Writer(Writer::new(buffer.clone())),
+        script: Some(Script::new(keys)),
+        ..RunOptions::default()
+    };
+    assert_eq!(menu.run_with(&opts).unwrap().name, "Item7");
+    let output = buffer.text();
+    // The first displayed `Item` of each frame, while the hover moves from `Item0` to `Item7`.
+    let tops: Vec<&str> = output
+        .split("\x1b[J")
+        .filter_map(|f| f.lines().nth(1))
+        .collect();
+    // Scrolling starts as the hover reaches the row before the bottom edge, and stops at the end of the list.
+    let names = [
+        "Item0", "Item0", "Item0", "Item1", "Item2", "Item3", "Item4", "Item4",
+    ];
+    assert_eq!(tops.len(), names.len());
+    assert!(tops
+        .iter()
+        .zip(names)
+        .all(|(top, name)| top.ends_with(name)));
+}
+
+#[test]
+fn border() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            Item::Action {
+                name: "Build".to_string(),
+                hotkey: Some('b'),
+                exp: Some("Compiles".to_string()),
+                badges: Vec::new(),
+                confirm_phrase: None,
+                global_hotkey: None,
+                url: None,
+                checkable: false,
+            },
+            Item::Action {
+                name: "Quit".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                confirm_phrase: None,
+                global_hotkey: None,
+                url: None,
+                checkable: false,
+            },
+        ],
+        esc: true,
+    };
+    let mut opts = RunOptions {
+        border: true,
+        capabilities: Capabilities {
+            unicode: true,
+            color: false,
+            hyperlinks: false,
+        },
+        ..RunOptions::default()
+    };
+    let text = menu.render_to_string(&opts, 80);
+    let boxed: Vec<&str> = text.lines().take(4).collect();
+    assert_eq!(
+        boxed,
+        [
+            "┌─ Main/ ───────────────┐",
+            "│ 0.(B)  Build Compiles │",
+            "│ 1.     Quit           │",
+            "└───────────────────────┘",
+        ]
+    );
+    // ASCII lines without Unicode.
+    opts.capabilities.unicode = false;
+    let text = menu.render_to_string(&opts, 80);
+    assert_eq!(text.lines().next(), Some("+- Main/ ---------------+"));
+    assert_eq!(
+        menu.rendered_height(&opts, 80) as usize,
+        text.lines().count()
+    );
+}
+
+#[test]
+fn exp_below() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: Some("Tools for building and shipping the project.".to_string()),
+        items: vec![action("Build"), action("Ship")],
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        exp_below: true,
+        fallback_width: 20,
+        capabilities: Capabilities {
+            unicode: false,
+            color: false,
+            hyperlinks: false,
+        },
+        output: Output::Writer(Writer::new(buffer.clone())),
+        script: Some(Script::new(["Down", "Enter"])),
+        ..RunOptions::default()
+    };
+    assert_eq!(menu.run_with(&opts).unwrap().name, "Ship");
+    let output = buffer.text();
+    let frames: Vec<&str> = output.split("\x1b[J").collect();
+    let lines: Vec<&str> = frames[0].lines().take(5).collect();
+    assert_eq!(
+        lines,
+        [
+            "Main/",
+            "Tools for building",
+            "and shipping the",
+            "project.",
+            "0.     Build"
+        ]
+    );
+    // The redraw moves up over the top line, the 3 lines of the paragraph, the 2 items, and the legend and hint wrapped into 6 lines.
+    assert!(frames[0].ends_with("\x1b[12A"));
+    assert!(frames[1].ends_with("\x1b[12A"));
+}
+
+#[test]
+fn tab_navigation() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![action("Build"), action("Test"), action("Deploy")],
+        esc: true,
+    };
+    let opts = RunOptions {
+        tab_navigation: true,
+        ..RunOptions::default()
+    };
+    let selection = menu.run_with_stdin_values(&opts, &["Tab", "Tab", "Enter"]);
+    assert_eq!(selection.unwrap().name, "Deploy");
+    let selection = menu.run_with_stdin_values(&opts, &["Tab", "Tab", "BackTab", "Enter"]);
+    assert_eq!(selection.unwrap().name, "Test");
+}
+
+#[test]
+fn start_unfocused() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![action("Build"), action("Test"), action("Deploy")],
+        esc: true,
+    };
+    let opts = RunOptions {
+        start_unfocused: true,
+        ..RunOptions::default()
+    };
+    // The first `Enter` does nothing.
+    let selection = menu.run_with_stdin_values(&opts, &["Enter", "Down", "Enter"]);
+    assert_eq!(selection.unwrap().name, "Build");
+    let selection = menu.run_with_stdin_values(&opts, &["Up", "Enter"]);
+    assert_eq!(selection.unwrap().name, "Deploy");
+    // A `MenuSession` gives no hover until a key moves it.
+    let mut session = menu.session(&opts, Writer::new(std::io::sink()));
+    assert_eq!(session.state().unwrap().hover, None);
+    session.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    assert_eq!(session.state().unwrap().hover, Some(0));
+}
+
+#[test]
+fn debounce() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: ["A", "B", "C", "D", "E"].map(action).to_vec(),
+        esc: true,
+    };
+    // a burst of four `Down`s, then `Enter`.
+    let run = |debounce| {
+        let opts = RunOptions {
+            debounce,
+            input: Input::Reader(Reader::new(Cursor::new(
+                b"\x1b[B\x1b[B\x1b[B\x1b[B\r".to_vec(),
+            ))),
+            output: Output::Writer(Writer::new(Vec::new())),
+            ..RunOptions::default()
+        };
+        menu.run_with(&opts).unwrap().name
+    };
+    assert_eq!(run(Some(Duration::from_secs(5))), "B");
+    assert_eq!(run(None), "E");
+}
+
+#[test]
+fn animation() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::SubMenu {
+            name: "Settings".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            items: vec![Item::Action {
+                name: "Reset".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                confirm_phrase: None,
+                global_hotkey: None,
+                url: None,
+                checkable: false,
+            }],
+        }],
+        esc: true,
+    };
+    let opts = RunOptions {
+        animation: Some(Duration::from_secs(10)),
+        ..RunOptions::default()
+    };
+    let start = Instant::now();
+    // entering, leaving and entering again.
+    let selection = menu.run_with_stdin_values(&opts, &["Enter", "Left", "Enter", "Enter"]);
+    assert_eq!(selection.unwrap().name, "Reset");
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(750) && elapsed < Duration::from_secs(5));
+}
+
+#[test]
+fn on_unhandled_key() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::Action {
+            name: "Build".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            confirm_phrase: None,
+            global_hotkey: None,
+            url: None,
+            checkable: false,
+        }],
+        esc: true,
+    };
+    let seen = Rc::new(Cell::new(0));
+    let counted = seen.clone();
+    let opts = RunOptions {
+        on_unhandled_key: Some(KeyHandler::new(move |event| {
+            counted.set(counted.get() + 1);
+            match event.code {
+                KeyCode::F(5) => KeyFlow::Exit("Refresh".to_string()),
+                KeyCode::F(6) => KeyFlow::Consume,
+                _ => KeyFlow::Ignore,
+            }
+        })),
+        script: Some(Script::new(["F6", "F7", "Down", "F5"])),
+        ..RunOptions::default()
+    };
+    assert_eq!(
+        menu.run_with(&opts),
+        Err(MenuError::Run("Refresh".to_string()))
+    );
+    // `Down` is used by `Menu`.
+    assert_eq!(seen.get(), 3);
+}
+
+#[test]
+fn detail_height() {
+    let menu = Menu {
+        name: "Settings".to_string(),
+        exp: None,
+        items: vec![
+            explained("Theme", "Colors of the interface."),
+            explained("Fonts", "Typefaces and their sizes."),
+        ],
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        detail_height: Some(1),
+        capabilities: Capabilities {
+            unicode: false,
+            color: false,
+            hyperlinks: false,
+        },
+        output: Output::Writer(Writer::new(buffer.clone())),
+        script: Some(Script::new(["Down", "Enter"])),
+        ..RunOptions::default()
+    };
+    assert_eq!(menu.run_with(&opts).unwrap().name, "Fonts");
+    let output = buffer.text();
+    let frames: Vec<&str> = output.split("\x1b[J").collect();
+    // The detail line follows the hover, and the redraw clears it with the rest of the frame.
+    assert_eq!(frames[0].lines().nth(3), Some("Colors of the interface."));
+    assert_eq!(frames[1].lines().nth(3), Some("Typefaces and their sizes."));
+    assert!(frames[0].ends_with("\x1b[6A"));
+}
+
+#[test]
+fn allow_non_finite() {
+    let menu = Menu {
+        name: "Scale".to_string(),
+        exp: None,
+        items: vec![Item::F64 {
+            name: "Factor".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            decimals: None,
+            round: false,
+            skippable: false,
+            current: None,
+            confirm: false,
+            step: None,
+        }],
+        esc: true,
+    };
+    let opts = RunOptions::default();
+    let selection = menu
+        .run_with_stdin_values(&opts, &["Enter", "inf", "nan", "2.5"])
+        .unwrap();
+    assert_eq!(
+        (selection.value, selection.attempt),
+        (Some(Value::F64(2.5)), Some(3))
+    );
+
+    let opts = RunOptions {
+        allow_non_finite: true,
+        ..RunOptions::default()
+    };
+    let selection = menu
+        .run_with_stdin_values(&opts, &["Enter", "-inf"])
+        .unwrap();
+    assert_eq!(selection.value, Some(Value::F64(f64::NEG_INFINITY)));
+    let selection = menu
+        .run_with_stdin_values(&opts, &["Enter", "nan"])
+        .unwrap();
+    assert!(matches!(selection.value, Some(Value::F64(value)) if value.is_nan()));
+
+    let opts = RunOptions {
+        positive_zero: true,
+        ..RunOptions::default()
+    };
+    let selection = menu
+        .run_with_stdin_values(&opts, &["Enter", "-0.0"])
+        .unwrap();
+    assert!(matches!(selection.value, Some(Value::F64(value)) if value.is_sign_positive()));
+}
+
+#[test]
+fn trim() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::String {
+            name: "Motto".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            skippable: false,
+            required: false,
+            history: vec![],
+            current: None,
+        }],
+        esc: true,
+    };
+    let value = |trim| {
+        let opts = RunOptions {
+            trim,
+            ..RunOptions::default()
+        };
+        menu.run_with_stdin_values(&opts, &["Enter", "  carpe diem  "])
+            .unwrap()
+            .value
+    };
+    assert_eq!(
+        value(TrimPolicy::None),
+        Some(Value::String("  carpe diem  ".to_string()))
+    );
+    assert_eq!(
+        value(TrimPolicy::End),
+        Some(Value::String("  carpe diem".to_string()))
+    );
+    assert_eq!(
+        value(TrimPolicy::Both),
+        Some(Value::String("carpe diem".to_string()))
+    );
+}
+
+#[test]
+fn type_hint() {
+    let menu = Menu {
+        name: "Order".to_string(),
+        exp: None,
+        items: vec![Item::I32 {
+            name: "Quantity".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            skippable: false,
+            current: None,
+            confirm: false,
+            step: None,
+        }],
+        esc: true,
+    };
+    let prompt = |type_hint: TypeHint| {
+        let buffer = Buffer::default();
+        let opts = RunOptions {
+            type_hint,
+            capabilities: Capabilities {
+                unicode: true,
+                color: false,
+                hyperlinks: false,
+            },
+            output: Output::Writer(Writer::new(buffer.clone())),
+            script: Some(Script::new(["Enter", "3", "Enter"])),
+            ..RunOptions::default()
+        };
+        menu.run_with(&opts).unwrap();
+        buffer.text()
+    };
+    assert!(prompt(TypeHint::Raw).contains("Enter a value. Type: I32"));
+    assert!(prompt(TypeHint::Friendly).contains("Enter a value. Type: whole number"));
+    let hidden = prompt(TypeHint::Hidden);
+    assert!(hidden.contains("Enter a value.") && !hidden.contains("Type:"));
+}
+
+#[test]
+fn normalize() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::Char {
+            name: "Grade".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            skippable: false,
+            current: None,
+            allowed: Some(vec!['A', 'B', 'C']),
+            ignore_case: false,
+        }],
+        esc: true,
+    };
+    let opts = RunOptions {
+        normalize: Some(Normalizer::new(|item, input| match item.name() {
+            "Grade" => input.to_uppercase(),
+            _ => input,
+        })),
+        ..RunOptions::default()
+    };
+    // `b` isn't allowed as typed, but its normalized `B` is.
+    let selection = menu.run_with_stdin_values(&opts, &["Enter", "b"]).unwrap();
+    assert_eq!(
+        (selection.value, selection.attempt),
+        (Some(Value::Char('B')), Some(1))
+    );
+}
+
+#[test]
+fn hover_marker() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![action("Open"), action("Save")],
+        esc: true,
+    };
+    let opts = RunOptions {
+        hover_marker: Some("> ".to_string()),
+        ..RunOptions::default()
+    };
+    let rendered = menu.render_to_string(&opts, 80);
+    let items: Vec<&str> = rendered.lines().skip(1).take(2).collect();
+    assert_eq!(items, ["> 0.     Open", "  1.     Save"]);
+
+    // Without colors, the marker follows the hover.
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        capabilities: Capabilities {
+            unicode: false,
+            color: false,
+            hyperlinks: false,
+        },
+        output: Output::Writer(Writer::new(buffer.clone())),
+        script: Some(Script::new(["Down", "Enter"])),
+        ..opts
+    };
+    menu.run_with(&opts).unwrap();
+    let output = buffer.text();
+    let frame = output.split("\x1b[J").nth(1).unwrap();
+    let items: Vec<&str> = frame.lines().skip(1).take(2).collect();
+    assert_eq!(items, ["  0.     Open", "> 1.     Save"]);
+}
+
+#[test]
+fn exp_toggle_key() {
+    use crossterm::event::KeyCode;
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            explained("Theme", "Colors."),
+            Item::SubMenu {
+                name: "More".to_string(),
+                hotkey: None,
+                exp: Some("Other settings.".to_string()),
+                badges: Vec::new(),
+                items: vec![explained("Fonts", "Typefaces.")],
+            },
+        ],
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        exp_toggle_key: Some(KeyCode::F(1)),
+        capabilities: Capabilities {
+            unicode: false,
+            color: false,
+            hyperlinks: false,
+        },
+        output: Output::Writer(Writer::new(buffer.clone())),
+        script: Some(Script::new([
+            "F1", "F1", "F1", "Down", "Enter", "F1", "Enter",
+        ])),
+        ..RunOptions::default()
+    };
+    assert_eq!(menu.run_with(&opts).unwrap().name, "Fonts");
+    let output = buffer.text();
+    let frames: Vec<&str> = output.split("\x1b[J").collect();
+    let theme = |frame: &str| frame.lines().nth(1).unwrap().to_string();
+    assert_eq!(theme(frames[0]), "0.     Theme Colors.");
+    // Hidden, shown again, and hidden again.
+    assert_eq!(theme(frames[1]), "0.     Theme");
+    assert_eq!(theme(frames[2]), "0.     Theme Colors.");
+    assert_eq!(theme(frames[3]), "0.     Theme");
+    // Still hidden in the `SubMenu`, until shown there.
+    assert_eq!(theme(frames[5]), "0.     Fonts");
+    assert_eq!(theme(frames[6]), "0.     Fonts Typefaces.");
+}
+
+#[test]
+fn mask_exp() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::Action {
+            name: "Deploy".to_string(),
+            hotkey: None,
+            exp: Some("token abcd1234efgh".to_string()),
+            badges: Vec::new(),
+            confirm_phrase: None,
+            global_hotkey: None,
+            url: None,
+            checkable: false,
+        }],
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        mask_exp: Some(ExpMask::new(|item| item.name() == "Deploy")),
+        reveal_key: Some(KeyCode::F(2)),
+        output: Output::Writer(Writer::new(buffer.clone())),
+        script: Some(Script::new(["F2", "Enter"])),
+        ..RunOptions::default()
+    };
+    assert!(menu
+        .render_to_string(&opts, 80)
+        .contains("toke**********efgh"));
+    assert_eq!(menu.run_with(&opts).unwrap().name, "Deploy");
+    let output = buffer.text();
+    // masked in the first frame, and revealed after `F2`.
+    let masked = output.find("toke**********efgh").unwrap();
+    assert!(output[masked..].contains("token abcd1234efgh"));
+}
+
+#[test]
+fn undo_key() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            action("Open"),
+            action("Save"),
+            Item::SubMenu {
+                name: "Settings".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                items: vec![action("Theme"), action("Font")],
+            },
+        ],
+        esc: true,
+    };
+    let opts = RunOptions {
+        undo_key: Some(KeyCode::F(3)),
+        script: Some(Script::new([
+            "Down", "Down", "Enter", "Down", "F3", "F3", "F3", "Enter",
+        ])),
+        ..RunOptions::default()
+    };
+    // the first undo moves back to "Theme", the second leaves "Settings" and the third moves up to "Save".
+    assert_eq!(menu.run_with(&opts).unwrap().name, "Save");
+}
+
+#[test]
+fn hotkey_position() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![keyed("Build", 'b'), keyed("Deploy", 'd')],
+        esc: true,
+    };
+    let text = menu.render_to_string(&RunOptions::default(), 80);
+    assert!(text.contains("0.(B)  Build\n"));
+    assert!(text.contains("1.(D)  Deploy\n"));
+    let opts = RunOptions {
+        hotkey_position: HotkeyPosition::After,
+        ..RunOptions::default()
+    };
+    let text = menu.render_to_string(&opts, 80);
+    assert!(text.contains("0.  Build  (B)\n"));
+    assert!(text.contains("1.  Deploy (D)\n"));
+}
+
+#[test]
+fn auto_hotkeys() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            action("Build"),
+            action("Bundle"),
+            keyed("Test", 'u'),
+            action("Bub"),
+        ],
+        esc: true,
+    };
+    let opts = RunOptions {
+        auto_hotkeys: true,
+        capabilities: Capabilities {
+            unicode: true,
+            color: false,
+            hyperlinks: false,
+        },
+        ..RunOptions::default()
+    };
+    // `B` is taken by `Build`, and `U` by `Test`, so `Bundle` gets `N`.
+    let text = menu.render_to_string(&opts, 80);
+    assert!(text.contains("0.(B)  Build\n1.(N)  Bundle\n2.(U)  Test\n3.     Bub\n"));
+    let selection = menu.run_with_stdin_values(&opts, &["n"]);
+    assert_eq!(selection.unwrap().name, "Bundle");
+}
+
+#[test]
+fn confirm_exit() {
+    let menu = Menu {
+        name: "Editor".to_string(),
+        exp: None,
+        items: vec![Item::Action {
+            name: "Save".to_string(),
+            hotkey: Some('s'),
+            exp: None,
+            badges: Vec::new(),
+            confirm_phrase: None,
+            global_hotkey: None,
+            url: None,
+            checkable: false,
+        }],
+        esc: true,
+    };
+    let unsaved = RunOptions {
+        confirm_exit: Some(ExitGuard::new(|| true)),
+        script: Some(Script::new(["Esc", "n", "Esc", "y"])),
+        ..RunOptions::default()
+    };
+    assert_eq!(menu.run_with(&unsaved), Err(MenuError::Exited));
+    let saved = RunOptions {
+        confirm_exit: Some(ExitGuard::new(|| false)),
+        script: Some(Script::new(["Esc"])),
+        ..RunOptions::default()
+    };
+    assert_eq!(menu.run_with(&saved), Err(MenuError::Exited));
+    // the hint names the keys of the `Locale`.
+    let buffer = Buffer::default();
+    let localized = RunOptions {
+        locale: Locale {
+            confirm_exit: "Trotzdem beenden?".to_string(),
+            yes: 'j',
+            ..Locale::default()
+        },
+        output: Output::Writer(Writer::new(buffer.clone())),
+        script: Some(Script::new(["Esc", "j"])),
+        ..unsaved
+    };
+    assert_eq!(menu.run_with(&localized), Err(MenuError::Exited));
+    assert!(buffer.text().contains("Trotzdem beenden? (j/n)"));
+}
+
+#[test]
+fn load_ui_state() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![group("File"), action("Open"), group("Edit"), action("Undo")],
+        esc: true,
+    };
+    let mut stored = UiState::default();
+    stored.set_collapsed("Main/File", true);
+    let opts = RunOptions {
+        load_ui_state: Some(UiStateLoader::new(move || stored.clone())),
+        capabilities: Capabilities {
+            unicode: true,
+            color: false,
+            hyperlinks: false,
+        },
+        ..RunOptions::default()
+    };
+    let text = menu.render_to_string(&opts, 80);
+    assert!(!text.contains("Open") && text.contains("Undo"));
+}
+
+#[test]
+fn save_ui_state() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            Item::Group {
+                name: "Files/Dirs".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                collapsed: false,
+            },
+            Item::Action {
+                name: "Open".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                confirm_phrase: None,
+                global_hotkey: None,
+                url: None,
+                checkable: false,
+            },
+        ],
+        esc: true,
+    };
+    // a `Group` of another `Menu`, stored by an earlier run.
+    let stored = Rc::new(RefCell::new(UiState::default()));
+    stored.borrow_mut().set_collapsed("Tools/Network", true);
+    let (load, store) = (stored.clone(), stored.clone());
+    let opts = RunOptions {
+        load_ui_state: Some(UiStateLoader::new(move || load.borrow().clone())),
+        save_ui_state: Some(UiStateSaver::new(move |f| *store.borrow_mut() = f.clone())),
+        ..RunOptions::default()
+    };
+    menu.run_with_stdin_values(&opts, &["Enter", "Esc"])
+        .unwrap_err();
+    // `/` in a name is escaped, so it's told apart from the `/` between names.
+    assert_eq!(stored.borrow().collapsed("Main/Files\\/Dirs"), Some(true));
+    assert_eq!(stored.borrow().collapsed("Tools/Network"), Some(true));
+
+    // Without `load_ui_state`, only the `Group`s toggled in this run are saved.
+    let store = stored.clone();
+    let opts = RunOptions {
+        save_ui_state: Some(UiStateSaver::new(move |f| *store.borrow_mut() = f.clone())),
+        ..RunOptions::default()
+    };
+    menu.run_with_stdin_values(&opts, &["Enter", "Esc"])
+        .unwrap_err();
+    assert_eq!(stored.borrow().collapsed("Main/Files\\/Dirs"), Some(true));
+    assert_eq!(stored.borrow().collapsed("Tools/Network"), None);
+}
+
+#[test]
+fn stay() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![action("Refresh"), action("Quit")],
+        esc: true,
+    };
+    let refreshes = Rc::new(Cell::new(0));
+    let counted = refreshes.clone();
+    let opts = RunOptions {
+        stay: Some(StayHandler::new(move |selection| {
+            let stay = selection.name == "Refresh";
+            if stay {
+                counted.set(counted.get() + 1);
+            }
+            stay
+        })),
+        script: Some(Script::new(["Enter", "Enter", "Down", "Enter"])),
+        ..RunOptions::default()
+    };
+    assert_eq!(menu.run_with(&opts).unwrap().name, "Quit");
+    assert_eq!(refreshes.get(), 2);
+}
+
+#[test]
+fn stay_nested() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            sub_menu("Settings", vec![action("Verbose"), action("Done")]),
+            action("Verbose"),
+        ],
+        esc: true,
+    };
+    let asked = Rc::new(RefCell::new(vec![]));
+    let seen = asked.clone();
+    let run = |keys: &[&str]| {
+        let seen = seen.clone();
+        let opts = RunOptions {
+            // only `Settings > Verbose` stays, told apart from `Verbose` by its index path.
+            stay: Some(StayHandler::new(move |selection| {
+                seen.borrow_mut().push(selection.indexes.clone());
+                selection.indexes == [0, 0]
+            })),
+            script: Some(Script::new(keys.iter().copied())),
+            ..RunOptions::default()
+        };
+        menu.run_with(&opts).unwrap().indexes
+    };
+    assert_eq!(run(&["Enter", "Enter", "Down", "Enter"]), [0, 1]);
+    assert_eq!(*asked.borrow(), [vec![0, 0], vec![0, 1]]);
+    asked.borrow_mut().clear();
+    assert_eq!(run(&["Down", "Enter"]), [1]);
+    assert_eq!(*asked.borrow(), [vec![1]]);
+}
+
+#[test]
+fn stay_in_submenu() {
+    let mut menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            sub_menu(
+                "Tools",
+                vec![sub_menu("Network", vec![action("Ping"), action("Trace")])],
+            ),
+            action("Quit"),
+        ],
+        esc: true,
+    };
+    let opts = RunOptions {
+        stay_in_submenu: true,
+        // `Down` and `Enter` would select `Quit` at the top level.
+        script: Some(Script::new([
+            "Enter", "Enter", "Enter", "Down", "Enter", "Esc",
+        ])),
+        ..RunOptions::default()
+    };
+    let mut selected = vec![];
+    menu.run_loop(&opts, |selection, _| {
+        selected.push(selection.path.join("/"))
+    })
+    .unwrap();
+    assert_eq!(
+        selected,
+        vec!["Main/Tools/Network/Ping", "Main/Tools/Network/Trace"]
+    );
+}
+
+#[test]
+fn dry_run() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::Action {
+            name: "Deploy".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            confirm_phrase: None,
+            global_hotkey: None,
+            url: None,
+            checkable: false,
+        }],
+        esc: true,
+    };
+    let runs = Rc::new(Cell::new(0));
+    let counted = runs.clone();
+    let mut opts = RunOptions {
+        stay: Some(StayHandler::new(move |_| {
+            counted.set(counted.get() + 1);
+            true
+        })),
+        dry_run: DryRun::Silent,
+        script: Some(Script::new(["Enter"])),
+        ..RunOptions::default()
+    };
+    assert_eq!(menu.run_with(&opts).unwrap().name, "Deploy");
+    assert_eq!(runs.get(), 0);
+
+    let mut menu = menu;
+    let mut deployed = 0;
+    opts.script = Some(Script::new(["Enter", "Enter", "Esc"]));
+    menu.run_loop(&opts, |_, _| deployed += 1).unwrap();
+    assert_eq!((deployed, runs.get()), (0, 0));
+
+    // Each selection is reported once.
+    let buffer = Buffer::default();
+    opts.dry_run = DryRun::Report;
+    opts.output = Output::Writer(Writer::new(buffer.clone()));
+    opts.script = Some(Script::new(["Enter"]));
+    menu.run_with(&opts).unwrap();
+    let output = buffer.text();
+    assert_eq!(output.matches("Would run: Main/Deploy").count(), 1);
+}
+
+#[test]
+fn confirm_discard() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            Item::SubMenu {
+                name: "Form".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                items: vec![Item::U32 {
+                    name: "Age".to_string(),
+                    hotkey: None,
+                    exp: None,
+                    badges: Vec::new(),
+                    skippable: false,
+                    current: None,
+                    confirm: false,
+                    step: None,
+                }],
+            },
+            Item::Action {
+                name: "Quit".to_string(),
+                hotkey: Some('q'),
+                exp: None,
+                badges: Vec::new(),
+                confirm_phrase: None,
+                global_hotkey: None,
+                url: None,
+                checkable: false,
+            },
+        ],
+        esc: true,
+    };
+    let run = |keys: &[&str]| {
+        let opts = RunOptions {
+            confirm_discard: true,
+            stay: Some(StayHandler::new(|selection| selection.value.is_some())),
+            script: Some(Script::new(keys.iter().copied())),
+            ..RunOptions::default()
+        };
+        menu.run_with(&opts).map(|f| f.name)
+    };
+    // After entering a value, `n` keeps the form open, and `q` doesn't reach `Quit`.
+    let keys = [
+        "Enter",
+        "Enter",
+        "42",
+        "Backspace",
+        "n",
+        "q",
+        "Backspace",
+        "y",
+        "q",
+    ];
+    assert_eq!(run(&keys), Ok("Quit".to_string()));
+    // Without entered values it goes back directly.
+    assert_eq!(run(&["Enter", "Backspace", "q"]), Ok("Quit".to_string()));
+    // the hint names the keys of the `Locale`.
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        confirm_discard: true,
+        stay: Some(StayHandler::new(|selection| selection.value.is_some())),
+        locale: Locale {
+            discard_entries: "Eingaben verwerfen?".to_string(),
+            yes: 'j',
+            ..Locale::default()
+        },
+        output: Output::Writer(Writer::new(buffer.clone())),
+        script: Some(Script::new(["Enter", "Enter", "42", "Backspace", "j", "q"])),
+        ..RunOptions::default()
+    };
+    assert_eq!(menu.run_with(&opts).unwrap().name, "Quit");
+    assert!(buffer.text().contains("Eingaben verwerfen? (j/n)"));
+}
+
+#[test]
+fn splash() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::Action {
+            name: "Start".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            confirm_phrase: None,
+            global_hotkey: None,
+            url: None,
+            checkable: false,
+        }],
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        splash: Some("Welcome to Rushterm\nversion 1.1".to_string()),
+        output: Output::Writer(Writer::new(buffer.clone())),
+        // `x` dismisses the splash, so `Enter` reaches the `Menu`.
+        script: Some(Script::new(["x", "Enter"])),
+        ..RunOptions::default()
+    };
+    assert_eq!(menu.run_with(&opts).unwrap().name, "Start");
+    let output = buffer.text();
+    // the text and the key hint are erased before the `Menu` is printed.
+    let splash = output.find("version 1.1").unwrap();
+    let erase = output.find("\u{1b}[3A\u{1b}[J").unwrap();
+    assert!(splash < erase && erase < output.find("Start").unwrap());
+}
+
+#[test]
+fn capabilities() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: Some("Pick one".to_string()),
+        items: vec![Item::Group {
+            name: "Tools".to_string(),
+            hotkey: Some('t'),
+            exp: None,
+            badges: Vec::new(),
+            collapsed: false,
+        }],
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        capabilities: Capabilities::from_env(Some("dumb"), None),
+        border: true,
+        output: Output::Writer(Writer::new(buffer.clone())),
+        script: Some(Script::new(["Esc"])),
+        ..RunOptions::default()
+    };
+    menu.run_with(&opts).unwrap_err();
+    let output = buffer.text();
+    assert!(output.is_ascii());
+    assert!(output.contains("+- Main/ Pick one"));
+    assert!(output.contains("v Tools"));
+    assert!(!output.contains("\u{1b}[38;"));
+}
+
+#[test]
+fn input() {
+    let menu = Menu {
+        name: "Remote".to_string(),
+        exp: None,
+        items: vec![
+            Item::Action {
+                name: "Disconnect".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                confirm_phrase: None,
+                global_hotkey: None,
+                url: None,
+                checkable: false,
+            },
+            Item::U32 {
+                name: "Port".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                skippable: false,
+                current: None,
+                confirm: false,
+                step: None,
+            },
+        ],
+        esc: true,
+    };
+    // the bytes a terminal in raw mode sends for `Down`, `Enter`, then "2x", `Backspace`, "2" and `Enter`.
+    let typed = b"\x1b[B\r2x\x7f2\r".to_vec();
+    let screen = Buffer::default();
+    let opts = RunOptions {
+        input: Input::Reader(Reader::new(Cursor::new(typed))),
+        output: Output::Writer(Writer::new(screen.clone())),
+        ..RunOptions::default()
+    };
+    let selection = menu.run_with(&opts).unwrap();
+    assert_eq!(selection.value, Some(Value::U32(22)));
+    // the typed line is echoed, with the erased char rubbed out.
+    let output = screen.text();
+    assert!(output.contains("2x\x08 \x082"));
+}
+
+/// Runs the only `Item` of the `Menu`, typing "a", a paste of two lines, "d" and `Enter`.
+fn run_pasted(item: Item) -> Option<Value> {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![item],
+        esc: true,
+    };
+    let typed = b"\ra\x1b[200~b\r\nc\x1b[201~d\r".to_vec();
+    let opts = RunOptions {
+        input: Input::Reader(Reader::new(Cursor::new(typed))),
+        output: Output::Writer(Writer::new(Vec::new())),
+        ..RunOptions::default()
+    };
+    menu.run_with(&opts).unwrap().value
+}
+
+#[test]
+fn paste_history() {
+    let item = Item::String {
+        name: "Note".to_string(),
+        hotkey: None,
+        exp: None,
+        badges: Vec::new(),
+        skippable: false,
+        required: false,
+        history: vec!["old".to_string()],
+        current: None,
+    };
+    assert_eq!(run_pasted(item), Some(Value::String("ab  cd".to_string())));
+}
+
+#[test]
+fn paste_secret() {
+    let item = Item::Secret {
+        name: "Token".to_string(),
+        hotkey: None,
+        exp: None,
+        badges: Vec::new(),
+        skippable: false,
+        required: false,
+    };
+    match run_pasted(item) {
+        Some(Value::Secret(secret)) => assert_eq!(secret.expose(), "ab  cd"),
+        value => panic!("not a secret: {:?}", value),
+    }
+}
+
+#[test]
+fn skip_signed() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::I32 {
+            name: "Offset".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            skippable: true,
+            current: None,
+            confirm: false,
+            step: None,
+        }],
+        esc: true,
+    };
+    // a lone sign prompts again instead of skipping.
+    let opts = RunOptions::default();
+    let selection = menu
+        .run_with_stdin_values(&opts, &["Enter", "-", "-42"])
+        .unwrap();
+    assert_eq!(selection.value, Some(Value::I32(-42)));
+    assert_eq!((selection.attempt, selection.skipped), (Some(2), false));
+    let opts = RunOptions {
+        skip_sentinel: "n/a".to_string(),
+        ..RunOptions::default()
+    };
+    let selection = menu
+        .run_with_stdin_values(&opts, &["Enter", "n/a"])
+        .unwrap();
+    assert_eq!((selection.value, selection.skipped), (None, true));
+}
+
+#[test]
+fn hotkey_policy() {
+    let menu = Menu {
+        name: "Edit".to_string(),
+        exp: None,
+        items: vec![keyed("Paste", 'p'), keyed("Copy", 'c'), keyed("Cut", 'c')],
+        esc: true,
+    };
+    let selected = |policy: HotkeyPolicy, values: &[&str]| {
+        let opts = RunOptions {
+            hotkey_policy: policy,
+            ..RunOptions::default()
+        };
+        menu.run_with_stdin_values(&opts, values).map(|f| f.name)
+    };
+    assert_eq!(
+        selected(HotkeyPolicy::FirstMatch, &["c"]),
+        Ok("Copy".to_string())
+    );
+    // Each press hovers the next `Item` with the hotkey, starting over after the last.
+    assert_eq!(
+        selected(HotkeyPolicy::Cycle, &["c", "Enter"]),
+        Ok("Copy".to_string())
+    );
+    assert_eq!(
+        selected(HotkeyPolicy::Cycle, &["c", "c", "Enter"]),
+        Ok("Cut".to_string())
+    );
+    assert_eq!(
+        selected(HotkeyPolicy::Cycle, &["c", "c", "c", "Enter"]),
+        Ok("Copy".to_string())
+    );
+    let err = selected(HotkeyPolicy::Error, &["c"]).unwrap_err();
+    assert!(matches!(err, MenuError::AmbiguousHotkey('c')));
+    // A hotkey of a single `Item` selects it under every policy.
+    assert_eq!(
+        selected(HotkeyPolicy::Error, &["p"]),
+        Ok("Paste".to_string())
+    );
+}
+
+#[test]
+fn layout_grid() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: ["Red", "Green", "Blue", "Cyan", "Pink"]
+            .map(action)
+            .to_vec(),
+        esc: true,
+    };
+    let mut opts = RunOptions {
+        layout: Layout::Grid { columns: 2 },
+        ..RunOptions::default()
+    };
+    let grid = menu.render_to_string(&opts, 40);
+    let rows: Vec<&str> = grid.lines().skip(1).take(3).map(str::trim_end).collect();
+    assert_eq!(
+        rows,
+        [
+            "0.     Red    3.     Cyan",
+            "1.     Green  4.     Pink",
+            "2.     Blue"
+        ]
+    );
+    // a single column fits in 12 columns of terminal.
+    let narrow = menu.render_to_string(&opts, 12);
+    assert_eq!(
+        narrow.lines().nth(5).map(str::trim_end),
+        Some("4.     Pink")
+    );
+
+    opts.script = Some(Script::new(["Down", "Right", "Enter"]));
+    assert_eq!(menu.run_with(&opts).unwrap().name, "Pink");
+}
+
+#[test]
+fn selection_attempt() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::U32 {
+            name: "Port".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            skippable: false,
+            current: None,
+            confirm: false,
+            step: None,
+        }],
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        capabilities: Capabilities {
+            unicode: false,
+            color: false,
+            hyperlinks: false,
+        },
+        output: Output::Writer(Writer::new(buffer.clone())),
+        // `Enter`, then five invalid lines and a valid one, echoed as typed.
+        input: Input::Reader(Reader::new(Cursor::new(b"\ra\nb\nc\nd\ne\n80\n".to_vec()))),
+        ..RunOptions::default()
+    };
+    assert_eq!(menu.run_with(&opts).unwrap().attempt, Some(6));
+
+    // Plays the output up to the final clear on a screen of lines, moving up by `\x1b[nA` and clearing below by `\x1b[J`.
+    let output = buffer.text();
+    let mut rest = &output[..output.rfind("\x1b[").unwrap() - "\x1b[4A".len()];
+    let (mut screen, mut row) = (vec![String::new()], 0);
+    while let Some(c) = rest.chars().next() {
+        if let Some(sequence) = rest.strip_prefix("\x1b[") {
+            let end = sequence.find(|f: char| f.is_ascii_alphabetic()).unwrap();
+            match &sequence[end..=end] {
+                "A" => row -= sequence[..end].parse::<usize>().unwrap(),
+                "J" => screen.truncate(row),
+                _ => {}
+            }
+            screen.resize(row + 1, String::new());
+            rest = &sequence[end + 1..];
+            continue;
+        }
+        match c {
+            '\n' => {
+                row += 1;
+                screen.resize(row + 1, String::new());
+            }
+            c => screen[row].push(c),
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    // Only the message of the fifth rejection is left under the prompt.
+    let expected = [
+        "Main/Port/",
+        "       Port= ",
+        "Invalid entry: Enter a value. Type: U32",
+        "80",
+        "",
+    ];
+    assert_eq!(screen, expected);
+}
+
+#[test]
+fn run() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            sub_menu("Settings", vec![sub_menu("Network", vec![action("Proxy")])]),
+            action("Quit"),
+        ],
+        esc: true,
+    };
+    let opts = RunOptions::default();
+    // backing out of both `SubMenu`s, and once more in the root `Menu`, which stays open.
+    let keys = [
+        "Enter",
+        "Enter",
+        "Backspace",
+        "Left",
+        "Backspace",
+        "Down",
+        "Enter",
+    ];
+    let selection = menu.run_with_stdin_values(&opts, &keys).unwrap();
+    assert_eq!(selection.path, ["Main", "Quit"]);
+    // `Esc` exits from any depth, telling where.
+    let keys = ["Enter", "Enter", "Esc"];
+    assert_eq!(
+        menu.run_with_stdin_values(&opts, &keys),
+        Err(MenuError::Escaped(vec![0, 0]))
+    );
+    let keys = ["Enter", "Enter", "Backspace", "Backspace", "Esc"];
+    assert_eq!(
+        menu.run_with_stdin_values(&opts, &keys),
+        Err(MenuError::Exited)
+    );
+}
+
+#[test]
+fn run_script() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![action("Build"), action("Test"), action("Deploy")],
+        esc: true,
+    };
+    let path = std::env::temp_dir().join("rushterm-run-script.txt");
+    std::fs::write(&path, "# pick the second item\nDown\nEnter\n").unwrap();
+    let selection = menu.run_script(&RunOptions::default(), &path);
+    assert_eq!(selection.unwrap().name, "Test");
+    std::fs::write(&path, "Down\nDwn\n").unwrap();
+    let err = menu.run_script(&RunOptions::default(), &path).unwrap_err();
+    assert_eq!(err, MenuError::InvalidScriptLine(2));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn run_with_history() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![action("Build"), action("Test"), action("Deploy")],
+        esc: true,
+    };
+    let mut history = MenuState::default();
+    let opts = RunOptions {
+        script: Some(Script::new(["Down", "Down", "Enter", "Enter"])),
+        ..RunOptions::default()
+    };
+    assert_eq!(
+        menu.run_with_history(&opts, &mut history).unwrap().name,
+        "Deploy"
+    );
+    assert_eq!(history.hover(), 2);
+    // The next run starts on `Deploy`.
+    assert_eq!(
+        menu.run_with_history(&opts, &mut history).unwrap().name,
+        "Deploy"
+    );
+
+    // A hover left past the `Item`s, which shrank since, lands on the last one.
+    let mut menu = menu;
+    menu.items.truncate(2);
+    let opts = RunOptions {
+        script: Some(Script::new(["Enter"])),
+        ..RunOptions::default()
+    };
+    assert_eq!(
+        menu.run_with_history(&opts, &mut history).unwrap().name,
+        "Test"
+    );
+    assert_eq!(history.hover(), 1);
+}
+
+#[test]
+fn run_loop() {
+    let mut menu = Menu {
+        name: "Setup".to_string(),
+        exp: None,
+        items: vec![action("Advanced mode"), action("Install")],
+        esc: true,
+    };
+    let buffer = Buffer::default();
+    let opts = RunOptions {
+        capabilities: Capabilities {
+            unicode: false,
+            color: false,
+            hyperlinks: false,
+        },
+        output: Output::Writer(Writer::new(buffer.clone())),
+        script: Some(Script::new(["Enter", "Enter", "Esc"])),
+        ..RunOptions::default()
+    };
+    let mut selected = vec![];
+    menu.run_loop(&opts, |selection, menu| {
+        selected.push(selection.name.to_string());
+        // "Advanced mode" can't be selected twice, and brings its own `Item`.
+        if selection.name == "Advanced mode" {
+            menu.items[0] = action("Tune");
+        }
+    })
+    .unwrap();
+    assert_eq!(selected, ["Advanced mode", "Tune"]);
+    let output = buffer.text();
+    let runs: Vec<&str> = output.split("Setup/").skip(1).collect();
+    assert!(runs[0].contains("0.     Advanced mode"));
+    assert!(!runs[1].contains("Advanced mode"));
+    assert!(runs[1].contains("0.     Tune"));
+}
+
+#[test]
+fn run_at() {
+    let network = sub_menu("Network", vec![action("Proxy"), action("Wi-Fi")]);
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![sub_menu("Settings", vec![network, action("Display")])],
+        esc: true,
+    };
+    let run = |keys: &[&str]| {
+        let opts = RunOptions {
+            script: Some(Script::new(keys.iter().copied())),
+            ..RunOptions::default()
+        };
+        menu.run_at(&opts, &[0, 0]).map(|f| f.path.join("/"))
+    };
+    assert_eq!(
+        run(&["Down", "Enter"]).unwrap(),
+        "Main/Settings/Network/Wi-Fi"
+    );
+    // Going back lands on `Settings`, hovering `Network`, then on `Main`.
+    assert_eq!(
+        run(&["Backspace", "Down", "Enter"]).unwrap(),
+        "Main/Settings/Display"
+    );
+    let keys = ["Backspace", "Backspace", "Enter", "Enter", "Enter"];
+    assert_eq!(run(&keys).unwrap(), "Main/Settings/Network/Proxy");
+    // `Display` is not a `SubMenu` to open.
+    let opts = RunOptions::default();
+    let err = MenuError::InvalidPath(vec![0, 1]);
+    assert_eq!(menu.run_at(&opts, &[0, 1]), Err(err));
+}
+
+#[test]
+fn run_checklist() {
+    let menu = Menu {
+        name: "Tasks".to_string(),
+        exp: None,
+        items: vec![
+            checkable("Lint"),
+            checkable("Test"),
+            checkable("Build"),
+            action("Quit"),
+        ],
+        esc: true,
+    };
+    let run = |keys: &[&str]| {
+        let opts = RunOptions {
+            script: Some(Script::new(keys.iter().copied())),
+            ..RunOptions::default()
+        };
+        let selections = menu.run_checklist(&opts).unwrap();
+        selections
+            .into_iter()
+            .map(|f| f.name)
+            .collect::<Vec<String>>()
+    };
+    // `Space` on `Quit` checks nothing.
+    let keys = [
+        "Down", "Down", "Space", "Up", "Up", "Space", "Down", "Down", "Down", "Space", "Enter",
+    ];
+    assert_eq!(run(&keys), ["Quit"]);
+    let keys = ["Down", "Down", "Space", "Up", "Up", "Space", "Enter"];
+    assert_eq!(run(&keys), ["Lint", "Build"]);
+    // `Quit` is selected alone, though some are checked.
+    assert_eq!(run(&["Space", "3"]), ["Quit"]);
+}
+
+#[test]
+fn clear() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![Item::Action {
+            name: "Build".to_string(),
+            hotkey: None,
+            exp: None,
+            badges: Vec::new(),
+            confirm_phrase: None,
+            global_hotkey: None,
+            url: None,
+            checkable: false,
+        }],
+        esc: true,
+    };
+    let mut buffer = Buffer::default();
+    let opts = RunOptions {
+        output: Output::Writer(Writer::new(buffer.clone())),
+        ..RunOptions::default()
+    };
+    let frame = menu.render_to_string(&opts, 80);
+    write!(buffer, "{}", frame).unwrap();
+    menu.clear(&opts).unwrap();
+    let output = buffer.text();
+    // Moves up over every line of the frame and clears down from there.
+    let erase = format!("\u{1b}[{}A\u{1b}[J", frame.lines().count());
+    assert_eq!(output, frame + &erase);
+}
+
+#[test]
+fn rendered_height() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: Some("An explanation too long to fit in a single line of the terminal.".to_string()),
+        items: vec![action("Build"), action("Test"), action("Deploy")],
+        esc: true,
+    };
+    let opts = RunOptions {
+        exp_below: true,
+        ..RunOptions::default()
+    };
+    let text = menu.render_to_string(&opts, 30);
+    let displayed: usize = text
+        .lines()
+        .map(|f| f.chars().count().div_ceil(30).max(1))
+        .sum();
+    assert_eq!(menu.rendered_height(&opts, 30) as usize, displayed);
+    assert!(displayed > text.lines().count());
+}
+
+#[test]
+fn completions() {
+    let menu = Menu {
+        name: "tool".to_string(),
+        exp: None,
+        items: vec![
+            action("Build"),
+            Item::SubMenu {
+                name: "Settings".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                items: vec![
+                    action("Dark mode"),
+                    Item::SubMenu {
+                        name: "Network".to_string(),
+                        hotkey: None,
+                        exp: None,
+                        badges: Vec::new(),
+                        items: vec![action("Reset")],
+                    },
+                ],
+            },
+        ],
+        esc: true,
+    };
+    assert_eq!(
+        menu.completions(),
+        ["Build", "Settings/Dark mode", "Settings/Network/Reset"]
+    );
+
+    let script = menu.completion_script(Shell::Bash, "tool");
+    assert!(script.contains("'Settings/Dark mode'"));
+    assert!(script.ends_with("complete -F _tool tool\n"));
+    let script = menu.completion_script(Shell::Zsh, "tool");
+    assert!(script.starts_with("#compdef tool\n"));
+}
+
+#[test]
+fn tree_string() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            keyed("Action0", 'a'),
+            Item::SubMenu {
+                name: "Submenu0".to_string(),
+                hotkey: Some('s'),
+                exp: None,
+                badges: Vec::new(),
+                items: vec![
+                    action("Sub Action0"),
+                    Item::I32 {
+                        name: "Count".to_string(),
+                        hotkey: Some('c'),
+                        exp: None,
+                        badges: Vec::new(),
+                        skippable: false,
+                        current: None,
+                        confirm: false,
+                        step: None,
+                    },
+                ],
+            },
+            Item::Bool {
+                name: "Bool0".to_string(),
+                hotkey: Some('b'),
+                exp: None,
+                badges: Vec::new(),
+                current: None,
+            },
+        ],
+        esc: true,
+    };
+    let tree = "\
+Main
+├── (A) Action0 [Action]
+├── (S) Submenu0 [SubMenu]
+│   ├── Sub Action0 [Action]
+│   └── (C) Count [I32]
+└── (B) Bool0 [Bool]
+";
+    assert_eq!(menu.tree_string(), tree);
+}
+
+#[test]
+fn sort_by_key() {
+    let mut menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            explained("Deploy", "3"),
+            explained("Build", "1"),
+            explained("Test", "2"),
+        ],
+        esc: true,
+    };
+    menu.sort_by_key(|item| item.exp().map(str::to_string));
+    let names: Vec<&str> = menu.items.iter().map(Item::name).collect();
+    assert_eq!(names, ["Build", "Test", "Deploy"]);
+
+    let opts = RunOptions {
+        script: Some(Script::new(["Down", "Enter"])),
+        ..RunOptions::default()
+    };
+    let selection = menu.run_with(&opts).unwrap();
+    assert_eq!(selection.name, "Test");
+    assert_eq!(selection.indexes, vec![1]);
+}
+
+#[test]
+fn validate_all() {
+    // the hotkeys of the example menu.
+    let mut menu = Menu {
+        name: "My Main Menu".to_string(),
+        exp: None,
+        items: vec![
+            keyed("Action0", 'a'),
+            action("Action1"),
+            Item::SubMenu {
+                name: "Submenu0".to_string(),
+                hotkey: Some('s'),
+                exp: None,
+                badges: Vec::new(),
+                items: vec![keyed("Sub Action0", 'a'), keyed("Sub Action1", 'c')],
+            },
+            keyed("Bool0", 'b'),
+            keyed("Char0", 'c'),
+        ],
+        esc: true,
+    };
+    // hotkeys of different levels don't collide.
+    assert!(menu.validate_all().is_empty());
+
+    menu.items.push(keyed("Clear", 'c'));
+    menu.items.push(keyed("First", '1'));
+    if let Item::SubMenu { items, .. } = &mut menu.items[2] {
+        items.clear();
+    }
+    let warnings = menu.validate_all();
+    let paths: Vec<&[usize]> = warnings.iter().map(|f| f.path.as_slice()).collect();
+    assert_eq!(paths, [&[5][..], &[6], &[2]]);
+    assert_eq!(
+        warnings[0],
+        MenuWarning {
+            path: vec![5],
+            description:
+                "hotkey 'c' is already used by item at index 4, so it is unreachable by hotkey"
+                    .to_string(),
+        }
+    );
+    assert!(warnings[1].description.contains("index number"));
+    assert_eq!(warnings[2].description, "sub menu has no items");
+}
+
+#[test]
+fn available_hotkeys() {
+    let menu = Menu {
+        name: "My Main Menu".to_string(),
+        exp: None,
+        items: vec![
+            keyed("Action0", 'a'),
+            action("Action1"),
+            Item::SubMenu {
+                name: "Submenu0".to_string(),
+                hotkey: Some('S'),
+                exp: None,
+                badges: Vec::new(),
+                items: vec![keyed("Sub Action0", 'a'), keyed("Sub Action1", 'c')],
+            },
+        ],
+        esc: true,
+    };
+    let free = menu.available_hotkeys(&[]).unwrap();
+    assert!(!free.contains(&'a') && !free.contains(&'s') && free.contains(&'c'));
+    // `0`, `1` and `2` are index numbers.
+    assert_eq!(free[free.len() - 7..], ['3', '4', '5', '6', '7', '8', '9']);
+    let free = menu.available_hotkeys(&[2]).unwrap();
+    assert!(!free.contains(&'a') && !free.contains(&'c') && free.contains(&'s'));
+    assert_eq!(
+        menu.available_hotkeys(&[0]),
+        Err(MenuError::InvalidPath(vec![0]))
+    );
+}
+
+#[test]
+fn select_by_path() {
+    let menu = Menu {
+        name: "Main".to_string(),
+        exp: None,
+        items: vec![
+            action("Save"),
+            Item::SubMenu {
+                name: "Tools".to_string(),
+                hotkey: None,
+                exp: None,
+                badges: Vec::new(),
+                items: vec![
+                    action("Lint"),
+                    Item::U32 {
+                        name: "Port".to_string(),
+                        hotkey: None,
+                        exp: None,
+                        badges: Vec::new(),
+                        skippable: false,
+                        current: None,
+                        confirm: false,
+                        step: None,
+                    },
+                ],
+            },
+        ],
+        esc: true,
+    };
+    let save = menu.select_by_path(&[0], None).unwrap();
+    assert_eq!((save.name.as_str(), save.value), ("Save", None));
+
+    let lint = menu.select_by_path(&[1, 0], None).unwrap();
+    assert_eq!(lint.path, ["Main", "Tools", "Lint"]);
+    assert_eq!(lint.indexes, [1, 0]);
+
+    let port = menu
+        .select_by_path(&[1, 1], Some(Value::U32(8080)))
+        .unwrap();
+    assert_eq!(port.value, Some(Value::U32(8080)));
+    assert_eq!(
+        menu.select_by_path(&[1, 1], None),
+        Err(MenuError::MissingValue(vec![1, 1]))
+    );
+    assert_eq!(
+        menu.select_by_path(&[1], None),
+        Err(MenuError::NotSelectable(vec![1]))
+    );
+    assert_eq!(
+        menu.select_by_path(&[1, 2], None),
+        Err(MenuError::InvalidPath(vec![1, 2]))
+    );
+}
+
+#[test]
+fn send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Item>();
+    assert_send_sync::<Menu>();
+}